@@ -0,0 +1,130 @@
+use crate::validation::ValidationErrors;
+use serde::{Deserialize, Serialize};
+
+/// 1つのイベントに追加できる添付ファイルの最大数
+pub const MAX_ATTACHMENTS: usize = 25;
+
+/// イベントの添付ファイル（Google ドライブのファイルへのリンク）
+///
+/// 作成・更新時は `file_url` が必須です。`icon_link` と `file_id` は読み取り専用で、APIが設定します。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub file_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_link: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+}
+
+impl Attachment {
+    pub fn new(file_url: impl Into<String>) -> Self {
+        Attachment {
+            file_url: file_url.into(),
+            title: None,
+            mime_type: None,
+            icon_link: None,
+            file_id: None,
+        }
+    }
+
+    /// ドライブのファイルIDから添付ファイルを作成します
+    pub fn drive_file(file_id: impl Into<String>) -> Self {
+        let file_id = file_id.into();
+        Attachment {
+            file_id: Some(file_id.clone()),
+            ..Attachment::new(format!("https://drive.google.com/open?id={}", file_id))
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// 同じファイルを指しているかを返します。ファイルIDがない場合はURLで比較します
+    pub fn is_same_file(&self, other: &Attachment) -> bool {
+        match (&self.file_id, &other.file_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.file_url == other.file_url,
+        }
+    }
+}
+
+/// 添付ファイルの件数とURLを検証します
+pub(crate) fn validate_attachments(attachments: &[Attachment], errors: &mut ValidationErrors) {
+    if attachments.len() > MAX_ATTACHMENTS {
+        errors.add(
+            "attachments",
+            format!(
+                "添付ファイルは{}件までです: {}",
+                MAX_ATTACHMENTS,
+                attachments.len()
+            ),
+        );
+    }
+    for (i, attachment) in attachments.iter().enumerate() {
+        if attachment.file_url.trim().is_empty() {
+            errors.add(
+                format!("attachments[{}].fileUrl", i),
+                "添付ファイルのURLが必要です",
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        let json = r#"{
+            "fileUrl": "https://drive.google.com/open?id=abc",
+            "title": "議事録",
+            "mimeType": "application/vnd.google-apps.document",
+            "iconLink": "https://drive-thirdparty.googleusercontent.com/16/type/application/vnd.google-apps.document",
+            "fileId": "abc"
+        }"#;
+        let attachment: Attachment = serde_json::from_str(json).unwrap();
+        assert_eq!(attachment.file_id.as_deref(), Some("abc"));
+        assert!(attachment.is_same_file(&Attachment::drive_file("abc")));
+        assert!(!attachment.is_same_file(&Attachment::drive_file("xyz")));
+
+        let created = Attachment::drive_file("abc").with_title("アジェンダ");
+        assert_eq!(
+            serde_json::to_value(&created).unwrap(),
+            serde_json::json!({
+                "fileUrl": "https://drive.google.com/open?id=abc",
+                "title": "アジェンダ",
+                "fileId": "abc"
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_attachments() {
+        let attachments: Vec<_> = (0..MAX_ATTACHMENTS)
+            .map(|i| Attachment::drive_file(i.to_string()))
+            .collect();
+        let mut errors = ValidationErrors::new();
+        validate_attachments(&attachments, &mut errors);
+        assert!(errors.is_empty());
+
+        let mut attachments = attachments;
+        attachments.push(Attachment::new(""));
+        validate_attachments(&attachments, &mut errors);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains("attachments"));
+        assert!(errors.contains("attachments[25].fileUrl"));
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// 招待への返答状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResponseStatus {
+    NeedsAction,
+    Declined,
+    Tentative,
+    Accepted,
+}
+
+/// イベントの参加者
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attendee {
+    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// 任意参加の場合は true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<ResponseStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 会議室などのリソースの場合は true（読み取り専用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<bool>,
+    /// 主催者の場合は true（読み取り専用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organizer: Option<bool>,
+    /// このイベントを取得したユーザー自身の場合は true（読み取り専用）
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub is_self: Option<bool>,
+}
+
+impl Attendee {
+    pub fn new(email: impl Into<String>) -> Self {
+        Attendee {
+            email: email.into(),
+            display_name: None,
+            optional: None,
+            response_status: None,
+            comment: None,
+            resource: None,
+            organizer: None,
+            is_self: None,
+        }
+    }
+
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = Some(true);
+        self
+    }
+}
+
+/// イベントの主催者
+///
+/// 通常は読み取り専用ですが、`events.import` では外部のカレンダーの主催者をそのまま保持できます。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Organizer {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// 主催者がこのイベントを取得したユーザー自身の場合は true（読み取り専用）
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub is_self: Option<bool>,
+}
+
+impl Organizer {
+    pub fn new(email: impl Into<String>) -> Self {
+        Organizer {
+            id: None,
+            email: Some(email.into()),
+            display_name: None,
+            is_self: None,
+        }
+    }
+
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attendee_serialization() {
+        let attendee = Attendee::new("alice@example.com")
+            .with_display_name("Alice")
+            .optional();
+        assert_eq!(
+            serde_json::to_value(&attendee).unwrap(),
+            serde_json::json!({
+                "email": "alice@example.com",
+                "displayName": "Alice",
+                "optional": true
+            })
+        );
+
+        let parsed: Attendee = serde_json::from_str(
+            r#"{"email": "bob@example.com", "self": true, "responseStatus": "needsAction"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.is_self, Some(true));
+        assert_eq!(parsed.response_status, Some(ResponseStatus::NeedsAction));
+    }
+}
@@ -0,0 +1,528 @@
+use crate::calendar_client::CalendarClient;
+use crate::error::{GCalError, Result};
+use crate::event::Event;
+
+/// 1回のバッチリクエストに含められる最大件数
+pub const MAX_BATCH_SIZE: usize = 50;
+
+/// バッチに積む個々の操作
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    Insert {
+        calendar_id: String,
+        event: Box<Event>,
+    },
+    Patch {
+        calendar_id: String,
+        event_id: String,
+        patch: serde_json::Value,
+    },
+    Delete {
+        calendar_id: String,
+        event_id: String,
+    },
+    Get {
+        calendar_id: String,
+        event_id: String,
+    },
+}
+
+impl BatchOperation {
+    fn method(&self) -> &'static str {
+        match self {
+            BatchOperation::Insert { .. } => "POST",
+            BatchOperation::Patch { .. } => "PATCH",
+            BatchOperation::Delete { .. } => "DELETE",
+            BatchOperation::Get { .. } => "GET",
+        }
+    }
+
+    fn path(&self) -> String {
+        match self {
+            BatchOperation::Insert { calendar_id, .. } => {
+                format!(
+                    "calendars/{}/events?conferenceDataVersion=1&supportsAttachments=true",
+                    calendar_id
+                )
+            }
+            BatchOperation::Patch {
+                calendar_id,
+                event_id,
+                ..
+            } => format!(
+                "calendars/{}/events/{}?conferenceDataVersion=1&supportsAttachments=true",
+                calendar_id, event_id
+            ),
+            BatchOperation::Delete {
+                calendar_id,
+                event_id,
+            }
+            | BatchOperation::Get {
+                calendar_id,
+                event_id,
+            } => format!("calendars/{}/events/{}", calendar_id, event_id),
+        }
+    }
+
+    fn body(&self) -> Result<Option<String>> {
+        match self {
+            BatchOperation::Insert { event, .. } => {
+                event.validate_new().map_err(GCalError::ValidationError)?;
+                Ok(Some(serde_json::to_string(event)?))
+            }
+            BatchOperation::Patch { patch, .. } => Ok(Some(serde_json::to_string(patch)?)),
+            BatchOperation::Delete { .. } | BatchOperation::Get { .. } => Ok(None),
+        }
+    }
+}
+
+/// Google Calendar の batch エンドポイントへまとめて送るリクエスト
+///
+/// 50件を超える操作は自動的に分割して送信します。結果は積んだ順に返り、
+/// 削除の成功は `Ok(None)` で表されます。
+pub struct BatchRequest<'a> {
+    client: &'a CalendarClient,
+    operations: Vec<BatchOperation>,
+}
+
+impl<'a> BatchRequest<'a> {
+    pub fn new(client: &'a CalendarClient) -> Self {
+        BatchRequest {
+            client,
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, operation: BatchOperation) -> &mut Self {
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn insert(&mut self, calendar_id: &str, event: &Event) -> &mut Self {
+        self.push(BatchOperation::Insert {
+            calendar_id: calendar_id.to_string(),
+            event: Box::new(event.clone()),
+        })
+    }
+
+    pub fn patch(
+        &mut self,
+        calendar_id: &str,
+        event_id: &str,
+        patch: impl serde::Serialize,
+    ) -> Result<&mut Self> {
+        let patch = serde_json::to_value(patch)?;
+        Ok(self.push(BatchOperation::Patch {
+            calendar_id: calendar_id.to_string(),
+            event_id: event_id.to_string(),
+            patch,
+        }))
+    }
+
+    pub fn delete(&mut self, calendar_id: &str, event_id: &str) -> &mut Self {
+        self.push(BatchOperation::Delete {
+            calendar_id: calendar_id.to_string(),
+            event_id: event_id.to_string(),
+        })
+    }
+
+    pub fn get(&mut self, calendar_id: &str, event_id: &str) -> &mut Self {
+        self.push(BatchOperation::Get {
+            calendar_id: calendar_id.to_string(),
+            event_id: event_id.to_string(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// 積んだ操作を送信し、操作ごとの結果を返します
+    ///
+    /// 分割した一部のリクエストが通信エラーになった場合、そのリクエストに含まれる
+    /// 操作はすべて同じエラー内容で失敗します。
+    pub async fn execute(self) -> Vec<Result<Option<Event>>> {
+        let mut results = Vec::with_capacity(self.operations.len());
+        for chunk in self.operations.chunks(MAX_BATCH_SIZE) {
+            results.extend(self.execute_chunk(chunk).await);
+        }
+        results
+    }
+
+    async fn execute_chunk(&self, chunk: &[BatchOperation]) -> Vec<Result<Option<Event>>> {
+        // バリデーションに失敗した操作は送信せずにエラーとする
+        let mut results: Vec<Option<Result<Option<Event>>>> = Vec::with_capacity(chunk.len());
+        let mut parts = Vec::new();
+        for (index, operation) in chunk.iter().enumerate() {
+            match operation.body() {
+                Ok(body) => {
+                    parts.push(RequestPart {
+                        content_id: format!("item-{}", index),
+                        method: operation.method(),
+                        path: operation.path(),
+                        body,
+                    });
+                    results.push(None);
+                }
+                Err(e) => results.push(Some(Err(e))),
+            }
+        }
+
+        if !parts.is_empty() {
+            let boundary = format!("batch_{:032x}", rand::random::<u128>());
+            let body = encode_multipart(&parts, &boundary, self.client.api_path_prefix());
+            match self.client.send_batch(body, &boundary).await {
+                Ok((content_type, response)) => {
+                    match parse_multipart_response(&content_type, &response) {
+                        Ok(responses) => {
+                            for response in responses {
+                                let slot = response
+                                    .content_id
+                                    .as_deref()
+                                    .and_then(|id| id.strip_prefix("response-item-"))
+                                    .and_then(|n| n.parse::<usize>().ok())
+                                    .and_then(|n| results.get_mut(n));
+                                if let Some(slot @ None) = slot {
+                                    *slot = Some(response.into_event());
+                                }
+                            }
+                        }
+                        Err(e) => fill_pending(&mut results, &e),
+                    }
+                }
+                Err(e) => fill_pending(&mut results, &e),
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|r| {
+                r.unwrap_or_else(|| {
+                    Err(GCalError::Other(
+                        "バッチレスポンスに対応する結果がありません".to_string(),
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+fn fill_pending(results: &mut [Option<Result<Option<Event>>>], error: &GCalError) {
+    for slot in results.iter_mut().filter(|r| r.is_none()) {
+        *slot = Some(Err(GCalError::Other(format!(
+            "バッチリクエストに失敗しました: {}",
+            error
+        ))));
+    }
+}
+
+struct RequestPart {
+    content_id: String,
+    method: &'static str,
+    path: String,
+    body: Option<String>,
+}
+
+fn encode_multipart(parts: &[RequestPart], boundary: &str, path_prefix: &str) -> String {
+    let mut out = String::new();
+    for part in parts {
+        out.push_str(&format!("--{}\r\n", boundary));
+        out.push_str("Content-Type: application/http\r\n");
+        out.push_str(&format!("Content-ID: <{}>\r\n\r\n", part.content_id));
+        out.push_str(&format!(
+            "{} {}/{} HTTP/1.1\r\n",
+            part.method, path_prefix, part.path
+        ));
+        match &part.body {
+            Some(body) => {
+                out.push_str("Content-Type: application/json\r\n\r\n");
+                out.push_str(body);
+                out.push_str("\r\n");
+            }
+            None => out.push_str("\r\n"),
+        }
+    }
+    out.push_str(&format!("--{}--\r\n", boundary));
+    out
+}
+
+/// バッチレスポンスに含まれる個々のHTTPレスポンス
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResponsePart {
+    pub content_id: Option<String>,
+    pub status: u16,
+    pub body: String,
+}
+
+impl BatchResponsePart {
+    fn into_event(self) -> Result<Option<Event>> {
+        if !(200..300).contains(&self.status) {
+            return Err(GCalError::ApiError {
+                status: self.status,
+                message: self.body,
+            });
+        }
+        if self.body.trim().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&self.body)?))
+    }
+}
+
+/// Content-Type ヘッダーから multipart の境界文字列を取り出します
+fn boundary_from_content_type(content_type: &str) -> Option<&str> {
+    content_type
+        .split(';')
+        .map(str::trim)
+        .find_map(|p| p.strip_prefix("boundary="))
+        .map(|b| b.trim_matches('"'))
+}
+
+/// ヘッダー部と本文を空行で分割します
+fn split_headers(text: &str) -> (&str, &str) {
+    for sep in ["\r\n\r\n", "\n\n"] {
+        if let Some(pos) = text.find(sep) {
+            return (&text[..pos], &text[pos + sep.len()..]);
+        }
+    }
+    (text, "")
+}
+
+fn header_value<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+    headers.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
+
+/// multipart/mixed の各パートを返します（境界行と終端は取り除かれます）
+fn split_parts<'a>(body: &'a str, boundary: &str) -> Vec<&'a str> {
+    let delimiter = format!("--{}", boundary);
+    body.split(delimiter.as_str())
+        .skip(1)
+        .take_while(|part| !part.starts_with("--"))
+        .map(|part| part.trim_start_matches(['\r', '\n']))
+        .collect()
+}
+
+/// バッチレスポンスを個々のレスポンスに分解します
+pub fn parse_multipart_response(content_type: &str, body: &str) -> Result<Vec<BatchResponsePart>> {
+    let boundary = boundary_from_content_type(content_type).ok_or_else(|| {
+        GCalError::Other(format!(
+            "バッチレスポンスに境界文字列がありません: {}",
+            content_type
+        ))
+    })?;
+
+    let mut parts = Vec::new();
+    for part in split_parts(body, boundary) {
+        let (outer_headers, http) = split_headers(part);
+        let content_id = header_value(outer_headers, "Content-ID")
+            .map(|id| id.trim_start_matches('<').trim_end_matches('>').to_string());
+        let (status_and_headers, inner_body) = split_headers(http);
+        let status_line = status_and_headers.lines().next().unwrap_or_default();
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse::<u16>().ok())
+            .ok_or_else(|| {
+                GCalError::Other(format!("ステータス行を解析できません: {}", status_line))
+            })?;
+        parts.push(BatchResponsePart {
+            content_id,
+            status,
+            body: inner_body.trim_end().to_string(),
+        });
+    }
+    Ok(parts)
+}
+
+/// テスト用に、バッチリクエストを各操作が成功したものとして応答します
+#[cfg(test)]
+pub(crate) fn mock_batch_response(body: &str, boundary: &str) -> (String, String) {
+    let response_boundary = "batch_mock_response";
+    let mut out = String::new();
+    for part in split_parts(body, boundary) {
+        let (outer_headers, http) = split_headers(part);
+        let content_id = header_value(outer_headers, "Content-ID")
+            .unwrap_or_default()
+            .trim_start_matches('<')
+            .trim_end_matches('>');
+        let (request_line_and_headers, request_body) = split_headers(http);
+        let method = request_line_and_headers
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+        let (status, response_body) = match method {
+            "DELETE" => ("204 No Content", String::new()),
+            "GET" => (
+                "200 OK",
+                serde_json::to_string(&crate::mock::test_utils::create_test_event()).unwrap(),
+            ),
+            _ if request_body.contains("\"summary\":\"fail\"") => {
+                ("400 Bad Request", "{\"error\":{\"code\":400}}".to_string())
+            }
+            _ => ("200 OK", request_body.trim_end().to_string()),
+        };
+        out.push_str(&format!(
+            "--{}\r\nContent-Type: application/http\r\nContent-ID: <response-{}>\r\n\r\n\
+             HTTP/1.1 {}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{}\r\n",
+            response_boundary, content_id, status, response_body
+        ));
+    }
+    out.push_str(&format!("--{}--\r\n", response_boundary));
+    (
+        format!("multipart/mixed; boundary={}", response_boundary),
+        out,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::HttpClient;
+    use crate::mock::test_utils::create_test_event;
+
+    #[test]
+    fn test_encode_multipart() {
+        let parts = vec![
+            RequestPart {
+                content_id: "item-0".to_string(),
+                method: "POST",
+                path: "calendars/primary/events".to_string(),
+                body: Some("{\"summary\":\"a\"}".to_string()),
+            },
+            RequestPart {
+                content_id: "item-1".to_string(),
+                method: "DELETE",
+                path: "calendars/primary/events/abc".to_string(),
+                body: None,
+            },
+        ];
+        let body = encode_multipart(&parts, "b", "/calendar/v3");
+        assert!(body.starts_with("--b\r\nContent-Type: application/http\r\nContent-ID: <item-0>"));
+        assert!(body.contains("POST /calendar/v3/calendars/primary/events HTTP/1.1\r\n"));
+        assert!(body.contains("DELETE /calendar/v3/calendars/primary/events/abc HTTP/1.1\r\n"));
+        assert!(body.ends_with("--b--\r\n"));
+    }
+
+    #[test]
+    fn test_request_lines() {
+        let client = CalendarClient::new(HttpClient::mock().expect("failed to create mock client"));
+        let mut batch = client.batch();
+        batch
+            .insert("primary", &create_test_event())
+            .delete("primary", "abc");
+        batch
+            .patch("primary", "abc", serde_json::json!({"location": "東京"}))
+            .unwrap();
+        let parts: Vec<_> = batch
+            .operations
+            .iter()
+            .enumerate()
+            .map(|(index, operation)| RequestPart {
+                content_id: format!("item-{}", index),
+                method: operation.method(),
+                path: operation.path(),
+                body: operation.body().unwrap(),
+            })
+            .collect();
+        let body = encode_multipart(&parts, "b", "/calendar/v3");
+        // 会議情報と添付ファイルを書き込めるように、作成・更新ではクエリを付ける
+        assert!(body.contains(
+            "POST /calendar/v3/calendars/primary/events?conferenceDataVersion=1&supportsAttachments=true HTTP/1.1\r\n"
+        ));
+        assert!(body.contains(
+            "PATCH /calendar/v3/calendars/primary/events/abc?conferenceDataVersion=1&supportsAttachments=true HTTP/1.1\r\n"
+        ));
+        assert!(body.contains("DELETE /calendar/v3/calendars/primary/events/abc HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_parse_multipart_response() {
+        let body = "--batch_abc\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item-0>\r\n\r\n\
+            HTTP/1.1 200 OK\r\n\
+            Content-Type: application/json; charset=UTF-8\r\n\r\n\
+            {\"id\": \"e1\", \"summary\": \"a\"}\r\n\
+            --batch_abc\r\n\
+            Content-Type: application/http\r\n\
+            Content-ID: <response-item-1>\r\n\r\n\
+            HTTP/1.1 404 Not Found\r\n\r\n\
+            {\"error\": {\"code\": 404}}\r\n\
+            --batch_abc--\r\n";
+        let parts =
+            parse_multipart_response("multipart/mixed; boundary=\"batch_abc\"", body).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].content_id.as_deref(), Some("response-item-0"));
+        assert_eq!(parts[0].status, 200);
+        assert_eq!(parts[1].status, 404);
+
+        let event = parts[0].clone().into_event().unwrap().unwrap();
+        assert_eq!(event.id.as_deref(), Some("e1"));
+        assert!(matches!(
+            parts[1].clone().into_event(),
+            Err(GCalError::ApiError { status: 404, .. })
+        ));
+
+        assert!(parse_multipart_response("multipart/mixed", body).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_splits_and_keeps_order() {
+        let client = CalendarClient::new(HttpClient::mock().expect("failed to create mock client"));
+        let mut batch = client.batch();
+        for i in 0..120 {
+            let mut event = create_test_event();
+            event.summary = Some(format!("event-{}", i));
+            batch.insert("primary", &event);
+        }
+        batch.delete("primary", "abc");
+        batch.get("primary", "abc");
+        assert_eq!(batch.len(), 122);
+
+        let results = batch.execute().await;
+        assert_eq!(results.len(), 122);
+        for (i, result) in results.iter().take(120).enumerate() {
+            let event = result.as_ref().unwrap().as_ref().unwrap();
+            assert_eq!(event.summary, Some(format!("event-{}", i)));
+        }
+        assert!(results[120].as_ref().unwrap().is_none());
+        assert!(results[121].as_ref().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_per_item_errors() {
+        let client = CalendarClient::new(HttpClient::mock().expect("failed to create mock client"));
+        let mut invalid = create_test_event();
+        invalid.summary = None;
+        let mut failing = create_test_event();
+        failing.summary = Some("fail".to_string());
+
+        let mut batch = client.batch();
+        batch
+            .insert("primary", &invalid)
+            .insert("primary", &failing)
+            .insert("primary", &create_test_event());
+        batch
+            .patch("primary", "abc", serde_json::json!({"location": "東京"}))
+            .unwrap();
+
+        let results = batch.execute().await;
+        assert!(matches!(results[0], Err(GCalError::ValidationError(_))));
+        assert!(matches!(
+            results[1],
+            Err(GCalError::ApiError { status: 400, .. })
+        ));
+        assert!(results[2].is_ok());
+        let patched = results[3].as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(patched.location.as_deref(), Some("東京"));
+    }
+}
@@ -1,23 +1,69 @@
+use crate::attachment::{Attachment, MAX_ATTACHMENTS};
+use crate::batch::BatchRequest;
+use crate::calendar_list::{CalendarList, CalendarListEntry};
+use crate::channel::{Channel, StopChannelRequest};
+use crate::colors::Colors;
+use crate::conference::{ConferenceData, ConferenceStatusCode};
+use crate::config::DefaultTimeZone;
 use crate::error::{GCalError, Result};
-use crate::event::Event;
+use crate::event::{Event, EventDateTime};
+use crate::event_builder::EventBuilder;
+use crate::event_csv::{CsvError, CsvImportReport, CsvImporter, CsvRowError};
+use crate::event_id::{generate_event_id, validate_event_id};
+use crate::event_import::ImportEventOptions;
+use crate::event_list::{EventList, ListEventsParams};
+use crate::event_move::{MoveFailure, MoveProgress, MoveReport, SendUpdates};
+use crate::freebusy::{FreeBusyRequest, FreeBusyResponse};
 use crate::http_client::HttpClient;
+use crate::ical::{IcalError, IcalImporter, IcsImportReport, ImportFailure};
+use crate::patch::EventPatch;
+use crate::scheduling::{self, CandidateSlot, SlotSearch};
+use crate::settings::{self as user_settings, Setting, SettingList, UserSettings};
+use chrono_tz::Tz;
 use serde_json;
+use tokio::sync::OnceCell;
+
+/// `modify_event` が競合時に読み込みからやり直す最大回数
+pub const MAX_MODIFY_ATTEMPTS: usize = 5;
 
 pub struct CalendarClient {
     http_client: HttpClient,
+    /// 解決済みのイベントの既定のタイムゾーン
+    default_time_zone: OnceCell<Tz>,
 }
 
 impl CalendarClient {
     pub fn new(http_client: HttpClient) -> Self {
-        CalendarClient { http_client }
+        let default_time_zone = match http_client.default_time_zone() {
+            DefaultTimeZone::Utc => OnceCell::new_with(Some(Tz::UTC)),
+            DefaultTimeZone::Fixed(tz) => OnceCell::new_with(Some(tz)),
+            DefaultTimeZone::CalendarOwner => OnceCell::new(),
+        };
+        CalendarClient {
+            http_client,
+            default_time_zone,
+        }
     }
 
+    /// イベントを作成
+    ///
+    /// `event.id` を指定した場合はそのIDで作成します。同じIDのイベントが既に存在する
+    /// （タイムアウト後の再送などで作成済みの）場合は、既存のイベントを取得して返します。
+    /// 時刻指定の開始・終了にタイムゾーンがない場合は、クライアントの既定のタイムゾーンを設定します。
     pub async fn create_event(&self, calendar_id: &str, event: &Event) -> Result<Event> {
+        let event = &self.with_default_time_zone(event).await?;
+
         // バリデーション
-        event.validate().map_err(GCalError::ValidationError)?;
+        event.validate_new().map_err(GCalError::ValidationError)?;
+        if let Some(id) = &event.id {
+            validate_event_id(id).map_err(|e| GCalError::field_validation("id", e))?;
+        }
 
-        // イベント作成のパスを構築
-        let path = format!("calendars/{}/events", calendar_id);
+        // イベント作成のパスを構築（会議データと添付ファイルを送受信するためのパラメータを付与）
+        let path = format!(
+            "calendars/{}/events?conferenceDataVersion=1&supportsAttachments=true",
+            calendar_id
+        );
 
         // Debug: Print the event JSON
         let event_json = serde_json::to_string_pretty(event).unwrap();
@@ -25,9 +71,17 @@ impl CalendarClient {
 
         // HTTPクライアントでPOST
         #[cfg(test)]
-        let resp = self.http_client.mock_post_response(&path, event).await?;
+        let resp = self.http_client.mock_post_response(&path, event).await;
         #[cfg(not(test))]
-        let resp = self.http_client.post(&path, event).await?;
+        let resp = self.http_client.post(&path, event).await;
+
+        let resp = match (resp, &event.id) {
+            (Ok(resp), _) => resp,
+            (Err(GCalError::AlreadyExists(_)), Some(id)) => {
+                return self.get_event(calendar_id, id).await;
+            }
+            (Err(e), _) => return Err(e),
+        };
 
         // レスポンスをEvent構造体にデシリアライズ
         let created_event: Event = serde_json::from_str(&resp)?;
@@ -35,6 +89,557 @@ impl CalendarClient {
         Ok(created_event)
     }
 
+    /// IDが未指定の場合はクライアント側で生成してからイベントを作成
+    ///
+    /// 返されたイベントのIDを使って再送すれば、重複して作成されることはありません。
+    pub async fn create_event_idempotent(&self, calendar_id: &str, event: &Event) -> Result<Event> {
+        if event.id.is_some() {
+            return self.create_event(calendar_id, event).await;
+        }
+        let mut event = event.clone();
+        event.id = Some(generate_event_id());
+        self.create_event(calendar_id, &event).await
+    }
+
+    /// 他のカレンダーのイベントをコピーとしてインポート
+    ///
+    /// `events.import` を使用するため、`i_cal_uid` が必須で、同じ iCalUID のイベントは重複せず更新されます。
+    /// `create_event` と異なり主催者と iCalUID が外部のカレンダーの値のまま保持され、招待は送信されません。
+    /// 会議データと添付ファイルも読み書きします（conferenceDataVersion=1、supportsAttachments=true）。
+    pub async fn import_event(&self, calendar_id: &str, event: &Event) -> Result<Event> {
+        let options = ImportEventOptions::new()
+            .with_conference_data_version(1)
+            .with_supports_attachments(true);
+        self.import_event_with_options(calendar_id, event, &options)
+            .await
+    }
+
+    /// クエリパラメータを指定してイベントをインポート
+    pub async fn import_event_with_options(
+        &self,
+        calendar_id: &str,
+        event: &Event,
+        options: &ImportEventOptions,
+    ) -> Result<Event> {
+        if event.i_cal_uid.as_deref().map_or(true, str::is_empty) {
+            return Err(GCalError::field_validation(
+                "iCalUID",
+                "インポートには iCalUID が必要です",
+            ));
+        }
+        options
+            .validate()
+            .map_err(|e| GCalError::field_validation("conferenceDataVersion", e))?;
+        event.validate().map_err(GCalError::ValidationError)?;
+
+        let query = options.to_query_string();
+        let path = if query.is_empty() {
+            format!("calendars/{}/events/import", calendar_id)
+        } else {
+            format!("calendars/{}/events/import?{}", calendar_id, query)
+        };
+        #[cfg(test)]
+        let resp = self.http_client.mock_post_response(&path, event).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.post(&path, event).await?;
+
+        Ok(serde_json::from_str(&resp)?)
+    }
+
+    /// iCalendar テキストに含まれる VEVENT をすべてインポート
+    ///
+    /// 変換できなかった VEVENT と、APIがインポートを拒否した VEVENT は `failures` に含めて
+    /// 残りの処理を続けます。テキスト自体を解析できない場合のみエラーを返します。
+    pub async fn import_ics(
+        &self,
+        calendar_id: &str,
+        ics: &str,
+        importer: &IcalImporter,
+    ) -> Result<IcsImportReport> {
+        let conversion = importer.convert(ics).map_err(|e| match e {
+            IcalError::Parse { .. } => GCalError::validation(e.to_string()),
+            e => GCalError::Other(e.to_string()),
+        })?;
+
+        let mut report = IcsImportReport {
+            imported: Vec::new(),
+            failures: conversion.failures,
+        };
+        for (event, &index) in conversion.events.iter().zip(&conversion.indices) {
+            match self.import_event(calendar_id, event).await {
+                Ok(imported) => report.imported.push(imported),
+                Err(e) => report.failures.push(ImportFailure {
+                    index,
+                    uid: event.i_cal_uid.clone(),
+                    summary: event.summary.clone(),
+                    message: e.to_string(),
+                }),
+            }
+        }
+        report.failures.sort_by_key(|f| f.index);
+        Ok(report)
+    }
+
+    /// CSVの各行をイベントとして作成
+    ///
+    /// 変換できなかった行と、作成に失敗した行は `errors` に含めて残りの処理を続けます。
+    /// イベントIDの列を対応付けておけば、同じCSVを再度取り込んでも重複して作成されません。
+    pub async fn import_csv(
+        &self,
+        calendar_id: &str,
+        csv: &str,
+        importer: &CsvImporter,
+    ) -> Result<CsvImportReport> {
+        let import = importer.convert(csv).map_err(|e| match e {
+            CsvError::MissingColumn(_) => GCalError::validation(e.to_string()),
+            e => GCalError::Other(e.to_string()),
+        })?;
+
+        let mut report = CsvImportReport {
+            created: Vec::new(),
+            errors: import.errors,
+        };
+        for (event, &row) in import.events.iter().zip(&import.rows) {
+            match self.create_event(calendar_id, event).await {
+                Ok(created) => report.created.push(created),
+                Err(e) => report.errors.push(CsvRowError {
+                    row,
+                    errors: match e {
+                        GCalError::ValidationError(errors) => errors,
+                        e => e.to_string().into(),
+                    },
+                }),
+            }
+        }
+        report.errors.sort_by_key(|e| e.row);
+        Ok(report)
+    }
+
+    /// イベント全体を置き換えて更新
+    pub async fn update_event(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        event: &Event,
+    ) -> Result<Event> {
+        self.put_event(calendar_id, event_id, event, None).await
+    }
+
+    /// `etag` が現在のETagと一致する場合のみイベント全体を置き換えて更新
+    ///
+    /// 取得後に他のクライアントが更新していた場合は `GCalError::PreconditionFailed` を返します。
+    pub async fn update_event_if_match(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        event: &Event,
+        etag: &str,
+    ) -> Result<Event> {
+        self.put_event(calendar_id, event_id, event, Some(etag))
+            .await
+    }
+
+    async fn put_event(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        event: &Event,
+        etag: Option<&str>,
+    ) -> Result<Event> {
+        event.validate().map_err(GCalError::ValidationError)?;
+
+        let path = format!(
+            "calendars/{}/events/{}?supportsAttachments=true",
+            calendar_id, event_id
+        );
+
+        #[cfg(test)]
+        let resp = self
+            .http_client
+            .mock_put_response(&path, event, etag)
+            .await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.put_if_match(&path, event, etag).await?;
+
+        let updated_event: Event = serde_json::from_str(&resp)?;
+        Ok(updated_event)
+    }
+
+    /// イベントの一部を更新
+    ///
+    /// `patch` で `Absent` のフィールドは変更されず、`Null` のフィールドは消去されます。
+    /// 会議データと添付ファイルを送受信するため `conferenceDataVersion=1` と `supportsAttachments=true` を付与します。
+    pub async fn patch_event(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        patch: &EventPatch,
+    ) -> Result<Event> {
+        self.send_patch(calendar_id, event_id, patch, None).await
+    }
+
+    /// `etag` が現在のETagと一致する場合のみイベントの一部を更新
+    pub async fn patch_event_if_match(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        patch: &EventPatch,
+        etag: &str,
+    ) -> Result<Event> {
+        self.send_patch(calendar_id, event_id, patch, Some(etag))
+            .await
+    }
+
+    async fn send_patch(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        patch: &EventPatch,
+        etag: Option<&str>,
+    ) -> Result<Event> {
+        patch.validate().map_err(GCalError::ValidationError)?;
+        let path = format!(
+            "calendars/{}/events/{}?conferenceDataVersion=1&supportsAttachments=true",
+            calendar_id, event_id
+        );
+
+        #[cfg(test)]
+        let resp = self
+            .http_client
+            .mock_patch_response(&path, patch, etag)
+            .await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.patch_if_match(&path, patch, etag).await?;
+
+        let patched_event: Event = serde_json::from_str(&resp)?;
+        Ok(patched_event)
+    }
+
+    /// 取得したイベントを変更して更新する処理を、競合しなくなるまで繰り返す
+    ///
+    /// 最新のイベントに `modify` を適用し、変更点だけを If-Match 付きで送信します。
+    /// その間に他のクライアントが更新していた場合は取得からやり直し、
+    /// `MAX_MODIFY_ATTEMPTS` 回競合した場合は `GCalError::PreconditionFailed` を返します。
+    /// `modify` は再試行のたびに呼ばれるため、副作用を持たないようにしてください。
+    pub async fn modify_event<F>(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        mut modify: F,
+    ) -> Result<Event>
+    where
+        F: FnMut(&mut Event),
+    {
+        let mut last_error = None;
+        for _ in 0..MAX_MODIFY_ATTEMPTS {
+            let current = self.get_event(calendar_id, event_id).await?;
+            let mut modified = current.clone();
+            modify(&mut modified);
+
+            let patch = EventPatch::diff(&current, &modified);
+            if patch.is_empty() {
+                return Ok(current);
+            }
+            let result = match current.etag.as_deref() {
+                Some(etag) => {
+                    self.patch_event_if_match(calendar_id, event_id, &patch, etag)
+                        .await
+                }
+                None => self.patch_event(calendar_id, event_id, &patch).await,
+            };
+            match result {
+                Err(e @ GCalError::PreconditionFailed(_)) => last_error = Some(e),
+                other => return other,
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            GCalError::PreconditionFailed(format!("イベントを更新できませんでした: {}", event_id))
+        }))
+    }
+
+    /// イベントに添付ファイルを追加（同じファイルが添付済みの場合は置き換え）
+    pub async fn add_attachment(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        attachment: &Attachment,
+    ) -> Result<Event> {
+        if attachment.file_url.trim().is_empty() {
+            return Err(GCalError::field_validation(
+                "attachments.fileUrl",
+                "添付ファイルのURLが必要です",
+            ));
+        }
+        let mut limit_reached = false;
+        let event = self
+            .modify_event(calendar_id, event_id, |event| {
+                let replaces = event.attachments.iter().any(|a| a.is_same_file(attachment));
+                // 上限に達している場合は変更しない
+                limit_reached = !replaces && event.attachments.len() >= MAX_ATTACHMENTS;
+                if !limit_reached {
+                    event.add_attachment(attachment.clone());
+                }
+            })
+            .await?;
+        if limit_reached {
+            return Err(GCalError::field_validation(
+                "attachments",
+                format!("添付ファイルは{}件までです", MAX_ATTACHMENTS),
+            ));
+        }
+        Ok(event)
+    }
+
+    /// ドライブのファイルIDが一致する添付ファイルをイベントから削除
+    pub async fn remove_attachment(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        file_id: &str,
+    ) -> Result<Event> {
+        self.modify_event(calendar_id, event_id, |event| {
+            event.remove_attachment(file_id);
+        })
+        .await
+    }
+
+    /// イベントを削除
+    pub async fn delete_event(&self, calendar_id: &str, event_id: &str) -> Result<()> {
+        self.send_delete(calendar_id, event_id, None).await
+    }
+
+    /// `etag` が現在のETagと一致する場合のみイベントを削除
+    pub async fn delete_event_if_match(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        etag: &str,
+    ) -> Result<()> {
+        self.send_delete(calendar_id, event_id, Some(etag)).await
+    }
+
+    async fn send_delete(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        etag: Option<&str>,
+    ) -> Result<()> {
+        let path = format!("calendars/{}/events/{}", calendar_id, event_id);
+
+        #[cfg(test)]
+        self.http_client.mock_delete_response(&path, etag).await?;
+        #[cfg(not(test))]
+        self.http_client.delete_if_match(&path, etag).await?;
+
+        Ok(())
+    }
+
+    /// テキストからイベントを作成（events.quickAdd）
+    ///
+    /// テキストの解釈は Google カレンダー側で行われます。作成前に内容を確認する場合は
+    /// [`QuickAddParser`](crate::quick_add::QuickAddParser) で組み立てたイベントを `create_event` してください。
+    pub async fn quick_add(&self, calendar_id: &str, text: &str) -> Result<Event> {
+        if text.trim().is_empty() {
+            return Err(GCalError::field_validation("text", "テキストが空です"));
+        }
+        let query = serde_urlencoded::to_string([("text", text)]).unwrap_or_default();
+        let path = format!("calendars/{}/events/quickAdd?{}", calendar_id, query);
+
+        #[cfg(test)]
+        let resp = self
+            .http_client
+            .mock_post_response(&path, serde_json::json!({}))
+            .await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.post(&path, serde_json::json!({})).await?;
+
+        Ok(serde_json::from_str(&resp)?)
+    }
+
+    /// イベントを別のカレンダーに移動し、主催者を移動先のカレンダーに変更
+    ///
+    /// イベントIDと参加者はそのまま保持されます。移動できるのは通常のイベント（eventType が default）のみです。
+    pub async fn move_event(
+        &self,
+        source_calendar_id: &str,
+        event_id: &str,
+        destination_calendar_id: &str,
+        send_updates: SendUpdates,
+    ) -> Result<Event> {
+        if source_calendar_id == destination_calendar_id {
+            return Err(GCalError::field_validation(
+                "destination",
+                "移動元と移動先のカレンダーが同じです",
+            ));
+        }
+        let query = serde_urlencoded::to_string([
+            ("destination", destination_calendar_id),
+            ("sendUpdates", send_updates.as_str()),
+        ])
+        .unwrap_or_default();
+        let path = format!(
+            "calendars/{}/events/{}/move?{}",
+            source_calendar_id, event_id, query
+        );
+
+        #[cfg(test)]
+        let resp = self
+            .http_client
+            .mock_post_response(&path, serde_json::json!({}))
+            .await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.post(&path, serde_json::json!({})).await?;
+
+        Ok(serde_json::from_str(&resp)?)
+    }
+
+    /// `params` に一致するイベントをすべて別のカレンダーに移動
+    ///
+    /// 繰り返しイベントは各回ではなくシリーズ単位で移動します。個々のイベントの失敗は結果と `progress` に
+    /// 記録して処理を続けます。一覧の取得に失敗した場合はエラーを返しますが、`progress` にはそれまでの
+    /// 進捗が残るため、同じ `progress` で呼び直すと続きから再開します。
+    pub async fn move_events(
+        &self,
+        source_calendar_id: &str,
+        destination_calendar_id: &str,
+        params: &ListEventsParams,
+        send_updates: SendUpdates,
+        progress: &mut MoveProgress,
+    ) -> Result<MoveReport> {
+        if source_calendar_id == destination_calendar_id {
+            return Err(GCalError::field_validation(
+                "destination",
+                "移動元と移動先のカレンダーが同じです",
+            ));
+        }
+        let mut report = MoveReport::default();
+        let mut params = params.clone();
+        params.single_events = None;
+        params.sync_token = None;
+        while !progress.listing_complete {
+            params.page_token = progress.page_token.clone();
+            let page = self.list_events(source_calendar_id, &params).await?;
+            let failed_before = progress.failed.len();
+            progress.enqueue(&page.items);
+            report
+                .failed
+                .extend_from_slice(&progress.failed[failed_before..]);
+            progress.listing_complete = page.next_page_token.is_none();
+            progress.page_token = page.next_page_token;
+        }
+
+        // 中断しても再開できるよう、移動が終わるまで移動待ちから外さない
+        while let Some(event_id) = progress.pending.front().cloned() {
+            match self
+                .move_event(
+                    source_calendar_id,
+                    &event_id,
+                    destination_calendar_id,
+                    send_updates,
+                )
+                .await
+            {
+                Ok(event) => {
+                    report.moved.push(event);
+                    progress.moved.push(event_id);
+                }
+                Err(e) => {
+                    let failure = MoveFailure {
+                        event_id,
+                        message: e.to_string(),
+                    };
+                    report.failed.push(failure.clone());
+                    progress.failed.push(failure);
+                }
+            }
+            progress.pending.pop_front();
+        }
+        Ok(report)
+    }
+
+    /// 既存のイベントに会議データを追加
+    pub async fn attach_conference(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        conference: &ConferenceData,
+    ) -> Result<Event> {
+        let patch = EventPatch::new().with_conference_data(conference.clone());
+        self.patch_event(calendar_id, event_id, &patch).await
+    }
+
+    /// 会議の作成が完了するまでイベントを取得し直して待機
+    ///
+    /// 作成リクエストの状態が `pending` の間は `interval` ごとに再取得し、`success`
+    /// になったイベントを返します。`failure` になった場合や `max_attempts` 回取得しても
+    /// 完了しない場合はエラーを返します。
+    pub async fn wait_for_conference(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        interval: std::time::Duration,
+        max_attempts: u32,
+    ) -> Result<Event> {
+        for attempt in 0..max_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            let event = self.get_event(calendar_id, event_id).await?;
+            let status = match &event.conference_data {
+                Some(conference) => conference.status(),
+                None => {
+                    return Err(GCalError::Other(format!(
+                        "イベントに会議データがありません: {}",
+                        event_id
+                    )))
+                }
+            };
+            match status {
+                Some(ConferenceStatusCode::Pending) => continue,
+                Some(ConferenceStatusCode::Failure) => {
+                    return Err(GCalError::Other(format!(
+                        "会議の作成に失敗しました: {}",
+                        event_id
+                    )))
+                }
+                // 作成済み、または外部で作成した会議
+                Some(ConferenceStatusCode::Success) | None => return Ok(event),
+            }
+        }
+        Err(GCalError::Other(format!(
+            "会議の作成が{}回の確認で完了しませんでした: {}",
+            max_attempts, event_id
+        )))
+    }
+
+    /// 外部システムのキーでイベントを作成または更新
+    ///
+    /// private拡張プロパティ `key=value` を持つイベントを探し、見つかればその内容を
+    /// `event` で置き換えます。見つからなければプロパティを付与して新規作成します。
+    pub async fn upsert_by_external_key(
+        &self,
+        calendar_id: &str,
+        key: &str,
+        value: &str,
+        event: &Event,
+    ) -> Result<Event> {
+        let mut event = event.clone();
+        event.set_private_property(key, value);
+
+        let params = ListEventsParams::new()
+            .with_private_extended_property(key, value)
+            .with_max_results(1);
+        let existing = self.list_events(calendar_id, &params).await?;
+
+        match existing.items.into_iter().find_map(|e| e.id) {
+            Some(id) => {
+                event.id = Some(id.clone());
+                self.update_event(calendar_id, &id, &event).await
+            }
+            None => self.create_event_idempotent(calendar_id, &event).await,
+        }
+    }
+
     /// 1つのイベントを取得
     pub async fn get_event(&self, calendar_id: &str, event_id: &str) -> Result<Event> {
         // GET /calendars/{calendarId}/events/{eventId} を実行
@@ -50,6 +655,316 @@ impl CalendarClient {
         let fetched_event: Event = serde_json::from_str(&resp)?;
         Ok(fetched_event)
     }
+
+    /// キャッシュ済みのイベントが変更されていなければそのまま返し、変更されていれば取得し直す
+    ///
+    /// `cached` のETagを If-None-Match として送信し、304 の場合は `cached` の複製を返します。
+    /// ETagを持たない場合は通常の取得と同じです。
+    pub async fn get_event_if_none_match(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        cached: &Event,
+    ) -> Result<Event> {
+        let etag = match cached.etag.as_deref() {
+            Some(etag) => etag,
+            None => return self.get_event(calendar_id, event_id).await,
+        };
+        let path = format!("calendars/{}/events/{}", calendar_id, event_id);
+
+        #[cfg(test)]
+        let resp = self.http_client.mock_get_if_none_match(&path, etag).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.get_if_none_match(&path, etag).await?;
+
+        match resp {
+            Some(resp) => Ok(serde_json::from_str(&resp)?),
+            None => Ok(cached.clone()),
+        }
+    }
+
+    /// イベント一覧を1ページ分取得
+    pub async fn list_events(
+        &self,
+        calendar_id: &str,
+        params: &ListEventsParams,
+    ) -> Result<EventList> {
+        let query = params.to_query_string();
+        let path = if query.is_empty() {
+            format!("calendars/{}/events", calendar_id)
+        } else {
+            format!("calendars/{}/events?{}", calendar_id, query)
+        };
+
+        #[cfg(test)]
+        let resp = self.http_client.mock_get_response(&path).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.get(&path).await?;
+
+        let list: EventList = serde_json::from_str(&resp)?;
+        Ok(list)
+    }
+
+    /// ページングをたどってイベント一覧をすべて取得
+    ///
+    /// 返り値の `next_sync_token` には最終ページの同期トークンが入ります。
+    pub async fn list_all_events(
+        &self,
+        calendar_id: &str,
+        params: &ListEventsParams,
+    ) -> Result<EventList> {
+        let mut params = params.clone();
+        let mut all = EventList::default();
+        loop {
+            let page = self.list_events(calendar_id, &params).await?;
+            all.items.extend(page.items);
+            all.time_zone = page.time_zone.or(all.time_zone);
+            match page.next_page_token {
+                Some(token) => params.page_token = Some(token),
+                None => {
+                    all.next_sync_token = page.next_sync_token;
+                    return Ok(all);
+                }
+            }
+        }
+    }
+
+    /// カレンダーリストの項目を取得（デフォルトリマインダーやタイムゾーンを含む）
+    pub async fn get_calendar_list_entry(&self, calendar_id: &str) -> Result<CalendarListEntry> {
+        let path = format!("users/me/calendarList/{}", calendar_id);
+
+        #[cfg(test)]
+        let resp = self.http_client.mock_get_response(&path).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.get(&path).await?;
+
+        let entry: CalendarListEntry = serde_json::from_str(&resp)?;
+        Ok(entry)
+    }
+
+    /// カレンダーリストをすべて取得
+    pub async fn list_calendar_list(&self) -> Result<Vec<CalendarListEntry>> {
+        let mut entries = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let path = match &page_token {
+                Some(token) => format!(
+                    "users/me/calendarList?{}",
+                    serde_urlencoded::to_string([("pageToken", token)]).unwrap_or_default()
+                ),
+                None => "users/me/calendarList".to_string(),
+            };
+
+            #[cfg(test)]
+            let resp = self.http_client.mock_get_response(&path).await?;
+            #[cfg(not(test))]
+            let resp = self.http_client.get(&path).await?;
+
+            let page: CalendarList = serde_json::from_str(&resp)?;
+            entries.extend(page.items);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(entries),
+            }
+        }
+    }
+
+    /// イベントとカレンダーの色のパレットを取得
+    pub async fn get_colors(&self) -> Result<Colors> {
+        #[cfg(test)]
+        let resp = self.http_client.mock_get_response("colors").await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.get("colors").await?;
+
+        Ok(serde_json::from_str(&resp)?)
+    }
+
+    /// ユーザー設定を1件取得（例: `settings::TIME_ZONE`）
+    pub async fn get_setting(&self, setting_id: &str) -> Result<Setting> {
+        let path = format!("users/me/settings/{}", setting_id);
+
+        #[cfg(test)]
+        let resp = self.http_client.mock_get_response(&path).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.get(&path).await?;
+
+        Ok(serde_json::from_str(&resp)?)
+    }
+
+    /// ユーザー設定をすべて取得
+    pub async fn list_settings(&self) -> Result<UserSettings> {
+        let mut settings = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let path = match &page_token {
+                Some(token) => format!(
+                    "users/me/settings?{}",
+                    serde_urlencoded::to_string([("pageToken", token)]).unwrap_or_default()
+                ),
+                None => "users/me/settings".to_string(),
+            };
+
+            #[cfg(test)]
+            let resp = self.http_client.mock_get_response(&path).await?;
+            #[cfg(not(test))]
+            let resp = self.http_client.get(&path).await?;
+
+            let page: SettingList = serde_json::from_str(&resp)?;
+            settings.extend(page.items);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(UserSettings::from_settings(settings)),
+            }
+        }
+    }
+
+    /// `GCalConfig` の設定に従って決定した、イベントの既定のタイムゾーン
+    ///
+    /// `DefaultTimeZone::CalendarOwner` の場合は最初の呼び出しでカレンダーの所有者の設定を取得し、
+    /// 以降はその値を返します。
+    pub async fn default_time_zone(&self) -> Result<Tz> {
+        self.default_time_zone
+            .get_or_try_init(|| async {
+                let setting = self.get_setting(user_settings::TIME_ZONE).await?;
+                setting.value.parse::<Tz>().map_err(|_| {
+                    GCalError::Other(format!(
+                        "カレンダーの所有者のタイムゾーンを解釈できません: {}",
+                        setting.value
+                    ))
+                })
+            })
+            .await
+            .copied()
+    }
+
+    /// タイムゾーンのない時刻指定の開始・終了に、既定のタイムゾーンを設定したイベントを返します
+    ///
+    /// 日時はオフセット付きのため時刻は変わりません。タイムゾーンは繰り返しの展開と表示に使われます。
+    async fn with_default_time_zone(&self, event: &Event) -> Result<Event> {
+        let mut event = event.clone();
+        let missing = |dt: &Option<EventDateTime>| {
+            dt.as_ref()
+                .is_some_and(|dt| !dt.date_time.is_empty() && dt.time_zone.is_empty())
+        };
+        if missing(&event.start) || missing(&event.end) {
+            let tz = self.default_time_zone().await?;
+            for dt in [&mut event.start, &mut event.end].into_iter().flatten() {
+                if !dt.date_time.is_empty() && dt.time_zone.is_empty() {
+                    dt.time_zone = tz.name().to_string();
+                }
+            }
+        }
+        Ok(event)
+    }
+
+    /// 既定のタイムゾーンを設定した [`EventBuilder`] を作成
+    pub async fn event_builder(&self, summary: impl Into<String>) -> Result<EventBuilder> {
+        let tz = self.default_time_zone().await?;
+        Ok(EventBuilder::new(summary).with_default_time_zone(tz))
+    }
+
+    /// 複数の操作をまとめて送信するバッチリクエストを作成
+    pub fn batch(&self) -> BatchRequest<'_> {
+        BatchRequest::new(self)
+    }
+
+    pub(crate) fn api_path_prefix(&self) -> &str {
+        self.http_client.base_path()
+    }
+
+    pub(crate) async fn send_batch(
+        &self,
+        body: String,
+        boundary: &str,
+    ) -> Result<(String, String)> {
+        #[cfg(test)]
+        let resp = Ok(crate::batch::mock_batch_response(&body, boundary));
+        #[cfg(not(test))]
+        let resp = self.http_client.post_batch(body, boundary).await;
+
+        resp
+    }
+
+    /// 複数カレンダーの予定あり時間帯を取得
+    pub async fn query_free_busy(&self, request: &FreeBusyRequest) -> Result<FreeBusyResponse> {
+        let path = "freeBusy";
+
+        #[cfg(test)]
+        let resp = self.http_client.mock_post_response(path, request).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.post(path, request).await?;
+
+        let free_busy: FreeBusyResponse = serde_json::from_str(&resp)?;
+        Ok(free_busy)
+    }
+
+    /// 全参加者の勤務時間内で空いている候補枠を、評価の高い順に取得
+    pub async fn find_free_slots(&self, search: &SlotSearch) -> Result<Vec<CandidateSlot>> {
+        search.validate().map_err(GCalError::validation)?;
+
+        let request = FreeBusyRequest::new(
+            search.window_start - search.buffer,
+            search.window_end + search.buffer,
+            search.calendar_ids(),
+        );
+        let free_busy = self.query_free_busy(&request).await?;
+
+        // 予定状況を取得できないカレンダーがある場合、空きと誤判定しないようエラーにする
+        for id in search.calendar_ids() {
+            match free_busy.calendars.get(&id) {
+                None => {
+                    return Err(GCalError::Other(format!(
+                        "カレンダーの予定状況が返されませんでした: {}",
+                        id
+                    )))
+                }
+                Some(calendar) if !calendar.errors.is_empty() => {
+                    let reasons: Vec<&str> =
+                        calendar.errors.iter().map(|e| e.reason.as_str()).collect();
+                    return Err(GCalError::Other(format!(
+                        "カレンダーの予定状況を取得できませんでした: {} ({})",
+                        id,
+                        reasons.join(", ")
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(scheduling::find_free_slots(search, &free_busy))
+    }
+
+    /// カレンダーのイベント変更をプッシュ通知で受け取るチャネルを作成
+    pub async fn watch_events(&self, calendar_id: &str, channel: &Channel) -> Result<Channel> {
+        let path = format!("calendars/{}/events/watch", calendar_id);
+
+        #[cfg(test)]
+        let resp = self.http_client.mock_post_response(&path, channel).await?;
+        #[cfg(not(test))]
+        let resp = self.http_client.post(&path, channel).await?;
+
+        let created: Channel = serde_json::from_str(&resp)?;
+        Ok(created)
+    }
+
+    /// プッシュ通知のチャネルを停止
+    pub async fn stop_channel(&self, channel: &Channel) -> Result<()> {
+        let resource_id = channel.resource_id.clone().ok_or_else(|| {
+            GCalError::field_validation("resourceId", "チャネルのresourceIdが必要です")
+        })?;
+        let request = StopChannelRequest {
+            id: channel.id.clone(),
+            resource_id,
+        };
+        let path = "channels/stop";
+
+        #[cfg(test)]
+        self.http_client.mock_post_response(path, &request).await?;
+        #[cfg(not(test))]
+        self.http_client.post(path, &request).await?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +977,11 @@ mod tests {
         let http_client = HttpClient::mock().expect("failed to create mock client");
         let client = CalendarClient::new(http_client);
         assert!(client.http_client.base_url().contains("calendar"));
+        assert_eq!(client.api_path_prefix(), "/calendar/v3");
+        assert_eq!(
+            client.http_client.batch_url(),
+            "https://www.googleapis.com/batch/calendar/v3"
+        );
     }
 
     #[tokio::test]
@@ -74,19 +994,241 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_attachments() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let agenda = Attachment::drive_file("agenda0001").with_title("アジェンダ");
+
+        let event = Event::builder("定例")
+            .start(chrono::Utc::now().with_timezone(&Tz::Asia__Tokyo))
+            .duration(chrono::Duration::hours(1))
+            .attachment(agenda.clone())
+            .build()
+            .unwrap();
+        let created = client.create_event("test_calendar", &event).await.unwrap();
+        assert_eq!(created.attachments, vec![agenda.clone()]);
+
+        let updated = client
+            .add_attachment("test_calendar", "event0001", &agenda)
+            .await
+            .unwrap();
+        assert_eq!(updated.attachments, vec![agenda]);
+
+        let mut too_many = create_test_event();
+        for i in 0..=MAX_ATTACHMENTS {
+            too_many.add_attachment(Attachment::drive_file(format!("file{}", i)));
+        }
+        match client.create_event("test_calendar", &too_many).await {
+            Err(GCalError::ValidationError(errors)) => assert!(errors.contains("attachments")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quick_add() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let event = client
+            .quick_add("test_calendar", "来週火曜 15時 定例")
+            .await
+            .unwrap();
+        assert_eq!(event.summary.as_deref(), Some("来週火曜 15時 定例"));
+        assert!(event.id.is_some());
+
+        let result = client.quick_add("test_calendar", " ").await;
+        assert!(matches!(result, Err(GCalError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_move_event() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let moved = client
+            .move_event("old_team", "event0001", "new_team", SendUpdates::None)
+            .await
+            .unwrap();
+        assert_eq!(moved.id.as_deref(), Some("event0001"));
+        assert_eq!(
+            moved.organizer.and_then(|o| o.email).as_deref(),
+            Some("new_team")
+        );
+
+        let result = client
+            .move_event("team", "event0001", "team", SendUpdates::All)
+            .await;
+        assert!(matches!(result, Err(GCalError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_move_events_with_progress() {
+        use crate::mock::test_utils::{MOVE_FORBIDDEN_EVENT_ID, MOVE_SOURCE_CALENDAR_ID};
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        // 1件目は前回の実行で移動済み
+        let mut progress = MoveProgress::new();
+        progress.moved.push("move0001".to_string());
+        let report = client
+            .move_events(
+                MOVE_SOURCE_CALENDAR_ID,
+                "new_team",
+                &ListEventsParams::new().with_single_events(true),
+                SendUpdates::ExternalOnly,
+                &mut progress,
+            )
+            .await
+            .unwrap();
+
+        let moved: Vec<_> = report.moved.iter().filter_map(|e| e.id.clone()).collect();
+        assert_eq!(moved, vec!["move0002", "move0003"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].event_id, MOVE_FORBIDDEN_EVENT_ID);
+        assert!(progress.is_complete());
+        assert_eq!(progress.moved.len(), 3);
+
+        // 失敗したイベントだけを再試行できる
+        progress.retry_failed();
+        let report = client
+            .move_events(
+                MOVE_SOURCE_CALENDAR_ID,
+                "new_team",
+                &ListEventsParams::new(),
+                SendUpdates::None,
+                &mut progress,
+            )
+            .await
+            .unwrap();
+        assert!(report.moved.is_empty());
+        assert_eq!(report.failed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_event_requires_ical_uid() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let mut event = create_test_event();
+        let result = client.import_event("test_calendar", &event).await;
+        assert!(matches!(result, Err(GCalError::ValidationError(e)) if e.contains("iCalUID")));
+
+        event.i_cal_uid = Some("imported@example.com".to_string());
+        let imported = client.import_event("test_calendar", &event).await.unwrap();
+        assert!(imported.id.is_some());
+        assert_eq!(imported.i_cal_uid.as_deref(), Some("imported@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_import_event_preserves_organizer() {
+        use crate::attendee::Organizer;
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let mut event = create_test_event();
+        event.i_cal_uid = Some("external-123@other.example.com".to_string());
+        event.organizer =
+            Some(Organizer::new("owner@other.example.com").with_display_name("Owner"));
+
+        let options = ImportEventOptions::new()
+            .with_conference_data_version(0)
+            .with_supports_attachments(true);
+        let imported = client
+            .import_event_with_options("test_calendar", &event, &options)
+            .await
+            .unwrap();
+        assert_eq!(imported.i_cal_uid, event.i_cal_uid);
+        assert_eq!(imported.organizer, event.organizer);
+
+        let invalid = ImportEventOptions::new().with_conference_data_version(2);
+        let result = client
+            .import_event_with_options("test_calendar", &event, &invalid)
+            .await;
+        assert!(
+            matches!(result, Err(GCalError::ValidationError(e)) if e.contains("conferenceDataVersion"))
+        );
+
+        event.organizer = Some(Organizer::new("not-an-email"));
+        let result = client.import_event("test_calendar", &event).await;
+        assert!(
+            matches!(result, Err(GCalError::ValidationError(e)) if e.contains("organizer.email"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_ics_reports_failures() {
+        use crate::mock::test_utils::REJECTED_ICAL_UID;
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\nUID:ok@example.com\r\nSUMMARY:取り込み\r\n\
+DTSTART;TZID=Asia/Tokyo:20240115T100000\r\nDTEND;TZID=Asia/Tokyo:20240115T110000\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:no-start@example.com\r\nSUMMARY:開始なし\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:{}\r\nSUMMARY:拒否\r\nDTSTART;VALUE=DATE:20240115\r\nEND:VEVENT\r\n\
+END:VCALENDAR\r\n",
+            REJECTED_ICAL_UID
+        );
+        let report = client
+            .import_ics("test_calendar", &ics, &IcalImporter::new())
+            .await
+            .unwrap();
+        assert_eq!(report.imported.len(), 1);
+        assert_eq!(
+            report.imported[0].i_cal_uid.as_deref(),
+            Some("ok@example.com")
+        );
+        let failed: Vec<_> = report.failures.iter().map(|f| f.index).collect();
+        assert_eq!(failed, vec![1, 2]);
+        assert_eq!(report.failures[1].uid.as_deref(), Some(REJECTED_ICAL_UID));
+
+        let result = client
+            .import_ics("test_calendar", "BEGIN:VEVENT\r\n", &IcalImporter::new())
+            .await;
+        assert!(matches!(result, Err(GCalError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_import_csv() {
+        use crate::mock::test_utils::CONFLICT_EVENT_ID;
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let csv = format!(
+            "Event ID,Subject,Start Date,Start Time,End Time\n\
+shift0001,早番,2024-01-15,09:00,17:00\n\
+{},遅番,2024-01-15,13:00,21:00\n\
+shift0003,夜勤,2024-01-15,22:00,\n\
+Shift_4,日勤,2024-01-16,09:00,17:00\n",
+            CONFLICT_EVENT_ID
+        );
+        let importer = CsvImporter::new(chrono_tz::Asia::Tokyo);
+        let report = client
+            .import_csv("test_calendar", &csv, &importer)
+            .await
+            .unwrap();
+        // 既に存在するIDの行は既存のイベントが返される
+        assert_eq!(report.created.len(), 2);
+        assert_eq!(report.created[0].id.as_deref(), Some("shift0001"));
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.errors[0].row, 4);
+        // 作成時の検証エラーもフィールドのパスを保持する
+        assert_eq!(report.errors[1].row, 5);
+        assert!(report.errors[1].errors.contains("id"));
+
+        let result = client
+            .import_csv("test_calendar", "Name,Date\n", &importer)
+            .await;
+        assert!(matches!(result, Err(GCalError::ValidationError(_))));
+    }
+
     #[tokio::test]
     async fn test_create_event_validation_error() {
         let http_client = HttpClient::mock().expect("failed to create mock client");
         let client = CalendarClient::new(http_client);
 
         let event = Event {
-            id: None,
-            status: None,
             summary: None, // バリデーションエラーの原因
-            description: None,
-            location: None,
-            start: None,
-            end: None,
+            ..Default::default()
         };
 
         let calendar_id = "test_calendar";
@@ -95,6 +1237,56 @@ mod tests {
         assert!(matches!(result.unwrap_err(), GCalError::ValidationError(_)));
     }
 
+    #[tokio::test]
+    async fn test_create_event_with_invalid_id() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let mut event = create_test_event();
+        event.id = Some("Invalid-ID".to_string());
+        let result = client.create_event("test_calendar", &event).await;
+        assert!(matches!(result, Err(GCalError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_already_exists_returns_existing() {
+        use crate::mock::test_utils::CONFLICT_EVENT_ID;
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let mut event = create_test_event();
+        event.id = Some(CONFLICT_EVENT_ID.to_string());
+        event.summary = Some("再送".to_string());
+        // 409の場合は既存イベント（モックではテストイベント）を取得して返す
+        let result = client.create_event("test_calendar", &event).await.unwrap();
+        assert_eq!(result.summary.as_deref(), Some("テスト会議"));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_idempotent_assigns_id() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let created = client
+            .create_event_idempotent("test_calendar", &create_test_event())
+            .await
+            .unwrap();
+        let id = created.id.expect("id should be assigned");
+        assert!(validate_event_id(&id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_by_external_key_updates_existing() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let event = create_test_event();
+        let result = client
+            .upsert_by_external_key("test_calendar", "jira", "PROJ-1", &event)
+            .await
+            .unwrap();
+        // モックの一覧は mock_event_1 を返すため、そのIDで更新される
+        assert_eq!(result.id.as_deref(), Some("mock_event_1"));
+        assert_eq!(result.private_property("jira"), Some("PROJ-1"));
+    }
+
     #[tokio::test]
     async fn test_get_event_ok() {
         let http_client = HttpClient::mock().expect("failed to create mock client");
@@ -106,4 +1298,316 @@ mod tests {
         let fetched_event = result.unwrap();
         assert_eq!(fetched_event.summary.as_deref(), Some("テスト会議"));
     }
+
+    #[tokio::test]
+    async fn test_find_free_slots_ok() {
+        use crate::scheduling::{SlotAttendee, WorkingHours};
+        use chrono::{Duration, NaiveTime, TimeZone, Utc};
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let hours = WorkingHours::new(
+            chrono_tz::Asia::Tokyo,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        );
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let search = SlotSearch::new(start, start + Duration::days(1), Duration::hours(1))
+            .with_attendee(SlotAttendee::new("a@example.com", hours.clone()))
+            .with_attendee(SlotAttendee::new("b@example.com", hours));
+        let slots = client.find_free_slots(&search).await.unwrap();
+        assert_eq!(slots.len(), 10);
+        assert_eq!(slots[0].start, start);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_events_ok() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let params = ListEventsParams::new().with_sync_token("token");
+        let list = client.list_all_events("primary", &params).await.unwrap();
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.next_sync_token.as_deref(), Some("mock_sync_token"));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_with_google_meet() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let mut event = create_test_event();
+        event.conference_data = Some(ConferenceData::google_meet());
+
+        let created = client.create_event("primary", &event).await.unwrap();
+        assert_eq!(
+            created.conference_data.unwrap().status(),
+            Some(ConferenceStatusCode::Pending)
+        );
+
+        let ready = client
+            .wait_for_conference(
+                "primary",
+                crate::mock::test_utils::CONFERENCE_EVENT_ID,
+                std::time::Duration::from_millis(1),
+                3,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            ready.conference_data.unwrap().video_uri(),
+            Some("https://meet.google.com/aaa-bbbb-ccc")
+        );
+
+        // 会議データのないイベントはエラー
+        let result = client
+            .wait_for_conference("primary", "event1", std::time::Duration::ZERO, 1)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_attach_third_party_conference() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let conference = ConferenceData::third_party("Zoom", "https://zoom.us/j/123");
+        let patched = client
+            .attach_conference("primary", "event1", &conference)
+            .await
+            .unwrap();
+        assert_eq!(patched.id.as_deref(), Some("event1"));
+        assert_eq!(patched.conference_data, Some(conference));
+
+        let invalid = client
+            .attach_conference("primary", "event1", &ConferenceData::default())
+            .await;
+        assert!(matches!(invalid, Err(GCalError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_patch_event_clears_location() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let patch = EventPatch::new()
+            .with_description("場所は未定")
+            .clear_location();
+        let patched = client
+            .patch_event("primary", "event1", &patch)
+            .await
+            .unwrap();
+        assert_eq!(patched.description.as_deref(), Some("場所は未定"));
+        assert!(patched.location.is_none());
+        assert_eq!(patched.summary.as_deref(), Some("テスト会議"));
+
+        // 検証エラーはフィールドのパスを保持する
+        let patch = EventPatch {
+            summary: crate::patch::Patch::Null,
+            ..Default::default()
+        };
+        match client.patch_event("primary", "event1", &patch).await {
+            Err(GCalError::ValidationError(errors)) => assert!(errors.contains("summary")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_with_etag() {
+        use crate::mock::test_utils::MOCK_ETAG;
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let event = client.get_event("primary", "event1").await.unwrap();
+        assert_eq!(event.etag.as_deref(), Some(MOCK_ETAG));
+
+        assert!(client
+            .update_event_if_match("primary", "event1", &event, MOCK_ETAG)
+            .await
+            .is_ok());
+        let stale = client
+            .update_event_if_match("primary", "event1", &event, "\"old\"")
+            .await;
+        assert!(matches!(stale, Err(GCalError::PreconditionFailed(_))));
+        assert_eq!(stale.unwrap_err().status(), Some(412));
+
+        let patch = EventPatch::new().with_location("会議室B");
+        let stale = client
+            .patch_event_if_match("primary", "event1", &patch, "\"old\"")
+            .await;
+        assert!(matches!(stale, Err(GCalError::PreconditionFailed(_))));
+
+        assert!(client
+            .delete_event_if_match("primary", "event1", MOCK_ETAG)
+            .await
+            .is_ok());
+        assert!(client
+            .delete_event_if_match("primary", "event1", "\"old\"")
+            .await
+            .is_err());
+        assert!(client.delete_event("primary", "event1").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_event_if_none_match_returns_cached() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let mut cached = create_test_event();
+        cached.summary = Some("キャッシュ".to_string());
+        cached.etag = Some(crate::mock::test_utils::MOCK_ETAG.to_string());
+
+        let event = client
+            .get_event_if_none_match("primary", "event1", &cached)
+            .await
+            .unwrap();
+        assert_eq!(event.summary.as_deref(), Some("キャッシュ"));
+
+        cached.etag = Some("\"old\"".to_string());
+        let event = client
+            .get_event_if_none_match("primary", "event1", &cached)
+            .await
+            .unwrap();
+        assert_eq!(event.summary.as_deref(), Some("テスト会議"));
+    }
+
+    #[tokio::test]
+    async fn test_modify_event() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let modified = client
+            .modify_event("primary", "event1", |ev| {
+                ev.location = Some("会議室C".to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(modified.location.as_deref(), Some("会議室C"));
+
+        // 毎回競合する場合は再試行した後にエラーを返す
+        let mut calls = 0;
+        let result = client
+            .modify_event(
+                "primary",
+                crate::mock::test_utils::STALE_ETAG_EVENT_ID,
+                |ev| {
+                    calls += 1;
+                    ev.location = Some("会議室C".to_string());
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(GCalError::PreconditionFailed(_))));
+        assert_eq!(calls, MAX_MODIFY_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn test_calendar_list() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let entry = client.get_calendar_list_entry("primary").await.unwrap();
+        assert_eq!(entry.id, "primary");
+        assert_eq!(entry.default_reminders.len(), 1);
+
+        let entries = client.list_calendar_list().await.unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_colors() {
+        use crate::colors::{EventColor, Rgb};
+
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let colors = client.get_colors().await.unwrap();
+        assert_eq!(colors.event.len(), EventColor::ALL.len());
+        assert_eq!(
+            colors.nearest_event_color(Rgb::new(0xdd, 0x22, 0x22)),
+            Some(EventColor::Tomato.id())
+        );
+        assert!(colors.nearest_calendar_color(Rgb::new(0, 0, 0)).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_settings() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let settings = client.list_settings().await.unwrap();
+        assert_eq!(settings.time_zone.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(settings.week_start, Some(chrono::Weekday::Mon));
+        assert_eq!(settings.hide_weekends, Some(false));
+
+        let setting = client.get_setting(user_settings::LOCALE).await.unwrap();
+        assert_eq!(setting.value, "ja");
+        assert!(matches!(
+            client.get_setting("unknown").await,
+            Err(GCalError::ApiError { status: 404, .. })
+        ));
+        assert_eq!(client.default_time_zone().await.unwrap(), Tz::UTC);
+    }
+
+    #[tokio::test]
+    async fn test_default_time_zone() {
+        use crate::config::GCalConfig;
+        let start = chrono::NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let client_with = |default_time_zone| {
+            let config = GCalConfig::default().with_default_time_zone(default_time_zone);
+            let http_client =
+                HttpClient::mock_with_config(config).expect("failed to create mock client");
+            CalendarClient::new(http_client)
+        };
+
+        let utc = client_with(DefaultTimeZone::Utc);
+        let fixed = client_with(DefaultTimeZone::Fixed(Tz::America__New_York));
+        let owner = client_with(DefaultTimeZone::CalendarOwner);
+        for (client, expected) in [
+            (&utc, "2024-01-15T10:00:00+00:00"),
+            (&fixed, "2024-01-15T10:00:00-05:00"),
+            (&owner, "2024-01-15T10:00:00+09:00"),
+        ] {
+            let event = client
+                .event_builder("会議")
+                .await
+                .unwrap()
+                .start_local(start)
+                .duration(chrono::Duration::hours(1))
+                .build()
+                .unwrap();
+            assert_eq!(event.start.unwrap().date_time, expected);
+        }
+        // タイムゾーンを指定せずに作成したイベントにも既定のタイムゾーンが設定される
+        let start = chrono::Utc::now();
+        let event = Event::new(
+            "会議".to_string(),
+            start,
+            start + chrono::Duration::hours(1),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let created = fixed.create_event("primary", &event).await.unwrap();
+        assert_eq!(created.start.unwrap().time_zone, "America/New_York");
+        let created = owner.create_event("primary", &event).await.unwrap();
+        assert_eq!(created.end.unwrap().time_zone, "Asia/Tokyo");
+
+        // クライアントごとに独立している
+        assert_eq!(utc.default_time_zone().await.unwrap(), Tz::UTC);
+        assert_eq!(owner.default_time_zone().await.unwrap(), Tz::Asia__Tokyo);
+    }
+
+    #[tokio::test]
+    async fn test_watch_and_stop_channel() {
+        let http_client = HttpClient::mock().expect("failed to create mock client");
+        let client = CalendarClient::new(http_client);
+        let channel = Channel::web_hook("https://example.com/hook").with_token("secret");
+        let created = client.watch_events("primary", &channel).await.unwrap();
+        assert_eq!(created.id, channel.id);
+
+        // resourceIdがないチャネルは停止できない
+        let result = client.stop_channel(&created).await;
+        assert!(matches!(result, Err(GCalError::ValidationError(_))));
+
+        let created = Channel {
+            resource_id: Some("res-1".to_string()),
+            ..created
+        };
+        assert!(client.stop_channel(&created).await.is_ok());
+    }
 }
@@ -0,0 +1,69 @@
+use crate::reminders::Reminder;
+use serde::{Deserialize, Serialize};
+
+/// ユーザーのカレンダーリストの1項目
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarListEntry {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_role: Option<String>,
+    #[serde(default)]
+    pub primary: bool,
+    /// カレンダーの色。colors.get の `calendar` パレットのIDです
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_id: Option<String>,
+    /// "#RRGGBB" 形式の背景色。`color_id` より優先されます
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground_color: Option<String>,
+    /// このカレンダーで `useDefault` のイベントに適用されるリマインダー
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_reminders: Vec<Reminder>,
+}
+
+/// calendarList.list のレスポンス
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarList {
+    #[serde(default)]
+    pub items: Vec<CalendarListEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_sync_token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reminders::ReminderMethod;
+
+    #[test]
+    fn test_entry_deserialization() {
+        let json = r##"{
+            "kind": "calendar#calendarListEntry",
+            "id": "team@example.com",
+            "summary": "オンコール",
+            "timeZone": "Asia/Tokyo",
+            "accessRole": "owner",
+            "colorId": "14",
+            "backgroundColor": "#9fe1e7",
+            "defaultReminders": [{"method": "popup", "minutes": 10}]
+        }"##;
+        let entry: CalendarListEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.time_zone.as_deref(), Some("Asia/Tokyo"));
+        assert!(!entry.primary);
+        assert_eq!(entry.color_id.as_deref(), Some("14"));
+        assert_eq!(entry.background_color.as_deref(), Some("#9fe1e7"));
+        assert_eq!(entry.default_reminders.len(), 1);
+        assert_eq!(entry.default_reminders[0].method, ReminderMethod::Popup);
+    }
+}
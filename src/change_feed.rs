@@ -0,0 +1,547 @@
+use crate::calendar_client::CalendarClient;
+use crate::error::Result;
+use crate::event::Event;
+use crate::event_list::ListEventsParams;
+use crate::notification::{PushNotification, ResourceState};
+use async_trait::async_trait;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+/// カレンダーで発生したイベントの変更
+#[derive(Debug, Clone)]
+pub enum EventChange {
+    Created {
+        calendar_id: String,
+        event: Event,
+    },
+    Updated {
+        calendar_id: String,
+        event: Event,
+    },
+    Deleted {
+        calendar_id: String,
+        event_id: String,
+    },
+}
+
+impl EventChange {
+    pub fn calendar_id(&self) -> &str {
+        match self {
+            EventChange::Created { calendar_id, .. }
+            | EventChange::Updated { calendar_id, .. }
+            | EventChange::Deleted { calendar_id, .. } => calendar_id,
+        }
+    }
+}
+
+/// カレンダーごとの同期状態
+///
+/// 作成と更新を区別するため、同期トークンに加えて既知のイベントIDとそのバージョン
+/// （etag、なければ更新日時）を保持します。同期トークンが失効して全件を取り直した場合は、
+/// バージョンを比較して欠落期間中の更新を検出します。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub sync_token: Option<String>,
+    pub known_events: HashMap<String, String>,
+}
+
+/// 変更の検出に使うイベントのバージョン。etag も更新日時もない場合は空文字列
+fn event_version(event: &Event) -> String {
+    event
+        .etag
+        .clone()
+        .or_else(|| event.updated.map(|t| t.to_rfc3339()))
+        .unwrap_or_default()
+}
+
+/// 同期状態の保存先
+#[async_trait]
+pub trait SyncStateStore: Send + Sync {
+    async fn load(&self, calendar_id: &str) -> Result<Option<SyncState>>;
+    async fn save(&self, calendar_id: &str, state: &SyncState) -> Result<()>;
+}
+
+/// プロセス内のメモリに同期状態を保持するストア
+#[derive(Debug, Default)]
+pub struct MemorySyncStateStore {
+    states: Mutex<HashMap<String, SyncState>>,
+}
+
+impl MemorySyncStateStore {
+    pub fn new() -> Self {
+        MemorySyncStateStore::default()
+    }
+}
+
+#[async_trait]
+impl SyncStateStore for MemorySyncStateStore {
+    async fn load(&self, calendar_id: &str) -> Result<Option<SyncState>> {
+        Ok(self.states.lock().await.get(calendar_id).cloned())
+    }
+
+    async fn save(&self, calendar_id: &str, state: &SyncState) -> Result<()> {
+        self.states
+            .lock()
+            .await
+            .insert(calendar_id.to_string(), state.clone());
+        Ok(())
+    }
+}
+
+/// プッシュ通知を受けて変更フィードに同期を要求するためのハンドル
+#[derive(Debug, Clone)]
+pub struct PushHandle {
+    sender: mpsc::UnboundedSender<Option<String>>,
+}
+
+impl PushHandle {
+    /// 指定カレンダーの同期を要求します
+    pub fn trigger(&self, calendar_id: impl Into<String>) {
+        let _ = self.sender.send(Some(calendar_id.into()));
+    }
+
+    /// すべてのカレンダーの同期を要求します
+    pub fn trigger_all(&self) {
+        let _ = self.sender.send(None);
+    }
+
+    /// 受信した通知に応じて同期を要求します
+    ///
+    /// 通知のリソースURIからカレンダーIDを特定できない場合は、すべてのカレンダーを同期します。
+    /// チャネル作成時の `sync` 通知は無視します。
+    pub fn notify(&self, notification: &PushNotification) {
+        if notification.resource_state == ResourceState::Sync {
+            return;
+        }
+        match notification
+            .resource_uri
+            .as_deref()
+            .and_then(calendar_id_from_resource_uri)
+        {
+            Some(calendar_id) => self.trigger(calendar_id),
+            None => self.trigger_all(),
+        }
+    }
+}
+
+/// "https://www.googleapis.com/calendar/v3/calendars/{calendarId}/events?alt=json" から
+/// カレンダーIDを取り出します
+fn calendar_id_from_resource_uri(uri: &str) -> Option<String> {
+    let path = uri.split('?').next()?;
+    let rest = path.split("/calendars/").nth(1)?;
+    let encoded = rest.strip_suffix("/events")?;
+    let pairs: Vec<(String, String)> =
+        serde_urlencoded::from_str(&format!("id={}", encoded)).ok()?;
+    pairs.into_iter().next().map(|(_, id)| id)
+}
+
+/// 複数カレンダーの変更を非同期ストリームとして配信するフィード
+///
+/// プッシュ通知を受けるたびに差分同期を行い、通知が届かない間は一定間隔でポーリングします。
+/// 同期状態は [`SyncStateStore`] に保存されるため、再起動後も続きから同期できます。
+/// 初回同期（同期トークンがない状態）では既存イベントを記録するだけで、変更は配信しません。
+pub struct ChangeFeed {
+    client: Arc<CalendarClient>,
+    store: Arc<dyn SyncStateStore>,
+    calendars: Vec<String>,
+    poll_interval: Duration,
+    trigger_tx: mpsc::UnboundedSender<Option<String>>,
+    trigger_rx: mpsc::UnboundedReceiver<Option<String>>,
+}
+
+impl ChangeFeed {
+    pub fn new<I, S>(
+        client: Arc<CalendarClient>,
+        store: Arc<dyn SyncStateStore>,
+        calendars: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let (trigger_tx, trigger_rx) = mpsc::unbounded_channel();
+        ChangeFeed {
+            client,
+            store,
+            calendars: calendars.into_iter().map(Into::into).collect(),
+            poll_interval: Duration::from_secs(300),
+            trigger_tx,
+            trigger_rx,
+        }
+    }
+
+    /// プッシュ通知が届かない場合にポーリングする間隔
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn push_handle(&self) -> PushHandle {
+        PushHandle {
+            sender: self.trigger_tx.clone(),
+        }
+    }
+
+    /// 1つのカレンダーを同期し、検出した変更を返します
+    pub async fn sync_calendar(&self, calendar_id: &str) -> Result<Vec<EventChange>> {
+        sync_calendar(&self.client, self.store.as_ref(), calendar_id).await
+    }
+
+    /// バックグラウンドで同期を開始し、変更のストリームを返します
+    ///
+    /// ストリームを破棄すると同期タスクも停止します。
+    pub fn into_stream(self) -> ChangeStream {
+        let (tx, rx) = mpsc::channel(256);
+        let ChangeFeed {
+            client,
+            store,
+            calendars,
+            poll_interval,
+            trigger_tx,
+            mut trigger_rx,
+        } = self;
+
+        let task = tokio::spawn(async move {
+            // プッシュ通知のハンドルがすべて破棄されても、受信側が閉じずにポーリングを続けるよう保持する
+            let _trigger_tx = trigger_tx;
+            let mut last_synced: HashMap<String, Instant> = HashMap::new();
+            // 起動時に全カレンダーを同期する
+            let mut pending: Vec<String> = calendars.clone();
+            loop {
+                for calendar_id in pending.drain(..) {
+                    let result = sync_calendar(&client, store.as_ref(), &calendar_id).await;
+                    last_synced.insert(calendar_id.clone(), Instant::now());
+                    let items: Vec<Result<EventChange>> = match result {
+                        Ok(changes) => changes.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    };
+                    for item in items {
+                        if tx.send(item).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+
+                // 最も長く同期していないカレンダーのポーリング時刻まで通知を待つ
+                let next_poll = calendars
+                    .iter()
+                    .filter_map(|id| last_synced.get(id))
+                    .min()
+                    .map(|t| *t + poll_interval)
+                    .unwrap_or_else(Instant::now);
+                tokio::select! {
+                    trigger = trigger_rx.recv() => match trigger {
+                        Some(Some(id)) if calendars.contains(&id) => pending.push(id),
+                        Some(Some(_)) => {}
+                        Some(None) | None => pending.extend(calendars.iter().cloned()),
+                    },
+                    _ = tokio::time::sleep_until(next_poll.into()) => {
+                        let now = Instant::now();
+                        pending.extend(calendars.iter().filter(|id| {
+                            last_synced
+                                .get(*id)
+                                .is_none_or(|t| now.duration_since(*t) >= poll_interval)
+                        }).cloned());
+                    }
+                }
+                pending.sort();
+                pending.dedup();
+            }
+        });
+
+        ChangeStream { rx, task }
+    }
+}
+
+/// [`ChangeFeed`] が配信する変更のストリーム
+pub struct ChangeStream {
+    rx: mpsc::Receiver<Result<EventChange>>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for ChangeStream {
+    type Item = Result<EventChange>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for ChangeStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn sync_calendar(
+    client: &CalendarClient,
+    store: &dyn SyncStateStore,
+    calendar_id: &str,
+) -> Result<Vec<EventChange>> {
+    let state = store.load(calendar_id).await?.unwrap_or_default();
+
+    let (changes, state) = match &state.sync_token {
+        None => (
+            Vec::new(),
+            full_sync(client, calendar_id, &state, false).await?.1,
+        ),
+        Some(token) => {
+            let params = ListEventsParams::new().with_sync_token(token.clone());
+            match client.list_all_events(calendar_id, &params).await {
+                Ok(list) => {
+                    let mut state = state.clone();
+                    let mut changes = Vec::new();
+                    for event in list.items {
+                        if let Some(change) = classify(calendar_id, event, &mut state) {
+                            changes.push(change);
+                        }
+                    }
+                    state.sync_token = list.next_sync_token.or(state.sync_token);
+                    (changes, state)
+                }
+                // 同期トークンが失効した場合は全件を取り直し、既知のIDとの差分を配信する
+                Err(e) if e.status() == Some(410) => {
+                    full_sync(client, calendar_id, &state, true).await?
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    };
+
+    store.save(calendar_id, &state).await?;
+    Ok(changes)
+}
+
+async fn full_sync(
+    client: &CalendarClient,
+    calendar_id: &str,
+    previous: &SyncState,
+    emit: bool,
+) -> Result<(Vec<EventChange>, SyncState)> {
+    let list = client
+        .list_all_events(calendar_id, &ListEventsParams::new())
+        .await?;
+
+    let (changes, known_events) = diff_full_list(calendar_id, list.items, previous, emit);
+    let state = SyncState {
+        sync_token: list.next_sync_token,
+        known_events,
+    };
+    Ok((changes, state))
+}
+
+/// 全件の一覧を前回の同期状態と比較し、変更と新しい既知のイベントを返します
+///
+/// 前回から残っているイベントは、バージョンが変わった（または比較できない）場合に更新として扱います。
+fn diff_full_list(
+    calendar_id: &str,
+    items: Vec<Event>,
+    previous: &SyncState,
+    emit: bool,
+) -> (Vec<EventChange>, HashMap<String, String>) {
+    let mut changes = Vec::new();
+    let mut known = HashMap::new();
+    for event in items {
+        let Some(id) = event.id.clone() else { continue };
+        if event.status.as_deref() == Some("cancelled") {
+            continue;
+        }
+        let version = event_version(&event);
+        if emit {
+            let calendar_id = calendar_id.to_string();
+            match previous.known_events.get(&id) {
+                None => changes.push(EventChange::Created { calendar_id, event }),
+                Some(before) if before.is_empty() || *before != version => {
+                    changes.push(EventChange::Updated { calendar_id, event })
+                }
+                Some(_) => {}
+            }
+        }
+        known.insert(id, version);
+    }
+    if emit {
+        for id in previous.known_events.keys() {
+            if !known.contains_key(id) {
+                changes.push(EventChange::Deleted {
+                    calendar_id: calendar_id.to_string(),
+                    event_id: id.clone(),
+                });
+            }
+        }
+    }
+    (changes, known)
+}
+
+fn classify(calendar_id: &str, event: Event, state: &mut SyncState) -> Option<EventChange> {
+    let id = event.id.clone()?;
+    let calendar_id = calendar_id.to_string();
+    if event.status.as_deref() == Some("cancelled") {
+        // 一度も見ていないイベントの削除は配信しない
+        return state
+            .known_events
+            .remove(&id)
+            .map(|_| EventChange::Deleted {
+                calendar_id,
+                event_id: id,
+            });
+    }
+    if state
+        .known_events
+        .insert(id, event_version(&event))
+        .is_none()
+    {
+        Some(EventChange::Created { calendar_id, event })
+    } else {
+        Some(EventChange::Updated { calendar_id, event })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::HttpClient;
+    use futures::StreamExt;
+
+    fn event(id: &str, status: Option<&str>) -> Event {
+        let mut event = crate::mock::test_utils::create_test_event();
+        event.id = Some(id.to_string());
+        event.status = status.map(str::to_string);
+        event
+    }
+
+    fn feed() -> ChangeFeed {
+        let client = Arc::new(CalendarClient::new(
+            HttpClient::mock().expect("failed to create mock client"),
+        ));
+        ChangeFeed::new(client, Arc::new(MemorySyncStateStore::new()), ["primary"])
+    }
+
+    #[test]
+    fn test_classify() {
+        let mut state = SyncState::default();
+        assert!(matches!(
+            classify("c", event("a", None), &mut state),
+            Some(EventChange::Created { .. })
+        ));
+        assert!(matches!(
+            classify("c", event("a", Some("confirmed")), &mut state),
+            Some(EventChange::Updated { .. })
+        ));
+        assert!(matches!(
+            classify("c", event("a", Some("cancelled")), &mut state),
+            Some(EventChange::Deleted { .. })
+        ));
+        assert!(classify("c", event("b", Some("cancelled")), &mut state).is_none());
+        assert!(state.known_events.is_empty());
+    }
+
+    #[test]
+    fn test_diff_full_list() {
+        let versioned = |id: &str, etag: &str| Event {
+            etag: Some(etag.to_string()),
+            ..event(id, None)
+        };
+        let previous = SyncState {
+            sync_token: None,
+            known_events: [
+                ("same", "\"1\""),
+                ("changed", "\"1\""),
+                ("unversioned", ""),
+                ("removed", "\"1\""),
+            ]
+            .into_iter()
+            .map(|(id, v)| (id.to_string(), v.to_string()))
+            .collect(),
+        };
+        let items = vec![
+            versioned("same", "\"1\""),
+            versioned("changed", "\"2\""),
+            event("unversioned", None),
+            versioned("new", "\"1\""),
+            event("cancelled", Some("cancelled")),
+        ];
+        let (changes, known) = diff_full_list("c", items, &previous, true);
+        let summary: Vec<(&str, String)> = changes
+            .iter()
+            .map(|c| match c {
+                EventChange::Created { event, .. } => ("created", event.id.clone().unwrap()),
+                EventChange::Updated { event, .. } => ("updated", event.id.clone().unwrap()),
+                EventChange::Deleted { event_id, .. } => ("deleted", event_id.clone()),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("updated", "changed".to_string()),
+                ("updated", "unversioned".to_string()),
+                ("created", "new".to_string()),
+                ("deleted", "removed".to_string()),
+            ]
+        );
+        assert_eq!(known.len(), 4);
+        assert_eq!(known["changed"], "\"2\"");
+    }
+
+    #[test]
+    fn test_calendar_id_from_resource_uri() {
+        assert_eq!(
+            calendar_id_from_resource_uri(
+                "https://www.googleapis.com/calendar/v3/calendars/team%40example.com/events?alt=json"
+            )
+            .as_deref(),
+            Some("team@example.com")
+        );
+        assert!(calendar_id_from_resource_uri("https://example.com/other").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sync_calendar_baseline_then_changes() {
+        let feed = feed();
+        // 初回は既存イベントを記録するだけ
+        assert!(feed.sync_calendar("primary").await.unwrap().is_empty());
+        let state = feed.store.load("primary").await.unwrap().unwrap();
+        assert_eq!(state.sync_token.as_deref(), Some("mock_sync_token"));
+        assert!(state.known_events.contains_key("mock_event_1"));
+
+        // 2回目以降は差分として配信される
+        let changes = feed.sync_calendar("primary").await.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(changes[0], EventChange::Updated { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_stream_syncs_on_push() {
+        let feed = feed().with_poll_interval(Duration::from_secs(3600));
+        let handle = feed.push_handle();
+        let mut stream = feed.into_stream();
+
+        handle.trigger("primary");
+        let change = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("no change received")
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.calendar_id(), "primary");
+    }
+
+    #[tokio::test]
+    async fn test_stream_polls_without_push_handle() {
+        let mut stream = feed()
+            .with_poll_interval(Duration::from_millis(10))
+            .into_stream();
+        // 初回同期の後、ポーリングで差分が配信される
+        let change = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("no change received")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(change, EventChange::Updated { .. }));
+    }
+}
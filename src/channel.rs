@@ -0,0 +1,190 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// プッシュ通知の受信チャネル（events.watch のリクエスト・レスポンス）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Channel {
+    pub id: String,
+    #[serde(rename = "type", default = "default_channel_type")]
+    pub channel_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// チャネルの有効期限。APIではエポックミリ秒の文字列で表されます
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "expiration_millis"
+    )]
+    pub expiration: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, String>,
+}
+
+fn default_channel_type() -> String {
+    "web_hook".to_string()
+}
+
+impl Channel {
+    /// 指定URLへ通知を送るWebhookチャネルを作成します。IDはランダムに生成されます
+    pub fn web_hook(address: impl Into<String>) -> Self {
+        Channel {
+            id: generate_channel_id(),
+            channel_type: default_channel_type(),
+            address: Some(address.into()),
+            token: None,
+            expiration: None,
+            resource_id: None,
+            resource_uri: None,
+            params: HashMap::new(),
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// 通知ごとに X-Goog-Channel-Token として送り返される検証用トークン
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// 有効期限を要求します。Googleはこれより短い期限を返すことがあります
+    pub fn with_expiration(mut self, expiration: DateTime<Utc>) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// チャネルの有効期間(秒)を要求します
+    pub fn with_ttl(mut self, seconds: u64) -> Self {
+        self.params.insert("ttl".to_string(), seconds.to_string());
+        self
+    }
+
+    /// 同じ宛先・トークンで新しいIDを持つチャネルを作成します（更新用）
+    pub fn renewed(&self) -> Self {
+        Channel {
+            id: generate_channel_id(),
+            expiration: None,
+            resource_id: None,
+            resource_uri: None,
+            ..self.clone()
+        }
+    }
+
+    /// 指定時刻から `margin` 以内に期限切れとなる場合に true を返します
+    pub fn expires_within(&self, now: DateTime<Utc>, margin: chrono::Duration) -> bool {
+        self.expiration.is_some_and(|exp| exp - margin <= now)
+    }
+}
+
+/// channels.stop のリクエストボディ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopChannelRequest {
+    pub id: String,
+    pub resource_id: String,
+}
+
+/// チャネルIDを生成します
+pub fn generate_channel_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+mod expiration_millis {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(dt) => serializer.serialize_str(&dt.timestamp_millis().to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Millis {
+            Str(String),
+            Num(i64),
+        }
+
+        let millis = match Option::<Millis>::deserialize(deserializer)? {
+            None => return Ok(None),
+            Some(Millis::Num(n)) => n,
+            Some(Millis::Str(s)) => s.parse().map_err(serde::de::Error::custom)?,
+        };
+        DateTime::from_timestamp_millis(millis)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid expiration: {}", millis)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_channel_serialization() {
+        let exp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let channel = Channel::web_hook("https://example.com/hook")
+            .with_id("ch-1")
+            .with_token("secret")
+            .with_expiration(exp);
+        let json = serde_json::to_value(&channel).unwrap();
+        assert_eq!(json["type"], "web_hook");
+        assert_eq!(json["expiration"], "1704067200000");
+        assert!(json.get("resourceId").is_none());
+        assert!(json.get("params").is_none());
+    }
+
+    #[test]
+    fn test_channel_response_deserialization() {
+        let json = r#"{
+            "kind": "api#channel",
+            "id": "ch-1",
+            "resourceId": "res-1",
+            "resourceUri": "https://www.googleapis.com/calendar/v3/calendars/primary/events",
+            "token": "secret",
+            "expiration": "1704067200000"
+        }"#;
+        let channel: Channel = serde_json::from_str(json).unwrap();
+        assert_eq!(channel.resource_id.as_deref(), Some("res-1"));
+        assert_eq!(
+            channel.expiration,
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(channel.channel_type, "web_hook");
+    }
+
+    #[test]
+    fn test_renewed_and_expiry() {
+        let exp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let channel = Channel::web_hook("https://example.com/hook")
+            .with_token("secret")
+            .with_expiration(exp);
+        let renewed = channel.renewed();
+        assert_ne!(renewed.id, channel.id);
+        assert_eq!(renewed.token, channel.token);
+        assert!(renewed.expiration.is_none());
+
+        assert!(channel.expires_within(exp - Duration::minutes(30), Duration::hours(1)));
+        assert!(!channel.expires_within(exp - Duration::hours(2), Duration::hours(1)));
+    }
+}
@@ -0,0 +1,284 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// パレットの1色（"#a4bdfc" 形式の16進数）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColorDefinition {
+    pub background: String,
+    pub foreground: String,
+}
+
+/// colors.get のレスポンス
+///
+/// キーは colorId です。イベントには `event`、カレンダーリストの項目には `calendar` のIDを指定します。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Colors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub calendar: BTreeMap<String, ColorDefinition>,
+    #[serde(default)]
+    pub event: BTreeMap<String, ColorDefinition>,
+}
+
+impl Colors {
+    /// 背景色が `rgb` に最も近いイベントの colorId を返します
+    pub fn nearest_event_color(&self, rgb: Rgb) -> Option<&str> {
+        nearest(&self.event, rgb)
+    }
+
+    /// 背景色が `rgb` に最も近いカレンダーの colorId を返します
+    pub fn nearest_calendar_color(&self, rgb: Rgb) -> Option<&str> {
+        nearest(&self.calendar, rgb)
+    }
+}
+
+/// 距離が等しい場合は colorId の数値が小さいほうを選びます
+fn nearest(palette: &BTreeMap<String, ColorDefinition>, rgb: Rgb) -> Option<&str> {
+    palette
+        .iter()
+        .filter_map(|(id, color)| {
+            let background = color.background.parse::<Rgb>().ok()?;
+            Some((
+                background.distance(rgb),
+                id.parse::<u32>().unwrap_or(u32::MAX),
+                id,
+            ))
+        })
+        .min_by_key(|(distance, order, _)| (*distance, *order))
+        .map(|(_, _, id)| id.as_str())
+}
+
+/// RGBの色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// ユークリッド距離の2乗
+    fn distance(self, other: Rgb) -> u32 {
+        [(self.r, other.r), (self.g, other.g), (self.b, other.b)]
+            .into_iter()
+            .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+            .sum()
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    /// "#a4bdfc" / "a4bdfc" / "#abc" 形式を受け付けます
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("色は #RRGGBB 形式で指定してください: {}", s);
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if !hex.is_ascii() {
+            return Err(invalid());
+        }
+        let hex = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 => hex.to_string(),
+            _ => return Err(invalid()),
+        };
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+        Ok(Rgb::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// イベントの色（Google カレンダーの画面に表示される名前）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventColor {
+    Lavender,
+    Sage,
+    Grape,
+    Flamingo,
+    Banana,
+    Tangerine,
+    Peacock,
+    Graphite,
+    Blueberry,
+    Basil,
+    Tomato,
+}
+
+impl EventColor {
+    pub const ALL: [EventColor; 11] = [
+        EventColor::Lavender,
+        EventColor::Sage,
+        EventColor::Grape,
+        EventColor::Flamingo,
+        EventColor::Banana,
+        EventColor::Tangerine,
+        EventColor::Peacock,
+        EventColor::Graphite,
+        EventColor::Blueberry,
+        EventColor::Basil,
+        EventColor::Tomato,
+    ];
+
+    /// colorId
+    pub fn id(&self) -> &'static str {
+        match self {
+            EventColor::Lavender => "1",
+            EventColor::Sage => "2",
+            EventColor::Grape => "3",
+            EventColor::Flamingo => "4",
+            EventColor::Banana => "5",
+            EventColor::Tangerine => "6",
+            EventColor::Peacock => "7",
+            EventColor::Graphite => "8",
+            EventColor::Blueberry => "9",
+            EventColor::Basil => "10",
+            EventColor::Tomato => "11",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        EventColor::ALL.into_iter().find(|c| c.id() == id)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventColor::Lavender => "Lavender",
+            EventColor::Sage => "Sage",
+            EventColor::Grape => "Grape",
+            EventColor::Flamingo => "Flamingo",
+            EventColor::Banana => "Banana",
+            EventColor::Tangerine => "Tangerine",
+            EventColor::Peacock => "Peacock",
+            EventColor::Graphite => "Graphite",
+            EventColor::Blueberry => "Blueberry",
+            EventColor::Basil => "Basil",
+            EventColor::Tomato => "Tomato",
+        }
+    }
+
+    /// 日本語版の画面での名前
+    pub fn japanese_name(&self) -> &'static str {
+        match self {
+            EventColor::Lavender => "ラベンダー",
+            EventColor::Sage => "セージ",
+            EventColor::Grape => "ブドウ",
+            EventColor::Flamingo => "フラミンゴ",
+            EventColor::Banana => "バナナ",
+            EventColor::Tangerine => "ミカン",
+            EventColor::Peacock => "ピーコック",
+            EventColor::Graphite => "グラファイト",
+            EventColor::Blueberry => "ブルーベリー",
+            EventColor::Basil => "バジル",
+            EventColor::Tomato => "トマト",
+        }
+    }
+
+    /// 英語名（大文字小文字を区別しません）または日本語名から色を返します
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        EventColor::ALL
+            .into_iter()
+            .find(|c| c.name().eq_ignore_ascii_case(name) || c.japanese_name() == name)
+    }
+
+    /// colors.get が返す既定の背景色
+    pub fn default_background(&self) -> Rgb {
+        let (r, g, b) = match self {
+            EventColor::Lavender => (0xa4, 0xbd, 0xfc),
+            EventColor::Sage => (0x7a, 0xe7, 0xbf),
+            EventColor::Grape => (0xdb, 0xad, 0xff),
+            EventColor::Flamingo => (0xff, 0x88, 0x7c),
+            EventColor::Banana => (0xfb, 0xd7, 0x5b),
+            EventColor::Tangerine => (0xff, 0xb8, 0x78),
+            EventColor::Peacock => (0x46, 0xd6, 0xdb),
+            EventColor::Graphite => (0xe1, 0xe1, 0xe1),
+            EventColor::Blueberry => (0x54, 0x84, 0xed),
+            EventColor::Basil => (0x51, 0xb7, 0x49),
+            EventColor::Tomato => (0xdc, 0x21, 0x27),
+        };
+        Rgb::new(r, g, b)
+    }
+
+    /// 既定の背景色が `rgb` に最も近い色を返します。APIから取得したパレットを使う場合は
+    /// [`Colors::nearest_event_color`] を使用してください
+    pub fn nearest(rgb: Rgb) -> Self {
+        EventColor::ALL
+            .into_iter()
+            .min_by_key(|c| c.default_background().distance(rgb))
+            .unwrap_or(EventColor::Lavender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colors_deserialization_and_nearest() {
+        let json = r##"{
+            "kind": "calendar#colors",
+            "updated": "2012-02-14T00:00:00.000Z",
+            "calendar": {
+                "1": {"background": "#ac725e", "foreground": "#1d1d1d"},
+                "2": {"background": "#d06b64", "foreground": "#1d1d1d"}
+            },
+            "event": {
+                "1": {"background": "#a4bdfc", "foreground": "#1d1d1d"},
+                "10": {"background": "#51b749", "foreground": "#1d1d1d"},
+                "11": {"background": "#dc2127", "foreground": "#1d1d1d"}
+            }
+        }"##;
+        let colors: Colors = serde_json::from_str(json).unwrap();
+        assert_eq!(colors.event.len(), 3);
+        assert_eq!(colors.calendar["2"].background, "#d06b64");
+        assert_eq!(colors.nearest_event_color(Rgb::new(0, 200, 0)), Some("10"));
+        assert_eq!(
+            colors.nearest_calendar_color("#cc6666".parse().unwrap()),
+            Some("2")
+        );
+        assert_eq!(
+            Colors::default().nearest_event_color(Rgb::new(0, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(
+            "#A4BDFC".parse::<Rgb>().unwrap(),
+            Rgb::new(0xa4, 0xbd, 0xfc)
+        );
+        assert_eq!("f00".parse::<Rgb>().unwrap(), Rgb::new(255, 0, 0));
+        assert!("#12345".parse::<Rgb>().is_err());
+        assert!("#gggggg".parse::<Rgb>().is_err());
+        assert_eq!(Rgb::new(0x51, 0xb7, 0x49).to_string(), "#51b749");
+    }
+
+    #[test]
+    fn test_event_color() {
+        assert_eq!(EventColor::from_name("tomato"), Some(EventColor::Tomato));
+        assert_eq!(EventColor::from_name("バジル"), Some(EventColor::Basil));
+        assert_eq!(EventColor::from_name("red"), None);
+        assert_eq!(EventColor::from_id("7"), Some(EventColor::Peacock));
+        assert_eq!(EventColor::Tomato.id(), "11");
+        assert_eq!(
+            EventColor::nearest(Rgb::new(250, 10, 10)),
+            EventColor::Tomato
+        );
+        for color in EventColor::ALL {
+            assert_eq!(EventColor::nearest(color.default_background()), color);
+        }
+    }
+}
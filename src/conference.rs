@@ -0,0 +1,313 @@
+use serde::{Deserialize, Serialize};
+
+/// Google Meet の会議ソリューションの種類
+pub const HANGOUTS_MEET: &str = "hangoutsMeet";
+/// サードパーティ（アドオン）の会議ソリューションの種類
+pub const ADD_ON: &str = "addOn";
+
+/// イベントに紐づくビデオ会議の情報
+///
+/// 新しい Google Meet を作成する場合は `create_request` を、Zoom など外部で作成した
+/// 会議を添付する場合は `conference_solution` と `entry_points` を指定します。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConferenceData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_request: Option<CreateConferenceRequest>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entry_points: Vec<EntryPoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conference_solution: Option<ConferenceSolution>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conference_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl ConferenceData {
+    /// 新しい Google Meet の作成を要求します。requestId はランダムに生成されます
+    pub fn google_meet() -> Self {
+        ConferenceData {
+            create_request: Some(CreateConferenceRequest::new(HANGOUTS_MEET)),
+            ..Default::default()
+        }
+    }
+
+    /// 外部で作成した会議（Zoom など）を添付します
+    ///
+    /// `name` はカレンダー上に表示される会議サービス名、`video_uri` は参加用URLです。
+    pub fn third_party(name: impl Into<String>, video_uri: impl Into<String>) -> Self {
+        ConferenceData {
+            conference_solution: Some(ConferenceSolution {
+                key: ConferenceSolutionKey::new(ADD_ON),
+                name: Some(name.into()),
+                icon_uri: None,
+            }),
+            entry_points: vec![EntryPoint::video(video_uri)],
+            ..Default::default()
+        }
+    }
+
+    pub fn with_entry_point(mut self, entry_point: EntryPoint) -> Self {
+        self.entry_points.push(entry_point);
+        self
+    }
+
+    pub fn with_conference_id(mut self, conference_id: impl Into<String>) -> Self {
+        self.conference_id = Some(conference_id.into());
+        self
+    }
+
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    /// 会議作成リクエストの状態。作成を要求していない場合は None を返します
+    pub fn status(&self) -> Option<ConferenceStatusCode> {
+        self.create_request
+            .as_ref()
+            .and_then(|r| r.status.as_ref())
+            .map(|s| s.status_code)
+    }
+
+    /// ビデオ会議の参加URL
+    pub fn video_uri(&self) -> Option<&str> {
+        self.entry_points
+            .iter()
+            .find(|e| e.entry_point_type == EntryPointType::Video)
+            .map(|e| e.uri.as_str())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(request) = &self.create_request {
+            if request.request_id.is_empty() {
+                return Err("会議作成リクエストには requestId が必要です".to_string());
+            }
+        } else if self.conference_solution.is_none() || self.entry_points.is_empty() {
+            return Err(
+                "会議データには createRequest、または conferenceSolution と entryPoints が必要です"
+                    .to_string(),
+            );
+        }
+        for entry_point in &self.entry_points {
+            if entry_point.uri.is_empty() {
+                return Err("会議の参加方法(entryPoint)には uri が必要です".to_string());
+            }
+        }
+        let videos = self
+            .entry_points
+            .iter()
+            .filter(|e| e.entry_point_type == EntryPointType::Video)
+            .count();
+        if videos > 1 {
+            return Err("ビデオの参加方法は1つまでです".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 新しい会議の作成リクエスト
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateConferenceRequest {
+    /// クライアントが生成する一意のID。再送時に同じ値を使えば会議は重複しません
+    pub request_id: String,
+    pub conference_solution_key: ConferenceSolutionKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ConferenceRequestStatus>,
+}
+
+impl CreateConferenceRequest {
+    pub fn new(solution_type: impl Into<String>) -> Self {
+        CreateConferenceRequest {
+            request_id: generate_request_id(),
+            conference_solution_key: ConferenceSolutionKey::new(solution_type),
+            status: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConferenceSolutionKey {
+    #[serde(rename = "type")]
+    pub solution_type: String,
+}
+
+impl ConferenceSolutionKey {
+    pub fn new(solution_type: impl Into<String>) -> Self {
+        ConferenceSolutionKey {
+            solution_type: solution_type.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConferenceSolution {
+    pub key: ConferenceSolutionKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_uri: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConferenceRequestStatus {
+    pub status_code: ConferenceStatusCode,
+}
+
+/// 会議作成リクエストの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConferenceStatusCode {
+    /// 作成処理中
+    Pending,
+    /// 作成済み。entryPoints が設定されています
+    Success,
+    /// 作成に失敗
+    Failure,
+}
+
+/// 会議への参加方法の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryPointType {
+    Video,
+    Phone,
+    Sip,
+    More,
+}
+
+/// 会議への参加方法
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryPoint {
+    pub entry_point_type: EntryPointType,
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meeting_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passcode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl EntryPoint {
+    pub fn new(entry_point_type: EntryPointType, uri: impl Into<String>) -> Self {
+        EntryPoint {
+            entry_point_type,
+            uri: uri.into(),
+            label: None,
+            pin: None,
+            access_code: None,
+            meeting_code: None,
+            passcode: None,
+            password: None,
+        }
+    }
+
+    pub fn video(uri: impl Into<String>) -> Self {
+        Self::new(EntryPointType::Video, uri)
+    }
+
+    /// 電話での参加方法。`uri` は "tel:+1-555-0100" のような形式です
+    pub fn phone(uri: impl Into<String>) -> Self {
+        Self::new(EntryPointType::Phone, uri)
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_passcode(mut self, passcode: impl Into<String>) -> Self {
+        self.passcode = Some(passcode.into());
+        self
+    }
+
+    pub fn with_meeting_code(mut self, meeting_code: impl Into<String>) -> Self {
+        self.meeting_code = Some(meeting_code.into());
+        self
+    }
+}
+
+/// 会議作成リクエストのIDを生成します
+pub fn generate_request_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_google_meet_serialization() {
+        let data = ConferenceData::google_meet();
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(
+            json["createRequest"]["conferenceSolutionKey"]["type"],
+            "hangoutsMeet"
+        );
+        assert_eq!(
+            json["createRequest"]["requestId"].as_str().unwrap().len(),
+            32
+        );
+        assert!(json.get("entryPoints").is_none());
+        assert!(data.validate().is_ok());
+        assert_ne!(
+            data.create_request.unwrap().request_id,
+            ConferenceData::google_meet()
+                .create_request
+                .unwrap()
+                .request_id
+        );
+    }
+
+    #[test]
+    fn test_third_party() {
+        let data = ConferenceData::third_party("Zoom", "https://zoom.us/j/123")
+            .with_entry_point(EntryPoint::phone("tel:+81-3-0000-0000").with_passcode("9999"))
+            .with_conference_id("123");
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json["conferenceSolution"]["key"]["type"], "addOn");
+        assert_eq!(json["conferenceSolution"]["name"], "Zoom");
+        assert_eq!(json["entryPoints"][0]["entryPointType"], "video");
+        assert_eq!(json["entryPoints"][1]["passcode"], "9999");
+        assert_eq!(data.video_uri(), Some("https://zoom.us/j/123"));
+        assert!(data.validate().is_ok());
+
+        let two_videos = data.with_entry_point(EntryPoint::video("https://zoom.us/j/456"));
+        assert!(two_videos.validate().is_err());
+        assert!(ConferenceData::default().validate().is_err());
+    }
+
+    #[test]
+    fn test_status_deserialization() {
+        let json = r#"{
+            "createRequest": {
+                "requestId": "abc",
+                "conferenceSolutionKey": {"type": "hangoutsMeet"},
+                "status": {"statusCode": "success"}
+            },
+            "entryPoints": [
+                {"entryPointType": "video", "uri": "https://meet.google.com/aaa-bbbb-ccc", "label": "meet.google.com/aaa-bbbb-ccc"}
+            ],
+            "conferenceSolution": {"key": {"type": "hangoutsMeet"}, "name": "Google Meet"},
+            "conferenceId": "aaa-bbbb-ccc"
+        }"#;
+        let data: ConferenceData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.status(), Some(ConferenceStatusCode::Success));
+        assert_eq!(
+            data.video_uri(),
+            Some("https://meet.google.com/aaa-bbbb-ccc")
+        );
+    }
+}
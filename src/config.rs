@@ -1,7 +1,24 @@
+use chrono_tz::Tz;
+
 pub struct GCalConfig {
     pub api_base_url: String,
     pub timeout_seconds: u64,
     pub credentials: Option<String>,
+    /// `CalendarClient::event_builder` で作成するイベントと、タイムゾーンを指定せずに
+    /// `CalendarClient::create_event` で作成するイベントの既定のタイムゾーン
+    pub default_time_zone: DefaultTimeZone,
+}
+
+/// イベントの既定のタイムゾーンの決め方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DefaultTimeZone {
+    #[default]
+    Utc,
+    /// 指定したタイムゾーン
+    Fixed(Tz),
+    /// カレンダーの所有者の設定（settings の timezone）。
+    /// `CalendarClient::default_time_zone` を最初に呼び出した時点で取得し、クライアントごとに保持します
+    CalendarOwner,
 }
 
 impl GCalConfig {
@@ -14,6 +31,7 @@ impl GCalConfig {
             api_base_url: "https://www.googleapis.com/calendar/v3".to_string(),
             timeout_seconds: 30,
             credentials: Some(credentials),
+            default_time_zone: DefaultTimeZone::Utc,
         })
     }
 }
@@ -24,6 +42,7 @@ impl Default for GCalConfig {
             api_base_url: "https://www.googleapis.com/calendar/v3".to_string(),
             timeout_seconds: 30,
             credentials: None,
+            default_time_zone: DefaultTimeZone::Utc,
         }
     }
 }
@@ -38,4 +57,9 @@ impl GCalConfig {
         self.timeout_seconds = seconds;
         self
     }
+
+    pub fn with_default_time_zone(mut self, default_time_zone: DefaultTimeZone) -> Self {
+        self.default_time_zone = default_time_zone;
+        self
+    }
 }
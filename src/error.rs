@@ -1,3 +1,4 @@
+use crate::validation::ValidationErrors;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,11 +15,45 @@ pub enum GCalError {
     #[error("認証エラー: {0}")]
     AuthError(String),
 
+    /// 送信前の検証で見つかったエラー（フィールドのパスごと）
     #[error("バリデーションエラー: {0}")]
-    ValidationError(String),
+    ValidationError(ValidationErrors),
+
+    #[error("既に存在します: {0}")]
+    AlreadyExists(String),
+
+    /// If-Match で指定したETagが現在のリソースと一致しない（412）
+    #[error("前提条件エラー: {0}")]
+    PreconditionFailed(String),
+
+    #[error("APIエラー: ステータスコード {status} - {message}")]
+    ApiError { status: u16, message: String },
 
     #[error("その他エラー: {0}")]
     Other(String),
 }
 
+impl GCalError {
+    /// 特定のフィールドに属さない検証エラーを作成します
+    pub fn validation(message: impl Into<String>) -> Self {
+        GCalError::ValidationError(ValidationErrors::from(message.into()))
+    }
+
+    /// 指定したフィールドの検証エラーを作成します
+    pub fn field_validation(path: impl Into<String>, message: impl Into<String>) -> Self {
+        GCalError::ValidationError(ValidationErrors::single(path, message))
+    }
+
+    /// APIエラーの場合、HTTPステータスコードを返します
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            GCalError::ApiError { status, .. } => Some(*status),
+            GCalError::AlreadyExists(_) => Some(409),
+            GCalError::PreconditionFailed(_) => Some(412),
+            GCalError::RequestError(e) => e.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, GCalError>;
@@ -1,27 +1,112 @@
-use chrono::{DateTime, Utc};
+use crate::attachment::{self, Attachment};
+use crate::attendee::{Attendee, Organizer};
+use crate::colors::EventColor;
+use crate::conference::ConferenceData;
+use crate::event_type::{
+    EventType, FocusTimeProperties, OutOfOfficeProperties, WorkingLocationProperties,
+};
+pub use crate::extended_properties::{ExtendedProperties, PropertyScope};
+use crate::interval::Interval;
+use crate::reminders::{self, Reminder, Reminders, ScheduledReminder};
+use crate::timezone_utils::{self, TimezoneError};
+use crate::validation::{is_valid_email, ValidationErrors};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// イベントタイトルの最大文字数
+pub const MAX_SUMMARY_LENGTH: usize = 1024;
+/// 説明の最大文字数
+pub const MAX_DESCRIPTION_LENGTH: usize = 8192;
+/// 場所の最大文字数
+pub const MAX_LOCATION_LENGTH: usize = 1024;
+
+/// カレンダーのイベント
+///
+/// このクレートが型として扱わないフィールド（visibility、transparency など）は
+/// `extra` に保持され、シリアライズ時にそのまま出力されます。取得したイベントを変更して
+/// 更新しても、他のクライアントが設定した値は失われません。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Event {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
+    /// リソースのバージョンを表すETag（読み取り専用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// Google Calendar の画面でイベントを開くURL（読み取り専用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_link: Option<String>,
+    /// RFC5545 のイベントUID。繰り返しイベントの各回で共通です
+    #[serde(rename = "iCalUID", skip_serializing_if = "Option::is_none")]
+    pub i_cal_uid: Option<String>,
+    /// 作成日時（読み取り専用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<DateTime<Utc>>,
+    /// 最終更新日時（読み取り専用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<DateTime<Utc>>,
+    /// iCalendar のシーケンス番号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
+    /// イベントの色。colors.get の `event` パレットのIDです
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start: Option<EventDateTime>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end: Option<EventDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organizer: Option<Organizer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attendees: Vec<Attendee>,
+    /// RFC5545 の RRULE・RDATE・EXDATE の行
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recurrence: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_properties: Option<ExtendedProperties>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminders: Option<Reminders>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conference_data: Option<ConferenceData>,
+    /// 添付ファイル（最大 `MAX_ATTACHMENTS` 件）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    /// Google Meet の参加URL（読み取り専用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hangout_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_of_office_properties: Option<OutOfOfficeProperties>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_time_properties: Option<FocusTimeProperties>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_location_properties: Option<WorkingLocationProperties>,
+    /// 型として定義されていないフィールド
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// イベントの開始・終了日時
+///
+/// 時刻指定のイベントは `date_time`、終日イベントは `date` を持ちます。
+/// 値を持たない `date_time` / `time_zone` は空文字列として扱います。
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventDateTime {
-    #[serde(rename = "dateTime")]
+    #[serde(rename = "dateTime", default, skip_serializing_if = "String::is_empty")]
     pub date_time: String,
-    #[serde(rename = "timeZone")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(rename = "timeZone", default, skip_serializing_if = "String::is_empty")]
     pub time_zone: String,
 }
 
@@ -35,10 +120,20 @@ impl EventDateTime {
 
         Ok(EventDateTime {
             date_time,
+            date: None,
             time_zone,
         })
     }
 
+    /// Creates an EventDateTime from a DateTime without a timezone
+    pub fn from_datetime<Tz: chrono::TimeZone>(dt: DateTime<Tz>) -> Self {
+        EventDateTime {
+            date_time: dt.to_rfc3339(),
+            date: None,
+            time_zone: String::new(),
+        }
+    }
+
     /// Creates an EventDateTime from a DateTime and timezone string
     pub fn from_datetime_with_tz<Tz: chrono::TimeZone>(
         dt: DateTime<Tz>,
@@ -50,12 +145,65 @@ impl EventDateTime {
 
         Ok(EventDateTime {
             date_time: dt.to_rfc3339(),
+            date: None,
             time_zone,
         })
     }
+
+    /// 終日イベント用のEventDateTimeを作成します
+    pub fn from_date(date: NaiveDate) -> Self {
+        EventDateTime {
+            date_time: String::new(),
+            date: Some(date.format("%Y-%m-%d").to_string()),
+            time_zone: String::new(),
+        }
+    }
+
+    pub fn is_all_day(&self) -> bool {
+        self.date_time.is_empty() && self.date.is_some()
+    }
+
+    /// 絶対時刻(UTC)に変換します
+    ///
+    /// オフセット付きの日時はそのまま変換します。オフセットを持たない日時（フローティング）と
+    /// 終日イベントの日付は `time_zone` で解釈し、`time_zone` が空の場合は
+    /// `default_time_zone` を使用します。
+    pub fn to_utc(&self, default_time_zone: &str) -> Result<DateTime<Utc>, TimezoneError> {
+        let time_zone = if self.time_zone.is_empty() {
+            default_time_zone
+        } else {
+            &self.time_zone
+        };
+
+        if !self.date_time.is_empty() {
+            if let Ok(dt) = DateTime::parse_from_rfc3339(&self.date_time) {
+                return Ok(dt.with_timezone(&Utc));
+            }
+            let local = NaiveDateTime::parse_from_str(&self.date_time, "%Y-%m-%dT%H:%M:%S%.f")
+                .map_err(|e| {
+                    TimezoneError::ConversionError(format!("{}: {}", self.date_time, e))
+                })?;
+            return timezone_utils::local_to_utc(local, time_zone);
+        }
+
+        if let Some(date) = &self.date {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| TimezoneError::ConversionError(format!("{}: {}", date, e)))?;
+            return timezone_utils::local_to_utc(date.and_time(Default::default()), time_zone);
+        }
+
+        Err(TimezoneError::ConversionError(
+            "日時(dateTime)も日付(date)も指定されていません".to_string(),
+        ))
+    }
 }
 
 impl Event {
+    /// イベントを作成します
+    ///
+    /// `time_zone` を指定しない場合、開始・終了はオフセット付きの日時だけを持ち、タイムゾーンは空になります。
+    /// `CalendarClient::create_event` で作成する時点で、クライアントの既定のタイムゾーン
+    /// （`GCalConfig::with_default_time_zone`）が設定されます。
     pub fn new(
         summary: String,
         start: DateTime<Utc>,
@@ -64,18 +212,22 @@ impl Event {
         location: Option<String>,
         time_zone: Option<String>,
     ) -> Result<Self, String> {
-        // タイムゾーンのデフォルト値はUTC
-        let tz = time_zone.unwrap_or_else(|| "UTC".to_string());
-
-        // タイムゾーンの検証
-        if !crate::timezone_utils::validate_timezone(&tz) {
-            return Err(format!("無効なタイムゾーン文字列です: {}", tz));
-        }
-
-        // start EventDateTimeの作成
-        let start_dt = EventDateTime::from_datetime_with_tz(start, tz.clone())?;
-        // end EventDateTimeの作成
-        let end_dt = EventDateTime::from_datetime_with_tz(end, tz)?;
+        let (start_dt, end_dt) = match time_zone {
+            Some(tz) => {
+                // タイムゾーンの検証
+                if !crate::timezone_utils::validate_timezone(&tz) {
+                    return Err(format!("無効なタイムゾーン文字列です: {}", tz));
+                }
+                (
+                    EventDateTime::from_datetime_with_tz(start, tz.clone())?,
+                    EventDateTime::from_datetime_with_tz(end, tz)?,
+                )
+            }
+            None => (
+                EventDateTime::from_datetime(start),
+                EventDateTime::from_datetime(end),
+            ),
+        };
 
         Ok(Event {
             id: None,
@@ -85,15 +237,474 @@ impl Event {
             location,
             start: Some(start_dt),
             end: Some(end_dt),
+            ..Default::default()
         })
     }
 
-    pub fn validate(&self) -> Result<(), String> {
-        if self.summary.is_none() {
-            return Err("イベントタイトル(summary)が必要です".to_string());
+    /// イベントを検証し、見つかったエラーをフィールドのパスとともにすべて返します
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        match &self.summary {
+            None => errors.add("summary", "イベントタイトル(summary)が必要です"),
+            Some(summary) => errors.check("summary", check_length(summary, MAX_SUMMARY_LENGTH)),
+        }
+        if let Some(description) = &self.description {
+            errors.check(
+                "description",
+                check_length(description, MAX_DESCRIPTION_LENGTH),
+            );
+        }
+        if let Some(location) = &self.location {
+            errors.check("location", check_length(location, MAX_LOCATION_LENGTH));
+        }
+
+        self.validate_time(&mut errors);
+        self.validate_attendees(&mut errors);
+        self.validate_recurrence(&mut errors);
+        attachment::validate_attachments(&self.attachments, &mut errors);
+
+        if let Some(properties) = &self.extended_properties {
+            errors.check("extendedProperties", properties.validate());
+        }
+        if let Some(reminders) = &self.reminders {
+            reminders::validate_reminders(reminders, &mut errors);
+        }
+        if let Some(conference) = &self.conference_data {
+            errors.check("conferenceData", conference.validate());
+        }
+        errors.check("eventType", self.validate_event_type());
+        errors.into_result()
+    }
+
+    /// 開始・終了の形式、種類（終日か時刻指定か）の一致、前後関係を検証します
+    fn validate_time(&self, errors: &mut ValidationErrors) {
+        let (start, end) = match (&self.start, &self.end) {
+            (Some(start), Some(end)) => (start, end),
+            (None, None) => return,
+            (Some(_), None) => {
+                return errors.add("end", "開始日時を指定する場合は終了日時が必要です")
+            }
+            (None, Some(_)) => {
+                return errors.add("start", "終了日時を指定する場合は開始日時が必要です")
+            }
+        };
+
+        let start_ok = validate_date_time(start, "start", errors);
+        let end_ok = validate_date_time(end, "end", errors);
+        if !start_ok || !end_ok {
+            return;
+        }
+        if start.is_all_day() != end.is_all_day() {
+            return errors.add(
+                "end",
+                "開始と終了は両方とも時刻指定、または両方とも終日である必要があります",
+            );
+        }
+        // 終日の日付は同じタイムゾーンで比較するため、どちらもUTCとして解釈する
+        let default_tz = if start.is_all_day() {
+            "UTC"
+        } else if !start.time_zone.is_empty() {
+            start.time_zone.as_str()
+        } else {
+            end.time_zone.as_str()
+        };
+        if let (Ok(s), Ok(e)) = (start.to_utc(default_tz), end.to_utc(default_tz)) {
+            if e <= s {
+                errors.add("end", "終了日時は開始日時より後である必要があります");
+            }
+        }
+    }
+
+    /// 主催者・参加者のメールアドレスの形式と、参加者の重複を検証します
+    fn validate_attendees(&self, errors: &mut ValidationErrors) {
+        if let Some(email) = self.organizer.as_ref().and_then(|o| o.email.as_ref()) {
+            if !is_valid_email(email) {
+                errors.add(
+                    "organizer.email",
+                    format!("メールアドレスの形式が正しくありません: {}", email),
+                );
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        for (i, attendee) in self.attendees.iter().enumerate() {
+            let path = format!("attendees[{}].email", i);
+            if !is_valid_email(&attendee.email) {
+                errors.add(
+                    path,
+                    format!("メールアドレスの形式が正しくありません: {}", attendee.email),
+                );
+            } else if !seen.insert(attendee.email.to_lowercase()) {
+                errors.add(path, format!("参加者が重複しています: {}", attendee.email));
+            }
+        }
+    }
+
+    /// 繰り返しルールの形式と、繰り返しを設定できるイベントかを検証します
+    fn validate_recurrence(&self, errors: &mut ValidationErrors) {
+        if self.recurrence.is_empty() {
+            return;
+        }
+        if self.extra.contains_key("recurringEventId") {
+            errors.add(
+                "recurrence",
+                "繰り返しイベントの各回(インスタンス)には繰り返しルールを設定できません",
+            );
+        }
+        match &self.start {
+            None => errors.add("recurrence", "繰り返しイベントには開始日時が必要です"),
+            // 時刻指定の繰り返しイベントは展開のためにタイムゾーンが必須
+            Some(start) if !start.is_all_day() && start.time_zone.is_empty() => errors.add(
+                "start.timeZone",
+                "時刻指定の繰り返しイベントにはタイムゾーンが必要です",
+            ),
+            _ => {}
+        }
+        for (i, rule) in self.recurrence.iter().enumerate() {
+            let name = rule
+                .split([':', ';'])
+                .next()
+                .unwrap_or_default()
+                .to_ascii_uppercase();
+            if !matches!(name.as_str(), "RRULE" | "EXRULE" | "RDATE" | "EXDATE")
+                || !rule.contains(':')
+            {
+                errors.add(
+                    format!("recurrence[{}]", i),
+                    format!(
+                        "RRULE・EXRULE・RDATE・EXDATE のいずれかである必要があります: {}",
+                        rule
+                    ),
+                );
+            }
+        }
+    }
+
+    /// 新しく作成するイベントとして検証します
+    ///
+    /// `validate` に加えて、作成できない種類（読み取り専用・認識できない種類）を拒否します。
+    pub fn validate_new(&self) -> Result<(), ValidationErrors> {
+        let mut errors = self.validate().err().unwrap_or_default();
+        if let Some(event_type) = self.event_type.as_ref().filter(|t| !t.is_writable()) {
+            errors.add(
+                "eventType",
+                format!(
+                    "この種類のイベントは作成できません: {}",
+                    event_type.as_str()
+                ),
+            );
+        }
+        errors.into_result()
+    }
+
+    /// イベントの種類と種類ごとの設定が整合しているかを検証します
+    fn validate_event_type(&self) -> Result<(), String> {
+        let event_type = self.event_type.clone().unwrap_or_default();
+        let mismatched = |field: &str| {
+            Err(format!(
+                "{} はイベントの種類が {} の場合は指定できません",
+                field,
+                event_type.as_str()
+            ))
+        };
+        if self.out_of_office_properties.is_some() && event_type != EventType::OutOfOffice {
+            return mismatched("outOfOfficeProperties");
+        }
+        if self.focus_time_properties.is_some() && event_type != EventType::FocusTime {
+            return mismatched("focusTimeProperties");
+        }
+        if self.working_location_properties.is_some() && event_type != EventType::WorkingLocation {
+            return mismatched("workingLocationProperties");
+        }
+
+        match event_type {
+            EventType::OutOfOffice | EventType::FocusTime => {
+                let all_day = [&self.start, &self.end]
+                    .into_iter()
+                    .any(|dt| dt.as_ref().is_some_and(EventDateTime::is_all_day));
+                if all_day {
+                    return Err(format!(
+                        "{} のイベントは終日にできません",
+                        event_type.as_str()
+                    ));
+                }
+            }
+            EventType::WorkingLocation => match &self.working_location_properties {
+                Some(properties) => properties.validate()?,
+                None => {
+                    return Err(
+                        "勤務場所のイベントには workingLocationProperties が必要です".to_string(),
+                    )
+                }
+            },
+            _ => {}
         }
         Ok(())
     }
+
+    /// 不在のイベントを作成します
+    pub fn out_of_office(
+        summary: String,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        time_zone: Option<String>,
+        properties: OutOfOfficeProperties,
+    ) -> Result<Self, String> {
+        let mut event = Event::new(summary, start, end, None, None, time_zone)?;
+        event.event_type = Some(EventType::OutOfOffice);
+        event.out_of_office_properties = Some(properties);
+        Ok(event)
+    }
+
+    /// サイレント時間のイベントを作成します
+    pub fn focus_time(
+        summary: String,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        time_zone: Option<String>,
+        properties: FocusTimeProperties,
+    ) -> Result<Self, String> {
+        let mut event = Event::new(summary, start, end, None, None, time_zone)?;
+        event.event_type = Some(EventType::FocusTime);
+        event.focus_time_properties = Some(properties);
+        Ok(event)
+    }
+
+    /// 指定日の勤務場所を表す終日イベントを作成します
+    pub fn working_location(
+        date: NaiveDate,
+        properties: WorkingLocationProperties,
+    ) -> Result<Self, String> {
+        properties.validate()?;
+        let next_day = date
+            .succ_opt()
+            .ok_or_else(|| format!("日付が範囲外です: {}", date))?;
+        Ok(Event {
+            summary: Some(properties.label().unwrap_or("勤務場所").to_string()),
+            start: Some(EventDateTime::from_date(date)),
+            end: Some(EventDateTime::from_date(next_day)),
+            event_type: Some(EventType::WorkingLocation),
+            working_location_properties: Some(properties),
+            ..Default::default()
+        })
+    }
+
+    /// 指定した開始日時の回について、リマインダーが通知される日時を返します
+    ///
+    /// リマインダーが未設定、または `useDefault` の場合は `calendar_defaults`
+    /// （カレンダーリストの `defaultReminders`）が適用されます。
+    /// 繰り返しイベントでは各回の開始日時を `occurrence_start` に渡してください。
+    pub fn effective_reminders(
+        &self,
+        occurrence_start: DateTime<Utc>,
+        calendar_defaults: &[Reminder],
+    ) -> Vec<ScheduledReminder> {
+        let effective = match &self.reminders {
+            Some(reminders) => reminders.effective(calendar_defaults),
+            None => calendar_defaults,
+        };
+        reminders::schedule(effective, occurrence_start)
+    }
+
+    /// イベントが占める時間帯を返します
+    ///
+    /// フローティング日時と終日イベントは `default_time_zone` で解釈します。
+    pub fn interval_in(&self, default_time_zone: &str) -> Result<Interval, TimezoneError> {
+        let (start, end) = match (&self.start, &self.end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => {
+                return Err(TimezoneError::ConversionError(
+                    "開始・終了日時が指定されていません".to_string(),
+                ))
+            }
+        };
+        let start = start.to_utc(default_time_zone)?;
+        let end = end.to_utc(default_time_zone)?;
+        Interval::new(start, end)
+            .ok_or_else(|| TimezoneError::ConversionError("終了日時が開始日時以前です".to_string()))
+    }
+
+    /// フローティング日時をUTCとして解釈した時間帯を返します
+    pub fn interval(&self) -> Result<Interval, TimezoneError> {
+        self.interval_in("UTC")
+    }
+
+    /// 構造体のフィールドを拡張プロパティとして保存します
+    ///
+    /// 詳細は [`ExtendedProperties::store`] を参照してください。
+    pub fn store_properties<T: Serialize>(
+        &mut self,
+        scope: PropertyScope,
+        value: &T,
+    ) -> Result<(), String> {
+        self.extended_properties
+            .get_or_insert_with(Default::default)
+            .store(scope, value)
+    }
+
+    /// 拡張プロパティから構造体を読み込みます
+    pub fn load_properties<T: serde::de::DeserializeOwned>(
+        &self,
+        scope: PropertyScope,
+    ) -> Result<T, String> {
+        self.extended_properties
+            .clone()
+            .unwrap_or_default()
+            .load(scope)
+    }
+
+    /// private拡張プロパティの値を返します
+    pub fn private_property(&self, key: &str) -> Option<&str> {
+        self.extended_properties
+            .as_ref()
+            .and_then(|p| p.private.get(key))
+            .map(String::as_str)
+    }
+
+    /// private拡張プロパティを設定します
+    pub fn set_private_property(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.extended_properties
+            .get_or_insert_with(Default::default)
+            .private
+            .insert(key.into(), value.into());
+    }
+
+    /// 添付ファイルを追加します。同じファイルが既に添付されている場合は置き換えます
+    pub fn add_attachment(&mut self, attachment: Attachment) {
+        match self
+            .attachments
+            .iter_mut()
+            .find(|a| a.is_same_file(&attachment))
+        {
+            Some(existing) => *existing = attachment,
+            None => self.attachments.push(attachment),
+        }
+    }
+
+    /// ドライブのファイルIDが一致する添付ファイルを削除して返します
+    pub fn remove_attachment(&mut self, file_id: &str) -> Option<Attachment> {
+        let index = self
+            .attachments
+            .iter()
+            .position(|a| a.file_id.as_deref() == Some(file_id))?;
+        Some(self.attachments.remove(index))
+    }
+
+    /// `color_id` が既定のパレットの色であれば返します
+    pub fn color(&self) -> Option<EventColor> {
+        self.color_id.as_deref().and_then(EventColor::from_id)
+    }
+
+    pub fn set_color(&mut self, color: EventColor) {
+        self.color_id = Some(color.id().to_string());
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.status.as_deref() == Some("cancelled")
+    }
+
+    /// 2つのイベントの時間帯が重なっているかを返します
+    ///
+    /// フローティング日時と終日イベントは `default_tz`（通常はカレンダーのタイムゾーン）で解釈します。
+    /// キャンセル済みのイベントや、時間帯を特定できないイベントは重ならないものとして扱います。
+    pub fn conflicts_with(&self, other: &Event, default_tz: Tz) -> bool {
+        if self.is_cancelled() || other.is_cancelled() {
+            return false;
+        }
+        match (
+            self.interval_in(default_tz.name()),
+            other.interval_in(default_tz.name()),
+        ) {
+            (Ok(a), Ok(b)) => a.overlaps(&b),
+            _ => false,
+        }
+    }
+}
+
+/// 時間帯が重なっているイベントの組
+#[derive(Debug)]
+pub struct DoubleBooking<'a> {
+    pub first: &'a Event,
+    pub second: &'a Event,
+    pub overlap: Interval,
+}
+
+/// イベント一覧からダブルブッキングしている組をすべて検出します
+///
+/// 判定基準は [`Event::conflicts_with`] と同じです。
+pub fn find_double_bookings(events: &[Event], default_tz: Tz) -> Vec<DoubleBooking<'_>> {
+    let mut timed: Vec<(Interval, &Event)> = events
+        .iter()
+        .filter(|e| !e.is_cancelled())
+        .filter_map(|e| e.interval_in(default_tz.name()).ok().map(|i| (i, e)))
+        .collect();
+    timed.sort_by_key(|(i, _)| *i);
+
+    let mut bookings = Vec::new();
+    for (idx, (interval, event)) in timed.iter().enumerate() {
+        // 開始順に並んでいるので、開始が終了を超えた時点で以降は重ならない
+        for (other_interval, other) in timed[idx + 1..]
+            .iter()
+            .take_while(|(i, _)| i.start < interval.end)
+        {
+            if let Some(overlap) = interval.intersection(other_interval) {
+                bookings.push(DoubleBooking {
+                    first: event,
+                    second: other,
+                    overlap,
+                });
+            }
+        }
+    }
+    bookings
+}
+
+/// 日時の形式とタイムゾーンを検証し、問題がなければ true を返します
+fn validate_date_time(dt: &EventDateTime, path: &str, errors: &mut ValidationErrors) -> bool {
+    let before = errors.len();
+    match (dt.date_time.is_empty(), &dt.date) {
+        (false, Some(_)) => errors.add(path, "dateTime と date は同時に指定できません"),
+        (true, None) => errors.add(path, "dateTime または date が必要です"),
+        (true, Some(date)) => {
+            if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+                errors.add(
+                    format!("{}.date", path),
+                    format!("日付の形式が正しくありません: {}", date),
+                );
+            }
+        }
+        (false, None) => {
+            let has_offset = DateTime::parse_from_rfc3339(&dt.date_time).is_ok();
+            let floating =
+                NaiveDateTime::parse_from_str(&dt.date_time, "%Y-%m-%dT%H:%M:%S%.f").is_ok();
+            if !has_offset && !floating {
+                errors.add(
+                    format!("{}.dateTime", path),
+                    format!("日時の形式が正しくありません: {}", dt.date_time),
+                );
+            } else if floating && dt.time_zone.is_empty() {
+                errors.add(
+                    format!("{}.timeZone", path),
+                    "オフセットのない日時にはタイムゾーンが必要です",
+                );
+            }
+        }
+    }
+    if !dt.time_zone.is_empty() && !timezone_utils::validate_timezone(&dt.time_zone) {
+        errors.add(
+            format!("{}.timeZone", path),
+            format!("無効なタイムゾーン文字列です: {}", dt.time_zone),
+        );
+    }
+    errors.len() == before
+}
+
+fn check_length(value: &str, max: usize) -> Result<(), String> {
+    let len = value.chars().count();
+    if len > max {
+        return Err(format!("{}文字以内である必要があります: {}文字", max, len));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -104,13 +715,9 @@ mod tests {
     #[test]
     fn test_validate_ok() {
         let ev = Event {
-            id: None,
             status: Some("confirmed".to_string()),
             summary: Some("テスト会議".to_string()),
-            description: None,
-            location: None,
-            start: None,
-            end: None,
+            ..Default::default()
         };
         assert!(ev.validate().is_ok());
     }
@@ -118,17 +725,315 @@ mod tests {
     #[test]
     fn test_validate_fail() {
         let ev = Event {
-            id: None,
-            status: None,
             summary: None,
-            description: None,
-            location: None,
-            start: None,
-            end: None,
+            ..Default::default()
+        };
+        assert!(ev.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_collects_field_errors() {
+        let mut ev = timed("2024-01-10T11:00:00Z", "2024-01-10T10:00:00Z");
+        ev.start.as_mut().unwrap().time_zone.clear();
+        ev.summary = Some("あ".repeat(MAX_SUMMARY_LENGTH + 1));
+        ev.attendees = vec![
+            Attendee::new("alice@example.com"),
+            Attendee::new("not-an-email"),
+            Attendee::new("Alice@Example.com"),
+        ];
+        ev.reminders = Some(Reminders::with_overrides(vec![
+            Reminder::popup(10),
+            Reminder::email(50000),
+        ]));
+        ev.recurrence = vec!["FREQ=WEEKLY".to_string()];
+
+        let errors = ev.validate().unwrap_err();
+        let paths: Vec<&str> = errors.errors().iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "summary",
+                "end",
+                "attendees[1].email",
+                "attendees[2].email",
+                "start.timeZone",
+                "recurrence[0]",
+                "reminders.overrides[1]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_date_times() {
+        let ok = |start: EventDateTime, end: EventDateTime| {
+            let ev = Event {
+                summary: Some("会議".to_string()),
+                start: Some(start),
+                end: Some(end),
+                ..Default::default()
+            };
+            ev.validate()
+        };
+        let date =
+            |d: &str| EventDateTime::from_date(NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap());
+        let floating = |dt: &str, tz: &str| EventDateTime {
+            date_time: dt.to_string(),
+            date: None,
+            time_zone: tz.to_string(),
+        };
+
+        assert!(ok(date("2024-01-10"), date("2024-01-11")).is_ok());
+        assert!(ok(date("2024-01-10"), date("2024-01-10"))
+            .unwrap_err()
+            .contains("end"));
+        // 終日と時刻指定の混在
+        assert!(ok(
+            date("2024-01-10"),
+            floating("2024-01-10T10:00:00", "Asia/Tokyo")
+        )
+        .unwrap_err()
+        .contains("end"));
+        // タイムゾーンのないフローティング日時、無効なタイムゾーン
+        assert!(ok(
+            floating("2024-01-10T10:00:00", ""),
+            floating("2024-01-10T11:00:00", "Asia/Tokyo")
+        )
+        .unwrap_err()
+        .contains("start.timeZone"));
+        assert!(ok(
+            floating("2024-01-10T10:00:00", "Mars/Olympus"),
+            floating("2024-01-10T11:00:00", "Asia/Tokyo")
+        )
+        .unwrap_err()
+        .contains("start.timeZone"));
+        assert!(ok(
+            floating("2024-01-10T10:00:00", "Asia/Nowhere"),
+            floating("2024-01-10T11:00:00", "Asia/Tokyo")
+        )
+        .unwrap_err()
+        .contains("start.timeZone"));
+        // 3階層のIANA名やEtc/UTC、GMTも受け付ける
+        for tz in [
+            "America/Argentina/Buenos_Aires",
+            "Etc/UTC",
+            "GMT",
+            "GMT+09:00",
+        ] {
+            assert!(ok(
+                floating("2024-01-10T10:00:00", tz),
+                floating("2024-01-11T10:00:00", tz)
+            )
+            .is_ok());
+        }
+        // 異なるタイムゾーン同士でも絶対時刻で比較する
+        assert!(ok(
+            floating("2024-01-10T10:00:00", "Asia/Tokyo"),
+            floating("2024-01-09T21:00:00", "America/New_York")
+        )
+        .is_ok());
+        let invalid_date = EventDateTime {
+            date: Some("2024-13-01".to_string()),
+            ..date("2024-01-10")
+        };
+        assert!(ok(invalid_date, date("2024-01-11"))
+            .unwrap_err()
+            .contains("start.date"));
+    }
+
+    #[test]
+    fn test_validate_recurrence() {
+        let mut ev = timed("2024-01-10T10:00:00Z", "2024-01-10T11:00:00Z");
+        ev.recurrence = vec![
+            "RRULE:FREQ=WEEKLY;BYDAY=WE".to_string(),
+            "EXDATE;TZID=Asia/Tokyo:20240117T100000".to_string(),
+        ];
+        assert!(ev.validate().is_ok());
+
+        ev.extra.insert(
+            "recurringEventId".to_string(),
+            serde_json::json!("abcde12345"),
+        );
+        assert!(ev.validate().unwrap_err().contains("recurrence"));
+    }
+
+    #[test]
+    fn test_round_trip_preserves_unknown_fields() {
+        let json = serde_json::json!({
+            "kind": "calendar#event",
+            "etag": "\"3181161784712000\"",
+            "id": "abcde12345",
+            "status": "confirmed",
+            "htmlLink": "https://www.google.com/calendar/event?eid=abc",
+            "created": "2024-01-01T00:00:00.000Z",
+            "updated": "2024-01-02T03:04:05.678Z",
+            "iCalUID": "abcde12345@google.com",
+            "sequence": 2,
+            "summary": "定例",
+            "colorId": "5",
+            "visibility": "private",
+            "transparency": "transparent",
+            "attachments": [{"fileUrl": "https://drive.google.com/file/d/1", "title": "議事録"}],
+            "source": {"url": "https://example.com", "title": "Example"},
+            "start": {"dateTime": "2024-01-10T10:00:00Z"},
+            "end": {"dateTime": "2024-01-10T11:00:00Z"}
+        });
+        let mut event: Event = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(event.i_cal_uid.as_deref(), Some("abcde12345@google.com"));
+        assert_eq!(event.sequence, Some(2));
+        assert_eq!(
+            event.updated.unwrap().timestamp_millis(),
+            DateTime::parse_from_rfc3339("2024-01-02T03:04:05.678Z")
+                .unwrap()
+                .timestamp_millis()
+        );
+        assert_eq!(event.color_id.as_deref(), Some("5"));
+        assert_eq!(event.color(), Some(EventColor::Banana));
+        assert!(!event.extra.contains_key("colorId"));
+        assert!(!event.extra.contains_key("summary"));
+
+        event.summary = Some("定例（変更）".to_string());
+        let out = serde_json::to_value(&event).unwrap();
+        for key in [
+            "kind",
+            "colorId",
+            "visibility",
+            "transparency",
+            "attachments",
+            "source",
+        ] {
+            assert_eq!(out[key], json[key], "{} が失われています", key);
+        }
+        assert_eq!(out["iCalUID"], json["iCalUID"]);
+        assert_eq!(out["summary"], "定例（変更）");
+    }
+
+    #[test]
+    fn test_special_event_types() {
+        use crate::event_type::{AutoDeclineMode, ChatStatus};
+
+        let now = Utc::now();
+        let ooo = Event::out_of_office(
+            "休暇".to_string(),
+            now,
+            now + chrono::Duration::days(3),
+            Some("Asia/Tokyo".to_string()),
+            OutOfOfficeProperties::new(AutoDeclineMode::DeclineAllConflictingInvitations)
+                .with_decline_message("休暇中です"),
+        )
+        .unwrap();
+        assert!(ooo.validate().is_ok());
+        let json = serde_json::to_value(&ooo).unwrap();
+        assert_eq!(json["eventType"], "outOfOffice");
+        assert_eq!(
+            json["outOfOfficeProperties"]["autoDeclineMode"],
+            "declineAllConflictingInvitations"
+        );
+
+        let focus = Event::focus_time(
+            "集中".to_string(),
+            now,
+            now + chrono::Duration::hours(2),
+            None,
+            FocusTimeProperties::new(AutoDeclineMode::DeclineNone)
+                .with_chat_status(ChatStatus::DoNotDisturb),
+        )
+        .unwrap();
+        assert!(focus.validate().is_ok());
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let wfh = Event::working_location(date, WorkingLocationProperties::home_office()).unwrap();
+        assert!(wfh.validate().is_ok());
+        assert_eq!(wfh.summary.as_deref(), Some("自宅"));
+        assert_eq!(
+            wfh.end.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-16")
+        );
+
+        // 種類と設定の不一致、終日の不在は不正
+        let mut mismatched = focus.clone();
+        mismatched.event_type = Some(EventType::Default);
+        assert!(mismatched.validate().is_err());
+        let mut all_day_ooo = ooo.clone();
+        all_day_ooo.start = wfh.start.clone();
+        assert!(all_day_ooo.validate().is_err());
+
+        // 認識できない種類のイベントは元の値のまま読み書きできるが、新しく作成はできない
+        let mut unknown: Event = serde_json::from_value(serde_json::json!({
+            "summary": "新しい種類",
+            "eventType": "someFutureType",
+            "start": {"dateTime": "2024-01-15T10:00:00Z"},
+            "end": {"dateTime": "2024-01-15T11:00:00Z"}
+        }))
+        .unwrap();
+        assert_eq!(
+            unknown.event_type,
+            Some(EventType::Other("someFutureType".to_string()))
+        );
+        assert!(unknown.validate().is_ok());
+        assert_eq!(
+            serde_json::to_value(&unknown).unwrap()["eventType"],
+            "someFutureType"
+        );
+        assert!(unknown.validate_new().unwrap_err().contains("eventType"));
+        unknown.event_type = Some(EventType::Birthday);
+        assert!(unknown.validate_new().unwrap_err().contains("eventType"));
+        unknown.event_type = None;
+        assert!(unknown.validate_new().is_ok());
+    }
+
+    #[test]
+    fn test_reminders() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let defaults = [Reminder::popup(30)];
+        let mut ev = Event {
+            summary: Some("オンコール".to_string()),
+            ..Default::default()
         };
+        assert_eq!(ev.effective_reminders(start, &defaults).len(), 1);
+
+        ev.reminders = Some(Reminders::with_overrides(vec![]));
+        assert!(ev.effective_reminders(start, &defaults).is_empty());
+
+        ev.reminders = Some(Reminders::with_overrides(vec![Reminder::email(50000)]));
         assert!(ev.validate().is_err());
     }
 
+    #[test]
+    fn test_private_property() {
+        let mut ev = Event::default();
+        assert!(ev.private_property("jira").is_none());
+        ev.set_private_property("jira", "PROJ-1");
+        assert_eq!(ev.private_property("jira"), Some("PROJ-1"));
+        let json = serde_json::to_value(&ev).unwrap();
+        assert_eq!(
+            json["extendedProperties"],
+            serde_json::json!({"private": {"jira": "PROJ-1"}})
+        );
+    }
+
+    #[test]
+    fn test_add_and_remove_attachment() {
+        let mut ev = Event::default();
+        ev.add_attachment(Attachment::drive_file("a").with_title("アジェンダ"));
+        ev.add_attachment(Attachment::drive_file("b"));
+        ev.add_attachment(Attachment::drive_file("a").with_title("アジェンダ（改訂）"));
+        assert_eq!(ev.attachments.len(), 2);
+        assert_eq!(
+            ev.attachments[0].title.as_deref(),
+            Some("アジェンダ（改訂）")
+        );
+
+        assert!(ev.remove_attachment("a").is_some());
+        assert!(ev.remove_attachment("a").is_none());
+        assert_eq!(ev.attachments.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&ev).unwrap()["attachments"][0]["fileId"],
+            "b"
+        );
+    }
+
     #[test]
     fn test_new_with_utc() {
         let start = Utc::now();
@@ -144,8 +1049,10 @@ mod tests {
         assert!(event.is_ok());
         let event = event.unwrap();
         assert_eq!(event.summary, Some("テストイベント".to_string()));
-        assert_eq!(event.start.unwrap().time_zone, "UTC");
-        assert_eq!(event.end.unwrap().time_zone, "UTC");
+        // タイムゾーンは作成時にクライアントの既定値が設定される
+        assert!(event.start.as_ref().unwrap().time_zone.is_empty());
+        assert!(event.end.as_ref().unwrap().time_zone.is_empty());
+        assert_eq!(event.start.unwrap().to_utc("UTC").unwrap(), start);
     }
 
     #[test]
@@ -166,6 +1073,112 @@ mod tests {
         assert_eq!(event.end.unwrap().time_zone, "Asia/Tokyo");
     }
 
+    fn timed(start: &str, end: &str) -> Event {
+        Event {
+            summary: Some("会議".to_string()),
+            start: Some(EventDateTime::new(start.to_string(), "Asia/Tokyo".to_string()).unwrap()),
+            end: Some(EventDateTime::new(end.to_string(), "Asia/Tokyo".to_string()).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_event_date_time_to_utc() {
+        use chrono::TimeZone;
+        let expected = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        // オフセット付き
+        let dt =
+            EventDateTime::new("2024-01-01T09:00:00+09:00".to_string(), "UTC".to_string()).unwrap();
+        assert_eq!(dt.to_utc("UTC").unwrap(), expected);
+
+        // フローティング（timeZoneで解釈）
+        let dt = EventDateTime::new("2024-01-01T09:00:00".to_string(), "Asia/Tokyo".to_string())
+            .unwrap();
+        assert_eq!(dt.to_utc("UTC").unwrap(), expected);
+
+        // 終日（timeZoneが空ならデフォルトで解釈）
+        let dt = EventDateTime::from_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        assert!(dt.is_all_day());
+        assert_eq!(
+            dt.to_utc("Asia/Tokyo").unwrap(),
+            Utc.with_ymd_and_hms(2023, 12, 31, 15, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_all_day_serialization() {
+        let dt = EventDateTime::from_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        let json = serde_json::to_value(&dt).unwrap();
+        assert_eq!(json, serde_json::json!({"date": "2024-01-01"}));
+
+        let parsed: EventDateTime = serde_json::from_str(r#"{"date": "2024-01-02"}"#).unwrap();
+        assert_eq!(parsed.date.as_deref(), Some("2024-01-02"));
+        assert!(parsed.date_time.is_empty());
+    }
+
+    #[test]
+    fn test_conflicts_with() {
+        let a = timed("2024-01-01T09:00:00", "2024-01-01T10:00:00");
+        let b = timed("2024-01-01T09:30:00+09:00", "2024-01-01T11:00:00+09:00");
+        let c = timed("2024-01-01T10:00:00", "2024-01-01T11:00:00");
+        assert!(a.conflicts_with(&b, Tz::UTC));
+        assert!(!a.conflicts_with(&c, Tz::UTC));
+
+        let mut cancelled = timed("2024-01-01T09:00:00", "2024-01-01T10:00:00");
+        cancelled.status = Some("cancelled".to_string());
+        assert!(!a.conflicts_with(&cancelled, Tz::UTC));
+
+        // 終日イベントはカレンダーのタイムゾーンの1日として扱う
+        let all_day = Event {
+            start: Some(EventDateTime::from_date(
+                NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            )),
+            end: Some(EventDateTime::from_date(
+                NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let morning_jst = timed("2024-01-02T08:00:00+09:00", "2024-01-02T09:00:00+09:00");
+        assert!(all_day.conflicts_with(&morning_jst, Tz::Asia__Tokyo));
+        assert!(!all_day.conflicts_with(&morning_jst, Tz::UTC));
+    }
+
+    #[test]
+    fn test_find_double_bookings() {
+        let events = vec![
+            timed("2024-01-01T09:00:00", "2024-01-01T12:00:00"),
+            timed("2024-01-01T10:00:00", "2024-01-01T10:30:00"),
+            timed("2024-01-01T12:00:00", "2024-01-01T13:00:00"),
+            timed("2024-01-01T11:30:00", "2024-01-01T12:30:00"),
+        ];
+        let bookings = find_double_bookings(&events, Tz::UTC);
+        assert_eq!(bookings.len(), 3);
+        let total: i64 = bookings
+            .iter()
+            .map(|b| b.overlap.duration().num_minutes())
+            .sum();
+        // 30分 + 30分 + 30分
+        assert_eq!(total, 90);
+
+        // タイムゾーンのないフローティング日時はカレンダーのタイムゾーンで解釈する
+        let floating = |dt: &str| EventDateTime {
+            date_time: dt.to_string(),
+            date: None,
+            time_zone: String::new(),
+        };
+        let events = vec![
+            Event {
+                start: Some(floating("2024-01-01T09:00:00")),
+                end: Some(floating("2024-01-01T10:00:00")),
+                ..Default::default()
+            },
+            timed("2024-01-01T00:30:00Z", "2024-01-01T01:30:00Z"),
+        ];
+        assert!(find_double_bookings(&events, Tz::UTC).is_empty());
+        assert_eq!(find_double_bookings(&events, Tz::Asia__Tokyo).len(), 1);
+    }
+
     #[test]
     fn test_new_with_invalid_timezone() {
         let start = Utc::now();
@@ -179,5 +1192,15 @@ mod tests {
             Some("Invalid/Timezone".to_string()),
         );
         assert!(event.is_err());
+
+        let event = Event::new(
+            "テストイベント".to_string(),
+            start,
+            end,
+            None,
+            None,
+            Some("America/Indiana/Indianapolis".to_string()),
+        );
+        assert!(event.is_ok());
     }
 }
@@ -0,0 +1,455 @@
+use crate::attachment::Attachment;
+use crate::attendee::Attendee;
+use crate::colors::EventColor;
+use crate::conference::ConferenceData;
+use crate::error::{GCalError, Result};
+use crate::event::{Event, EventDateTime};
+use crate::reminders::{Reminder, Reminders};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone};
+use chrono_tz::Tz;
+
+/// イベントを組み立てるビルダー
+///
+/// 開始・終了は `chrono_tz` のタイムゾーン付き日時、終日の日付、または開始と長さで指定します。
+/// タイムゾーンのない日時（`start_local` / `end_local`）は、既定のタイムゾーン
+/// （`with_default_time_zone` で指定、未指定の場合はUTC）で解釈します。
+/// `CalendarClient::event_builder` で作成すると、クライアントの既定のタイムゾーンが設定されます。
+/// `build()` で検証を行い、不正な場合は `GCalError::ValidationError` を返します。
+#[derive(Debug, Clone)]
+pub struct EventBuilder {
+    event: Event,
+    start: Option<Input>,
+    end: Option<Input>,
+    duration: Option<Duration>,
+    default_time_zone: Tz,
+}
+
+/// 指定された開始・終了。`Local` は `build()` で既定のタイムゾーンの日時に解決し、
+/// `AllDayThrough` は翌日（終日イベントの排他的な終了日）に解決します
+#[derive(Debug, Clone, Copy)]
+enum Input {
+    Local(NaiveDateTime),
+    AllDayThrough(NaiveDate),
+    Boundary(Boundary),
+}
+
+/// 開始・終了の指定方法
+#[derive(Debug, Clone, Copy)]
+enum Boundary {
+    Timed(DateTime<Tz>),
+    AllDay(NaiveDate),
+}
+
+impl EventBuilder {
+    pub fn new(summary: impl Into<String>) -> Self {
+        EventBuilder {
+            event: Event {
+                summary: Some(summary.into()),
+                ..Default::default()
+            },
+            start: None,
+            end: None,
+            duration: None,
+            default_time_zone: Tz::UTC,
+        }
+    }
+
+    /// タイムゾーンのない日時を解釈するタイムゾーンを指定します
+    pub fn with_default_time_zone(mut self, tz: Tz) -> Self {
+        self.default_time_zone = tz;
+        self
+    }
+
+    /// クライアント側で決めたイベントIDを指定します
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.event.id = Some(id.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.event.description = Some(description.into());
+        self
+    }
+
+    pub fn location(mut self, location: impl Into<String>) -> Self {
+        self.event.location = Some(location.into());
+        self
+    }
+
+    pub fn color(mut self, color: EventColor) -> Self {
+        self.event.set_color(color);
+        self
+    }
+
+    /// 開始日時。イベントのタイムゾーンには `start` のタイムゾーンが使われます
+    pub fn start(mut self, start: DateTime<Tz>) -> Self {
+        self.start = Some(Input::Boundary(Boundary::Timed(start)));
+        self
+    }
+
+    /// 既定のタイムゾーンの日時で開始日時を指定します
+    pub fn start_local(mut self, start: NaiveDateTime) -> Self {
+        self.start = Some(Input::Local(start));
+        self
+    }
+
+    pub fn end(mut self, end: DateTime<Tz>) -> Self {
+        self.end = Some(Input::Boundary(Boundary::Timed(end)));
+        self
+    }
+
+    /// 既定のタイムゾーンの日時で終了日時を指定します
+    pub fn end_local(mut self, end: NaiveDateTime) -> Self {
+        self.end = Some(Input::Local(end));
+        self
+    }
+
+    /// 指定日の終日イベントにします
+    pub fn all_day(mut self, date: NaiveDate) -> Self {
+        self.start = Some(Input::Boundary(Boundary::AllDay(date)));
+        self.end = None;
+        self
+    }
+
+    /// `start` から `end`（その日を含む）までの複数日にわたる終日イベントにします
+    pub fn all_day_range(mut self, start: NaiveDate, end_inclusive: NaiveDate) -> Self {
+        self.start = Some(Input::Boundary(Boundary::AllDay(start)));
+        self.end = Some(Input::AllDayThrough(end_inclusive));
+        self
+    }
+
+    /// 開始からの長さで終了日時を指定します。終日イベントでは日単位で指定してください
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn attendee(mut self, email: impl Into<String>) -> Self {
+        self.event.attendees.push(Attendee::new(email));
+        self
+    }
+
+    pub fn attendees(mut self, attendees: impl IntoIterator<Item = Attendee>) -> Self {
+        self.event.attendees.extend(attendees);
+        self
+    }
+
+    /// RRULE・EXDATE などの繰り返しルールを追加します（例: "RRULE:FREQ=WEEKLY;BYDAY=MO"）
+    pub fn recurrence(mut self, rule: impl Into<String>) -> Self {
+        self.event.recurrence.push(rule.into());
+        self
+    }
+
+    pub fn reminders(mut self, reminders: Reminders) -> Self {
+        self.event.reminders = Some(reminders);
+        self
+    }
+
+    /// イベント固有のリマインダーを追加します
+    pub fn reminder(mut self, reminder: Reminder) -> Self {
+        let reminders = self
+            .event
+            .reminders
+            .get_or_insert_with(|| Reminders::with_overrides(Vec::new()));
+        reminders.use_default = false;
+        reminders.overrides.push(reminder);
+        self
+    }
+
+    /// 添付ファイルを追加します
+    pub fn attachment(mut self, attachment: Attachment) -> Self {
+        self.event.add_attachment(attachment);
+        self
+    }
+
+    pub fn conference(mut self, conference: ConferenceData) -> Self {
+        self.event.conference_data = Some(conference);
+        self
+    }
+
+    /// Google Meet の会議を作成します
+    pub fn google_meet(self) -> Self {
+        self.conference(ConferenceData::google_meet())
+    }
+
+    pub fn private_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.event.set_private_property(key, value);
+        self
+    }
+
+    /// 開始・終了を確定させ、検証したうえでイベントを返します
+    pub fn build(self) -> Result<Event> {
+        let mut event = self.event;
+        let tz = self.default_time_zone;
+        let start = self
+            .start
+            .map(|start| resolve(start, tz, "start"))
+            .transpose()?
+            .ok_or_else(|| GCalError::field_validation("start", "開始日時が必要です"))?;
+        let end = self.end.map(|end| resolve(end, tz, "end")).transpose()?;
+        let end = match (end, self.duration) {
+            (Some(_), Some(_)) => {
+                return Err(GCalError::field_validation(
+                    "end",
+                    "終了日時と長さは同時に指定できません",
+                ))
+            }
+            (Some(end), None) => end,
+            (None, Some(duration)) => add_duration(start, duration)?,
+            // 終日イベントは1日、時刻指定のイベントは終了が必須
+            (None, None) => match start {
+                Boundary::AllDay(date) => add_duration(Boundary::AllDay(date), Duration::days(1))?,
+                Boundary::Timed(_) => {
+                    return Err(GCalError::field_validation(
+                        "end",
+                        "終了日時または長さが必要です",
+                    ))
+                }
+            },
+        };
+
+        match (start, end) {
+            (Boundary::Timed(s), Boundary::Timed(e)) if e <= s => {
+                return Err(GCalError::field_validation(
+                    "end",
+                    "終了日時は開始日時より後である必要があります",
+                ))
+            }
+            (Boundary::AllDay(s), Boundary::AllDay(e)) if e <= s => {
+                return Err(GCalError::field_validation(
+                    "end",
+                    "終了日は開始日より後である必要があります",
+                ))
+            }
+            (Boundary::Timed(_), Boundary::AllDay(_))
+            | (Boundary::AllDay(_), Boundary::Timed(_)) => {
+                return Err(GCalError::field_validation(
+                    "end",
+                    "開始と終了は両方とも時刻指定、または両方とも終日である必要があります",
+                ))
+            }
+            _ => {}
+        }
+
+        event.start = Some(to_event_date_time(start));
+        event.end = Some(to_event_date_time(end));
+        event.validate().map_err(GCalError::ValidationError)?;
+        Ok(event)
+    }
+}
+
+impl Event {
+    /// [`EventBuilder`] を作成します
+    pub fn builder(summary: impl Into<String>) -> EventBuilder {
+        EventBuilder::new(summary)
+    }
+}
+
+fn resolve(input: Input, tz: Tz, path: &str) -> Result<Boundary> {
+    match input {
+        Input::Boundary(boundary) => Ok(boundary),
+        Input::AllDayThrough(date) => date
+            .succ_opt()
+            .map(Boundary::AllDay)
+            .ok_or_else(|| GCalError::field_validation(path, "日付が範囲外です")),
+        Input::Local(local) => tz
+            .from_local_datetime(&local)
+            .earliest()
+            .map(Boundary::Timed)
+            .ok_or_else(|| {
+                GCalError::field_validation(
+                    path,
+                    format!("存在しない時刻です: {} {}", local, tz.name()),
+                )
+            }),
+    }
+}
+
+fn add_duration(start: Boundary, duration: Duration) -> Result<Boundary> {
+    if duration <= Duration::zero() {
+        return Err(GCalError::field_validation(
+            "duration",
+            "長さは正の値である必要があります",
+        ));
+    }
+    match start {
+        Boundary::Timed(dt) => Ok(Boundary::Timed(dt + duration)),
+        Boundary::AllDay(date) => {
+            if duration != Duration::days(duration.num_days()) {
+                return Err(GCalError::field_validation(
+                    "duration",
+                    "終日イベントの長さは日単位で指定する必要があります",
+                ));
+            }
+            date.checked_add_signed(duration)
+                .map(Boundary::AllDay)
+                .ok_or_else(|| GCalError::field_validation("end", "日付が範囲外です"))
+        }
+    }
+}
+
+fn to_event_date_time(boundary: Boundary) -> EventDateTime {
+    match boundary {
+        Boundary::Timed(dt) => EventDateTime {
+            date_time: dt.to_rfc3339_opts(SecondsFormat::Secs, false),
+            date: None,
+            time_zone: dt.timezone().name().to_string(),
+        },
+        Boundary::AllDay(date) => EventDateTime::from_date(date),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::{America::New_York, Asia::Tokyo};
+
+    #[test]
+    fn test_build_timed_event() {
+        let start = Tokyo.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap();
+        let event = Event::builder("定例会議")
+            .description("週次の定例")
+            .location("会議室A")
+            .start(start)
+            .duration(Duration::minutes(30))
+            .attendee("alice@example.com")
+            .recurrence("RRULE:FREQ=WEEKLY;BYDAY=MO")
+            .reminder(Reminder::popup(10))
+            .google_meet()
+            .build()
+            .unwrap();
+
+        let start = event.start.as_ref().unwrap();
+        assert_eq!(start.date_time, "2024-01-15T10:00:00+09:00");
+        assert_eq!(start.time_zone, "Asia/Tokyo");
+        assert_eq!(
+            event.end.as_ref().unwrap().date_time,
+            "2024-01-15T10:30:00+09:00"
+        );
+        assert_eq!(event.attendees.len(), 1);
+        assert_eq!(event.recurrence, vec!["RRULE:FREQ=WEEKLY;BYDAY=MO"]);
+        assert!(!event.reminders.as_ref().unwrap().use_default);
+        assert!(event.conference_data.is_some());
+    }
+
+    #[test]
+    fn test_build_all_day_event() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let event = Event::builder("休日").all_day(date).build().unwrap();
+        assert_eq!(
+            event.start.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-15")
+        );
+        assert_eq!(
+            event.end.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-16")
+        );
+
+        let event = Event::builder("出張")
+            .all_day_range(date, NaiveDate::from_ymd_opt(2024, 1, 17).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            event.end.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-18")
+        );
+
+        let event = Event::builder("合宿")
+            .all_day(date)
+            .duration(Duration::days(2))
+            .build()
+            .unwrap();
+        assert_eq!(
+            event.end.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-17")
+        );
+        assert!(Event::builder("合宿")
+            .all_day(date)
+            .duration(Duration::hours(5))
+            .build()
+            .is_err());
+
+        // 終了日の翌日が表せない場合は1日のイベントにせずエラーにする
+        let result = Event::builder("出張")
+            .all_day_range(date, NaiveDate::MAX)
+            .build();
+        assert!(matches!(
+            result,
+            Err(GCalError::ValidationError(errors)) if errors.contains("end")
+        ));
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let start = Tokyo.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap();
+        // 開始・終了がない
+        assert!(Event::builder("会議").build().is_err());
+        assert!(Event::builder("会議").start(start).build().is_err());
+        // 終了が開始より前
+        assert!(Event::builder("会議")
+            .start(start)
+            .end(start - Duration::hours(1))
+            .build()
+            .is_err());
+        // 終了と長さの両方
+        assert!(Event::builder("会議")
+            .start(start)
+            .end(start + Duration::hours(1))
+            .duration(Duration::hours(1))
+            .build()
+            .is_err());
+        // リマインダーの上限を超える
+        let mut builder = Event::builder("会議")
+            .start(start)
+            .duration(Duration::hours(1));
+        for minutes in 0..6 {
+            builder = builder.reminder(Reminder::popup(minutes));
+        }
+        assert!(matches!(
+            builder.build(),
+            Err(GCalError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_local_start_uses_default_time_zone() {
+        // 既定のタイムゾーンを指定しない場合はUTCで解釈される
+        let start_local = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let event = Event::builder("会議")
+            .start_local(start_local)
+            .end_local(start_local + Duration::minutes(45))
+            .build()
+            .unwrap();
+        let start = event.start.as_ref().unwrap();
+        assert_eq!(start.date_time, "2024-01-15T10:00:00+00:00");
+        assert_eq!(start.time_zone, "UTC");
+        assert_eq!(event.interval().unwrap().duration(), Duration::minutes(45));
+
+        let event = Event::builder("会議")
+            .with_default_time_zone(Tz::Asia__Tokyo)
+            .start_local(start_local)
+            .duration(Duration::minutes(30))
+            .build()
+            .unwrap();
+        let start = event.start.as_ref().unwrap();
+        assert_eq!(start.date_time, "2024-01-15T10:00:00+09:00");
+        assert_eq!(start.time_zone, "Asia/Tokyo");
+    }
+
+    #[test]
+    fn test_end_in_other_timezone() {
+        let start = Tokyo.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap();
+        let end = New_York.with_ymd_and_hms(2024, 1, 14, 21, 0, 0).unwrap();
+        let event = Event::builder("会議")
+            .start(start)
+            .end(end)
+            .build()
+            .unwrap();
+        assert_eq!(event.end.as_ref().unwrap().time_zone, "America/New_York");
+        assert_eq!(event.interval().unwrap().duration(), Duration::hours(1));
+    }
+}
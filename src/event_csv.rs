@@ -0,0 +1,774 @@
+use crate::attendee::Attendee;
+use crate::error::GCalError;
+use crate::event::Event;
+use crate::event_builder::EventBuilder;
+use crate::validation::ValidationErrors;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CsvError {
+    #[error("CSVの読み書きに失敗しました: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("必須の列がありません: {0}")]
+    MissingColumn(String),
+    #[error("イベントをCSVに変換できません: {0}")]
+    InvalidEvent(String),
+}
+
+/// CSVの列に対応するイベントの項目
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CsvField {
+    Id,
+    ICalUid,
+    Summary,
+    Description,
+    Location,
+    StartDate,
+    StartTime,
+    EndDate,
+    EndTime,
+    /// 終日イベントかどうか（TRUE / FALSE）
+    AllDay,
+    /// 行ごとのタイムゾーン（IANA名）。空欄の場合はインポーター・エクスポーターのタイムゾーンを使用します
+    TimeZone,
+    /// 参加者のメールアドレス（";" 区切り）
+    Attendees,
+    /// 繰り返しルール（改行区切り。"RRULE:" は省略可能）
+    Recurrence,
+    Status,
+    /// 指定したキーの非公開拡張プロパティ
+    PrivateProperty(String),
+}
+
+impl CsvField {
+    /// 既定の列名（Google カレンダーのCSV形式に合わせています）
+    pub fn default_header(&self) -> String {
+        match self {
+            CsvField::Id => "Event ID".to_string(),
+            CsvField::ICalUid => "iCalUID".to_string(),
+            CsvField::Summary => "Subject".to_string(),
+            CsvField::Description => "Description".to_string(),
+            CsvField::Location => "Location".to_string(),
+            CsvField::StartDate => "Start Date".to_string(),
+            CsvField::StartTime => "Start Time".to_string(),
+            CsvField::EndDate => "End Date".to_string(),
+            CsvField::EndTime => "End Time".to_string(),
+            CsvField::AllDay => "All Day Event".to_string(),
+            CsvField::TimeZone => "Time Zone".to_string(),
+            CsvField::Attendees => "Attendees".to_string(),
+            CsvField::Recurrence => "Recurrence".to_string(),
+            CsvField::Status => "Status".to_string(),
+            CsvField::PrivateProperty(key) => key.clone(),
+        }
+    }
+}
+
+/// CSVの列名と項目の対応
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvColumn {
+    pub header: String,
+    pub field: CsvField,
+}
+
+impl CsvColumn {
+    pub fn new(header: impl Into<String>, field: CsvField) -> Self {
+        CsvColumn {
+            header: header.into(),
+            field,
+        }
+    }
+}
+
+impl From<CsvField> for CsvColumn {
+    /// 既定の列名を使用します
+    fn from(field: CsvField) -> Self {
+        CsvColumn::new(field.default_header(), field)
+    }
+}
+
+/// 日付・時刻の表記
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvLocale {
+    /// 2024-01-15 / 13:00
+    #[default]
+    Iso,
+    /// 2024/01/15 / 13:00
+    Japanese,
+    /// 01/15/2024 / 01:00 PM
+    UnitedStates,
+    /// 15/01/2024 / 13:00
+    Europe,
+}
+
+impl CsvLocale {
+    pub fn date_format(&self) -> &'static str {
+        match self {
+            CsvLocale::Iso => "%Y-%m-%d",
+            CsvLocale::Japanese => "%Y/%m/%d",
+            CsvLocale::UnitedStates => "%m/%d/%Y",
+            CsvLocale::Europe => "%d/%m/%Y",
+        }
+    }
+
+    pub fn time_format(&self) -> &'static str {
+        match self {
+            CsvLocale::UnitedStates => "%I:%M %p",
+            _ => "%H:%M",
+        }
+    }
+
+    /// 日付を解析します。ロケールの表記に加えてISO形式も受け付けます
+    pub fn parse_date(&self, value: &str) -> Option<NaiveDate> {
+        [self.date_format(), "%Y-%m-%d"]
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(value.trim(), format).ok())
+    }
+
+    /// 時刻を解析します。秒の有無、12時間表記・24時間表記のどちらも受け付けます
+    pub fn parse_time(&self, value: &str) -> Option<NaiveTime> {
+        let value = value.trim();
+        [
+            self.time_format(),
+            "%H:%M",
+            "%H:%M:%S",
+            "%I:%M %p",
+            "%I:%M:%S %p",
+        ]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(value, format).ok())
+    }
+}
+
+/// 既定で出力・入力する列
+pub fn default_columns() -> Vec<CsvColumn> {
+    [
+        CsvField::Summary,
+        CsvField::StartDate,
+        CsvField::StartTime,
+        CsvField::EndDate,
+        CsvField::EndTime,
+        CsvField::AllDay,
+        CsvField::Description,
+        CsvField::Location,
+    ]
+    .into_iter()
+    .map(CsvColumn::from)
+    .collect()
+}
+
+/// イベントをCSVに変換するエクスポーター
+///
+/// 時刻指定のイベントは指定したタイムゾーンの日時で出力します。
+/// 終日イベントの終了日は、表計算ソフトで扱いやすいようにその日を含む最終日を出力します。
+#[derive(Debug, Clone)]
+pub struct CsvExporter {
+    columns: Vec<CsvColumn>,
+    time_zone: Tz,
+    locale: CsvLocale,
+}
+
+impl CsvExporter {
+    pub fn new(time_zone: Tz) -> Self {
+        CsvExporter {
+            columns: default_columns(),
+            time_zone,
+            locale: CsvLocale::default(),
+        }
+    }
+
+    pub fn with_columns(mut self, columns: impl IntoIterator<Item = impl Into<CsvColumn>>) -> Self {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_locale(mut self, locale: CsvLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// ヘッダー行付きのCSVテキストに変換します
+    pub fn export(&self, events: &[Event]) -> Result<String, CsvError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(self.columns.iter().map(|c| c.header.as_str()))?;
+        for event in events {
+            let row = self.row(event)?;
+            writer.write_record(&row)?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| CsvError::InvalidEvent(e.to_string()))?;
+        String::from_utf8(bytes).map_err(|e| CsvError::InvalidEvent(e.to_string()))
+    }
+
+    fn row(&self, event: &Event) -> Result<Vec<String>, CsvError> {
+        let times = self.times(event)?;
+        Ok(self
+            .columns
+            .iter()
+            .map(|column| self.cell(event, &times, &column.field))
+            .collect())
+    }
+
+    fn cell(&self, event: &Event, times: &RenderedTimes, field: &CsvField) -> String {
+        let date = |d: Option<NaiveDate>| {
+            d.map(|d| d.format(self.locale.date_format()).to_string())
+                .unwrap_or_default()
+        };
+        let time = |t: Option<NaiveTime>| {
+            t.map(|t| t.format(self.locale.time_format()).to_string())
+                .unwrap_or_default()
+        };
+        match field {
+            CsvField::Id => event.id.clone().unwrap_or_default(),
+            CsvField::ICalUid => event.i_cal_uid.clone().unwrap_or_default(),
+            CsvField::Summary => event.summary.clone().unwrap_or_default(),
+            CsvField::Description => event.description.clone().unwrap_or_default(),
+            CsvField::Location => event.location.clone().unwrap_or_default(),
+            CsvField::StartDate => date(times.start_date),
+            CsvField::StartTime => time(times.start_time),
+            CsvField::EndDate => date(times.end_date),
+            CsvField::EndTime => time(times.end_time),
+            CsvField::AllDay => if times.all_day { "TRUE" } else { "FALSE" }.to_string(),
+            CsvField::TimeZone => {
+                if times.all_day {
+                    String::new()
+                } else {
+                    self.time_zone.name().to_string()
+                }
+            }
+            CsvField::Attendees => event
+                .attendees
+                .iter()
+                .map(|a| a.email.as_str())
+                .collect::<Vec<_>>()
+                .join(";"),
+            CsvField::Recurrence => event.recurrence.join("\n"),
+            CsvField::Status => event.status.clone().unwrap_or_default(),
+            CsvField::PrivateProperty(key) => event
+                .private_property(key)
+                .map(str::to_string)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn times(&self, event: &Event) -> Result<RenderedTimes, CsvError> {
+        let (Some(start), Some(end)) = (&event.start, &event.end) else {
+            return Ok(RenderedTimes::default());
+        };
+        let invalid = |e: String| {
+            CsvError::InvalidEvent(format!(
+                "{}: {}",
+                event.summary.as_deref().unwrap_or_default(),
+                e
+            ))
+        };
+        if start.is_all_day() {
+            let parse = |d: &Option<String>| {
+                NaiveDate::parse_from_str(d.as_deref().unwrap_or_default(), "%Y-%m-%d")
+                    .map_err(|e| invalid(e.to_string()))
+            };
+            let end_exclusive = parse(&end.date)?;
+            return Ok(RenderedTimes {
+                all_day: true,
+                start_date: Some(parse(&start.date)?),
+                end_date: end_exclusive.pred_opt(),
+                ..Default::default()
+            });
+        }
+        let interval = event.interval().map_err(|e| invalid(e.to_string()))?;
+        let start = interval.start.with_timezone(&self.time_zone);
+        let end = interval.end.with_timezone(&self.time_zone);
+        Ok(RenderedTimes {
+            all_day: false,
+            start_date: Some(start.date_naive()),
+            start_time: Some(start.time()),
+            end_date: Some(end.date_naive()),
+            end_time: Some(end.time()),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct RenderedTimes {
+    all_day: bool,
+    start_date: Option<NaiveDate>,
+    start_time: Option<NaiveTime>,
+    end_date: Option<NaiveDate>,
+    end_time: Option<NaiveTime>,
+}
+
+/// 1行分の変換エラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    /// 表計算ソフトでの行番号（ヘッダー行が1行目）
+    pub row: usize,
+    /// 値を解釈できない列は列名を、イベントの検証エラーはイベントのフィールドのパスを持ちます
+    pub errors: ValidationErrors,
+}
+
+/// CSVの変換結果
+#[derive(Debug, Clone, Default)]
+pub struct CsvImport {
+    pub events: Vec<Event>,
+    pub errors: Vec<CsvRowError>,
+    /// `events` の各イベントの行番号
+    pub(crate) rows: Vec<usize>,
+}
+
+/// `CalendarClient::import_csv` の結果
+#[derive(Debug, Clone, Default)]
+pub struct CsvImportReport {
+    /// 作成されたイベント（APIの応答）
+    pub created: Vec<Event>,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// 列の対応表に従ってCSVの行をイベントに変換するインポーター
+///
+/// 列名は大文字・小文字を区別せずに照合します。終了日を省略して終了時刻が開始時刻以前の場合は、
+/// 翌日に終わる（夜勤などの）イベントとして扱います。
+#[derive(Debug, Clone)]
+pub struct CsvImporter {
+    columns: Vec<CsvColumn>,
+    time_zone: Tz,
+    locale: CsvLocale,
+    default_duration: Option<Duration>,
+}
+
+impl CsvImporter {
+    /// 既定の列名（[`CsvField::default_header`]）ですべての項目を対応付けます
+    pub fn new(time_zone: Tz) -> Self {
+        let columns = [
+            CsvField::Id,
+            CsvField::ICalUid,
+            CsvField::Summary,
+            CsvField::Description,
+            CsvField::Location,
+            CsvField::StartDate,
+            CsvField::StartTime,
+            CsvField::EndDate,
+            CsvField::EndTime,
+            CsvField::AllDay,
+            CsvField::TimeZone,
+            CsvField::Attendees,
+            CsvField::Recurrence,
+            CsvField::Status,
+        ]
+        .into_iter()
+        .map(CsvColumn::from)
+        .collect();
+        CsvImporter {
+            columns,
+            time_zone,
+            locale: CsvLocale::default(),
+            default_duration: None,
+        }
+    }
+
+    /// 列名 `header` を `field` に対応付けます。同じ項目の既存の対応は置き換えます
+    pub fn with_column(mut self, header: impl Into<String>, field: CsvField) -> Self {
+        self.columns.retain(|c| c.field != field);
+        self.columns.push(CsvColumn::new(header, field));
+        self
+    }
+
+    pub fn with_locale(mut self, locale: CsvLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 終了日時の列が空欄の時刻指定イベントに使う長さ。指定しない場合、終了日時は必須です
+    pub fn with_default_duration(mut self, duration: Duration) -> Self {
+        self.default_duration = Some(duration);
+        self
+    }
+
+    /// CSVテキストを変換します
+    ///
+    /// ヘッダー行を読めない場合や、件名・開始日の列がない場合のみエラーを返し、
+    /// 各行のエラーは結果に含めます。
+    pub fn convert(&self, text: &str) -> Result<CsvImport, CsvError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(text.as_bytes());
+        let headers = reader.headers()?.clone();
+
+        let mut index: HashMap<&CsvField, (usize, &str)> = HashMap::new();
+        for column in &self.columns {
+            if let Some(i) = headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(column.header.trim()))
+            {
+                index.insert(&column.field, (i, column.header.as_str()));
+            }
+        }
+        for required in [CsvField::Summary, CsvField::StartDate] {
+            if !index.contains_key(&required) {
+                let header = self
+                    .columns
+                    .iter()
+                    .find(|c| c.field == required)
+                    .map(|c| c.header.clone())
+                    .unwrap_or_else(|| required.default_header());
+                return Err(CsvError::MissingColumn(header));
+            }
+        }
+
+        let mut import = CsvImport::default();
+        for (i, record) in reader.records().enumerate() {
+            let row_number = i + 2;
+            let record = record?;
+            if record.iter().all(|cell| cell.trim().is_empty()) {
+                continue;
+            }
+            let row = Row {
+                record: &record,
+                index: &index,
+            };
+            match self.row_to_event(&row) {
+                Ok(event) => {
+                    import.events.push(event);
+                    import.rows.push(row_number);
+                }
+                Err(errors) => import.errors.push(CsvRowError {
+                    row: row_number,
+                    errors,
+                }),
+            }
+        }
+        Ok(import)
+    }
+
+    fn row_to_event(&self, row: &Row) -> Result<Event, ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let summary = row.get(&CsvField::Summary);
+        if summary.is_none() {
+            errors.add(
+                row.header(&CsvField::Summary).unwrap_or_default(),
+                "件名が必要です",
+            );
+        }
+        let mut builder = EventBuilder::new(summary.unwrap_or_default());
+        if let Some(description) = row.get(&CsvField::Description) {
+            builder = builder.description(description);
+        }
+        if let Some(location) = row.get(&CsvField::Location) {
+            builder = builder.location(location);
+        }
+        if let Some(id) = row.get(&CsvField::Id) {
+            builder = builder.id(id);
+        }
+        if let Some(attendees) = row.get(&CsvField::Attendees) {
+            builder = builder.attendees(
+                attendees
+                    .split([';', ',', '\n'])
+                    .map(str::trim)
+                    .filter(|email| !email.is_empty())
+                    .map(Attendee::new),
+            );
+        }
+        if let Some(recurrence) = row.get(&CsvField::Recurrence) {
+            for line in recurrence.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                builder = builder.recurrence(if line.contains(':') {
+                    line.to_string()
+                } else {
+                    format!("RRULE:{}", line)
+                });
+            }
+        }
+        for column in &self.columns {
+            if let CsvField::PrivateProperty(key) = &column.field {
+                if let Some(value) = row.get(&column.field) {
+                    builder = builder.private_property(key.clone(), value);
+                }
+            }
+        }
+
+        match self.times(row, builder) {
+            Ok(b) => builder = b,
+            Err((header, message)) => {
+                errors.add(header, message);
+                return Err(errors);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let mut event = match builder.build() {
+            Ok(event) => event,
+            Err(GCalError::ValidationError(e)) => return Err(e),
+            Err(e) => return Err(ValidationErrors::from(e.to_string())),
+        };
+        event.i_cal_uid = row.get(&CsvField::ICalUid).map(str::to_string);
+        event.status = row.get(&CsvField::Status).map(|s| s.to_ascii_lowercase());
+        Ok(event)
+    }
+
+    /// 開始・終了を設定します。エラーは (列名, メッセージ) で返します
+    fn times(&self, row: &Row, builder: EventBuilder) -> Result<EventBuilder, (String, String)> {
+        let header = |field: &CsvField| row.header(field).unwrap_or_default().to_string();
+        let date = |field: CsvField| -> Result<Option<NaiveDate>, (String, String)> {
+            row.get(&field)
+                .map(|value| {
+                    self.locale
+                        .parse_date(value)
+                        .ok_or_else(|| (header(&field), format!("日付を解釈できません: {}", value)))
+                })
+                .transpose()
+        };
+        let time = |field: CsvField| -> Result<Option<NaiveTime>, (String, String)> {
+            row.get(&field)
+                .map(|value| {
+                    self.locale
+                        .parse_time(value)
+                        .ok_or_else(|| (header(&field), format!("時刻を解釈できません: {}", value)))
+                })
+                .transpose()
+        };
+
+        let start_date = date(CsvField::StartDate)?
+            .ok_or_else(|| (header(&CsvField::StartDate), "開始日が必要です".to_string()))?;
+        let end_date = date(CsvField::EndDate)?;
+        let start_time = time(CsvField::StartTime)?;
+        let end_time = time(CsvField::EndTime)?;
+        let all_day = match row.get(&CsvField::AllDay) {
+            Some(value) => parse_bool(value).ok_or_else(|| {
+                (
+                    header(&CsvField::AllDay),
+                    format!("TRUE または FALSE を指定してください: {}", value),
+                )
+            })?,
+            None => start_time.is_none(),
+        };
+
+        if all_day {
+            let end_date = end_date.unwrap_or(start_date);
+            return Ok(builder.all_day_range(start_date, end_date));
+        }
+
+        let tz = match row.get(&CsvField::TimeZone) {
+            Some(name) => name.parse::<Tz>().map_err(|_| {
+                (
+                    header(&CsvField::TimeZone),
+                    format!("不明なタイムゾーンです: {}", name),
+                )
+            })?,
+            None => self.time_zone,
+        };
+        let start_time = start_time.ok_or_else(|| {
+            (
+                header(&CsvField::StartTime),
+                "開始時刻が必要です".to_string(),
+            )
+        })?;
+        let local = |date: NaiveDate, time: NaiveTime, field: &CsvField| {
+            localize(tz, date.and_time(time)).ok_or_else(|| {
+                (
+                    header(field),
+                    format!("{} に存在しない時刻です: {} {}", tz.name(), date, time),
+                )
+            })
+        };
+        let start = local(start_date, start_time, &CsvField::StartTime)?;
+        let builder = builder.start(start);
+
+        match (end_date, end_time) {
+            (_, Some(end_time)) => {
+                // 終了日がなく終了時刻が開始時刻以前なら翌日に終わる
+                let end_date = end_date.unwrap_or(if end_time <= start_time {
+                    start_date + Duration::days(1)
+                } else {
+                    start_date
+                });
+                Ok(builder.end(local(end_date, end_time, &CsvField::EndTime)?))
+            }
+            (_, None) => match self.default_duration {
+                Some(duration) => Ok(builder.duration(duration)),
+                None => Err((header(&CsvField::EndTime), "終了時刻が必要です".to_string())),
+            },
+        }
+    }
+}
+
+/// 対応表を適用した1行
+struct Row<'a> {
+    record: &'a csv::StringRecord,
+    index: &'a HashMap<&'a CsvField, (usize, &'a str)>,
+}
+
+impl Row<'_> {
+    /// 空欄でない値
+    fn get(&self, field: &CsvField) -> Option<&str> {
+        let (i, _) = self.index.get(field)?;
+        self.record
+            .get(*i)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn header(&self, field: &CsvField) -> Option<&str> {
+        self.index.get(field).map(|(_, header)| *header)
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "1" | "y" | "はい" | "○" => Some(true),
+        "false" | "no" | "0" | "n" | "いいえ" | "×" => Some(false),
+        _ => None,
+    }
+}
+
+/// ローカル日時をタイムゾーン付きにします。夏時間の重複では早い方を使い、存在しない時刻は None
+fn localize(tz: Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    tz.from_local_datetime(&local).earliest()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Asia::Tokyo;
+
+    const ROSTER: &str = "\
+氏名,日付,開始,終了,場所,メール,備考
+早番,2024/01/15,09:00,17:00,本社,alice@example.com;bob@example.com,
+夜勤,2024/01/15,22:00,06:00,倉庫,carol@example.com,
+,2024/01/16,09:00,17:00,本社,,件名なし
+休暇,2024/01/17,,,,,
+遅番,2024/13/01,13:00,21:00,本社,,
+早番,2024/01/18,09:00,,本社,not-an-email,
+";
+
+    fn roster_importer() -> CsvImporter {
+        CsvImporter::new(Tokyo)
+            .with_locale(CsvLocale::Japanese)
+            .with_column("氏名", CsvField::Summary)
+            .with_column("日付", CsvField::StartDate)
+            .with_column("開始", CsvField::StartTime)
+            .with_column("終了", CsvField::EndTime)
+            .with_column("場所", CsvField::Location)
+            .with_column("メール", CsvField::Attendees)
+            .with_column("備考", CsvField::PrivateProperty("note".to_string()))
+    }
+
+    #[test]
+    fn test_import_roster() {
+        let import = roster_importer().convert(ROSTER).unwrap();
+        assert_eq!(import.events.len(), 3);
+        assert_eq!(import.rows, vec![2, 3, 5]);
+
+        let early = &import.events[0];
+        assert_eq!(early.summary.as_deref(), Some("早番"));
+        assert_eq!(
+            early.start.as_ref().unwrap().date_time,
+            "2024-01-15T09:00:00+09:00"
+        );
+        assert_eq!(early.start.as_ref().unwrap().time_zone, "Asia/Tokyo");
+        assert_eq!(early.attendees.len(), 2);
+
+        // 終了時刻が開始時刻以前なら翌日に終わる
+        let night = &import.events[1];
+        assert_eq!(
+            night.end.as_ref().unwrap().date_time,
+            "2024-01-16T06:00:00+09:00"
+        );
+
+        // 時刻がなければ終日
+        let holiday = &import.events[2];
+        assert_eq!(
+            holiday.start.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-17")
+        );
+        assert_eq!(
+            holiday.end.as_ref().unwrap().date.as_deref(),
+            Some("2024-01-18")
+        );
+    }
+
+    #[test]
+    fn test_import_row_errors() {
+        let import = roster_importer().convert(ROSTER).unwrap();
+        let rows: Vec<_> = import.errors.iter().map(|e| e.row).collect();
+        assert_eq!(rows, vec![4, 6, 7]);
+        assert!(import.errors[0].errors.contains("氏名"));
+        assert!(import.errors[1].errors.contains("日付"));
+        assert!(import.errors[2].errors.contains("終了"));
+
+        // 既定の長さを指定すると終了時刻を省略できる（メールアドレスの検証エラーは残る）
+        let import = roster_importer()
+            .with_default_duration(Duration::hours(8))
+            .convert(ROSTER)
+            .unwrap();
+        let last = import.errors.last().unwrap();
+        assert_eq!(last.row, 7);
+        assert!(last.errors.contains("attendees[0].email"));
+
+        assert!(matches!(
+            CsvImporter::new(Tokyo).convert(ROSTER),
+            Err(CsvError::MissingColumn(header)) if header == "Subject"
+        ));
+    }
+
+    #[test]
+    fn test_export_columns_and_locale() {
+        let import = roster_importer().convert(ROSTER).unwrap();
+        let csv = CsvExporter::new(chrono_tz::America::New_York)
+            .with_locale(CsvLocale::UnitedStates)
+            .with_columns([
+                CsvField::Summary,
+                CsvField::StartDate,
+                CsvField::StartTime,
+                CsvField::EndDate,
+                CsvField::AllDay,
+                CsvField::TimeZone,
+                CsvField::Attendees,
+            ])
+            .export(&import.events)
+            .unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Subject,Start Date,Start Time,End Date,All Day Event,Time Zone,Attendees"
+        );
+        assert_eq!(
+            lines[1],
+            "早番,01/14/2024,07:00 PM,01/15/2024,FALSE,America/New_York,alice@example.com;bob@example.com"
+        );
+        // 終日イベントの終了日はその日を含む最終日
+        assert_eq!(lines[3], "休暇,01/17/2024,,01/17/2024,TRUE,,");
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let import = roster_importer().convert(ROSTER).unwrap();
+        let columns = [
+            CsvField::Summary,
+            CsvField::StartDate,
+            CsvField::StartTime,
+            CsvField::EndDate,
+            CsvField::EndTime,
+            CsvField::AllDay,
+            CsvField::Location,
+            CsvField::Attendees,
+        ];
+        let csv = CsvExporter::new(Tokyo)
+            .with_columns(columns)
+            .with_locale(CsvLocale::Europe)
+            .export(&import.events)
+            .unwrap();
+        let round_trip = CsvImporter::new(Tokyo)
+            .with_locale(CsvLocale::Europe)
+            .convert(&csv)
+            .unwrap();
+        assert!(round_trip.errors.is_empty(), "{:?}", round_trip.errors);
+        for (before, after) in import.events.iter().zip(&round_trip.events) {
+            assert_eq!(before.summary, after.summary);
+            assert_eq!(before.interval().unwrap(), after.interval().unwrap());
+            assert_eq!(before.location, after.location);
+            assert_eq!(before.attendees, after.attendees);
+        }
+    }
+}
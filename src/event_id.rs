@@ -0,0 +1,69 @@
+/// base32hex で使用できる文字（Google Calendar のイベントIDは小文字のみ）
+const BASE32HEX: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+
+pub const MIN_EVENT_ID_LEN: usize = 5;
+pub const MAX_EVENT_ID_LEN: usize = 1024;
+
+/// クライアント側でイベントIDを生成します
+///
+/// 128ビットの乱数を base32hex で表した26文字の文字列を返します。
+/// 作成前にIDを決めておくことで、タイムアウト後の再送でもイベントが重複しません。
+pub fn generate_event_id() -> String {
+    let mut value = rand::random::<u128>();
+    let mut id = vec![0u8; 26];
+    for c in id.iter_mut().rev() {
+        *c = BASE32HEX[(value & 0x1f) as usize];
+        value >>= 5;
+    }
+    String::from_utf8(id).expect("base32hex is ascii")
+}
+
+/// イベントIDがGoogle Calendarの形式を満たしているかを検証します
+///
+/// 使用できる文字は base32hex（a-v と 0-9）で、長さは5〜1024文字です。
+pub fn validate_event_id(id: &str) -> Result<(), String> {
+    if id.len() < MIN_EVENT_ID_LEN || id.len() > MAX_EVENT_ID_LEN {
+        return Err(format!(
+            "イベントIDの長さは{}〜{}文字である必要があります: {}",
+            MIN_EVENT_ID_LEN,
+            MAX_EVENT_ID_LEN,
+            id.len()
+        ));
+    }
+    if let Some(c) = id
+        .chars()
+        .find(|c| !c.is_ascii() || !BASE32HEX.contains(&(*c as u8)))
+    {
+        return Err(format!(
+            "イベントIDに使用できない文字が含まれています: {:?}",
+            c
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_event_id() {
+        let id = generate_event_id();
+        assert_eq!(id.len(), 26);
+        assert!(validate_event_id(&id).is_ok());
+        assert_ne!(id, generate_event_id());
+    }
+
+    #[test]
+    fn test_validate_event_id() {
+        assert!(validate_event_id("abcde").is_ok());
+        assert!(validate_event_id("0123456789abcdefghijklmnopqrstuv").is_ok());
+        assert!(validate_event_id("abcd").is_err());
+        assert!(validate_event_id(&"a".repeat(1025)).is_err());
+        // w-z、大文字、記号は使用できない
+        assert!(validate_event_id("abcdw").is_err());
+        assert!(validate_event_id("ABCDE").is_err());
+        assert!(validate_event_id("abc-de").is_err());
+        assert!(validate_event_id("abcdé").is_err());
+    }
+}
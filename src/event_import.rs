@@ -0,0 +1,76 @@
+/// events.import のクエリパラメータ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportEventOptions {
+    /// 会議データの扱い。0 は会議データを無視し、1 は `conferenceData` を読み書きします
+    pub conference_data_version: Option<u8>,
+    /// 添付ファイル（attachments）を扱う場合は true
+    pub supports_attachments: Option<bool>,
+}
+
+/// 指定できる会議データのバージョンの最大値
+pub const MAX_CONFERENCE_DATA_VERSION: u8 = 1;
+
+impl ImportEventOptions {
+    pub fn new() -> Self {
+        ImportEventOptions::default()
+    }
+
+    pub fn with_conference_data_version(mut self, version: u8) -> Self {
+        self.conference_data_version = Some(version);
+        self
+    }
+
+    pub fn with_supports_attachments(mut self, supports_attachments: bool) -> Self {
+        self.supports_attachments = Some(supports_attachments);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self.conference_data_version {
+            Some(version) if version > MAX_CONFERENCE_DATA_VERSION => Err(format!(
+                "conferenceDataVersion は0から{}で指定してください: {}",
+                MAX_CONFERENCE_DATA_VERSION, version
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// クエリ文字列のキーと値の組を返します
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(v) = self.conference_data_version {
+            pairs.push(("conferenceDataVersion", v.to_string()));
+        }
+        if let Some(v) = self.supports_attachments {
+            pairs.push(("supportsAttachments", v.to_string()));
+        }
+        pairs
+    }
+
+    /// URLエンコード済みのクエリ文字列を返します
+    pub fn to_query_string(&self) -> String {
+        serde_urlencoded::to_string(self.query_pairs()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_string() {
+        assert_eq!(ImportEventOptions::new().to_query_string(), "");
+        let options = ImportEventOptions::new()
+            .with_conference_data_version(1)
+            .with_supports_attachments(true);
+        assert_eq!(
+            options.to_query_string(),
+            "conferenceDataVersion=1&supportsAttachments=true"
+        );
+        assert!(options.validate().is_ok());
+        assert!(ImportEventOptions::new()
+            .with_conference_data_version(2)
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::event::Event;
+use crate::event_type::EventType;
+use crate::extended_properties::{ExtendedProperties, PropertyScope};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+/// events.list のクエリパラメータ
+///
+/// `sync_token` を指定した場合、Googleの仕様により期間や検索条件は併用できません。
+#[derive(Debug, Clone, Default)]
+pub struct ListEventsParams {
+    pub sync_token: Option<String>,
+    pub page_token: Option<String>,
+    pub time_min: Option<DateTime<Utc>>,
+    pub time_max: Option<DateTime<Utc>>,
+    pub updated_min: Option<DateTime<Utc>>,
+    pub q: Option<String>,
+    pub single_events: Option<bool>,
+    pub show_deleted: Option<bool>,
+    pub order_by: Option<String>,
+    pub max_results: Option<u32>,
+    /// "key=value" 形式。複数指定した場合はすべてに一致するイベントを返します
+    pub private_extended_property: Vec<String>,
+    /// "key=value" 形式。shared拡張プロパティに対する絞り込み
+    pub shared_extended_property: Vec<String>,
+    /// 指定した種類のイベントのみを返します。空の場合はすべての種類
+    pub event_types: Vec<EventType>,
+}
+
+impl ListEventsParams {
+    pub fn new() -> Self {
+        ListEventsParams::default()
+    }
+
+    pub fn with_sync_token(mut self, token: impl Into<String>) -> Self {
+        self.sync_token = Some(token.into());
+        self
+    }
+
+    pub fn with_page_token(mut self, token: impl Into<String>) -> Self {
+        self.page_token = Some(token.into());
+        self
+    }
+
+    pub fn with_time_range(mut self, time_min: DateTime<Utc>, time_max: DateTime<Utc>) -> Self {
+        self.time_min = Some(time_min);
+        self.time_max = Some(time_max);
+        self
+    }
+
+    pub fn with_query(mut self, q: impl Into<String>) -> Self {
+        self.q = Some(q.into());
+        self
+    }
+
+    pub fn with_single_events(mut self, single_events: bool) -> Self {
+        self.single_events = Some(single_events);
+        self
+    }
+
+    pub fn with_show_deleted(mut self, show_deleted: bool) -> Self {
+        self.show_deleted = Some(show_deleted);
+        self
+    }
+
+    pub fn with_max_results(mut self, max_results: u32) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// イベントの種類で絞り込みます。複数回呼ぶといずれかに一致するイベントを返します
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        self.event_types.push(event_type);
+        self
+    }
+
+    pub fn with_event_types(mut self, event_types: impl IntoIterator<Item = EventType>) -> Self {
+        self.event_types.extend(event_types);
+        self
+    }
+
+    /// private拡張プロパティで絞り込みます
+    pub fn with_private_extended_property(
+        mut self,
+        key: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Self {
+        self.private_extended_property
+            .push(format!("{}={}", key.as_ref(), value.as_ref()));
+        self
+    }
+
+    /// shared拡張プロパティで絞り込みます
+    pub fn with_shared_extended_property(
+        mut self,
+        key: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Self {
+        self.shared_extended_property
+            .push(format!("{}={}", key.as_ref(), value.as_ref()));
+        self
+    }
+
+    /// 構造体の各フィールドに一致する拡張プロパティで絞り込みます
+    ///
+    /// [`ExtendedProperties::store`] で保存した構造体と同じ型を渡すことで、
+    /// 保存時と同じキーと値の組で検索できます。
+    pub fn with_extended_properties<T: Serialize>(
+        mut self,
+        scope: PropertyScope,
+        value: &T,
+    ) -> Result<Self, String> {
+        let mut properties = ExtendedProperties::default();
+        properties.store(scope, value)?;
+        let mut pairs: Vec<(String, String)> = properties.map(scope).clone().into_iter().collect();
+        pairs.sort();
+        for (key, value) in pairs {
+            self = match scope {
+                PropertyScope::Private => self.with_private_extended_property(key, value),
+                PropertyScope::Shared => self.with_shared_extended_property(key, value),
+            };
+        }
+        Ok(self)
+    }
+
+    /// クエリ文字列のキーと値の組を返します
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let rfc3339 = |dt: &DateTime<Utc>| dt.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut pairs = Vec::new();
+        if let Some(v) = &self.sync_token {
+            pairs.push(("syncToken", v.clone()));
+        }
+        if let Some(v) = &self.page_token {
+            pairs.push(("pageToken", v.clone()));
+        }
+        if let Some(v) = &self.time_min {
+            pairs.push(("timeMin", rfc3339(v)));
+        }
+        if let Some(v) = &self.time_max {
+            pairs.push(("timeMax", rfc3339(v)));
+        }
+        if let Some(v) = &self.updated_min {
+            pairs.push(("updatedMin", rfc3339(v)));
+        }
+        if let Some(v) = &self.q {
+            pairs.push(("q", v.clone()));
+        }
+        if let Some(v) = self.single_events {
+            pairs.push(("singleEvents", v.to_string()));
+        }
+        if let Some(v) = self.show_deleted {
+            pairs.push(("showDeleted", v.to_string()));
+        }
+        if let Some(v) = &self.order_by {
+            pairs.push(("orderBy", v.clone()));
+        }
+        if let Some(v) = self.max_results {
+            pairs.push(("maxResults", v.to_string()));
+        }
+        for v in &self.private_extended_property {
+            pairs.push(("privateExtendedProperty", v.clone()));
+        }
+        for v in &self.shared_extended_property {
+            pairs.push(("sharedExtendedProperty", v.clone()));
+        }
+        for v in &self.event_types {
+            pairs.push(("eventTypes", v.as_str().to_string()));
+        }
+        pairs
+    }
+
+    /// URLエンコード済みのクエリ文字列を返します
+    pub fn to_query_string(&self) -> String {
+        serde_urlencoded::to_string(self.query_pairs()).unwrap_or_default()
+    }
+}
+
+/// events.list のレスポンス
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventList {
+    #[serde(default)]
+    pub items: Vec<Event>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    /// 最終ページにのみ含まれる、次回の差分同期用トークン
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_sync_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_query_string() {
+        let params = ListEventsParams::new()
+            .with_time_range(
+                Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            )
+            .with_query("定例 会議")
+            .with_single_events(true);
+        assert_eq!(
+            params.to_query_string(),
+            "timeMin=2024-01-01T00%3A00%3A00Z&timeMax=2024-01-02T00%3A00%3A00Z\
+             &q=%E5%AE%9A%E4%BE%8B+%E4%BC%9A%E8%AD%B0&singleEvents=true"
+        );
+        assert_eq!(ListEventsParams::new().to_query_string(), "");
+
+        let params = ListEventsParams::new()
+            .with_private_extended_property("source", "jira")
+            .with_private_extended_property("ticket", "PROJ-1");
+        assert_eq!(
+            params.to_query_string(),
+            "privateExtendedProperty=source%3Djira&privateExtendedProperty=ticket%3DPROJ-1"
+        );
+
+        let params = ListEventsParams::new()
+            .with_event_types([EventType::OutOfOffice, EventType::WorkingLocation]);
+        assert_eq!(
+            params.to_query_string(),
+            "eventTypes=outOfOffice&eventTypes=workingLocation"
+        );
+
+        #[derive(Serialize)]
+        struct Meta {
+            source: &'static str,
+            ticket: u32,
+        }
+        let params = ListEventsParams::new()
+            .with_extended_properties(
+                PropertyScope::Shared,
+                &Meta {
+                    source: "hr",
+                    ticket: 42,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            params.to_query_string(),
+            "sharedExtendedProperty=source%3Dhr&sharedExtendedProperty=ticket%3D42"
+        );
+    }
+
+    #[test]
+    fn test_event_list_deserialization() {
+        let json = r#"{
+            "kind": "calendar#events",
+            "items": [{"id": "a", "status": "cancelled"}],
+            "nextSyncToken": "token-1"
+        }"#;
+        let list: EventList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].status.as_deref(), Some("cancelled"));
+        assert_eq!(list.next_sync_token.as_deref(), Some("token-1"));
+        assert!(list.next_page_token.is_none());
+    }
+}
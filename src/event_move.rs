@@ -0,0 +1,188 @@
+use crate::event::Event;
+use crate::event_type::EventType;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+
+/// 参加者への通知の送信先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SendUpdates {
+    /// すべての参加者に通知します
+    All,
+    /// Google カレンダー以外の参加者にのみ通知します
+    ExternalOnly,
+    /// 通知しません
+    #[default]
+    None,
+}
+
+impl SendUpdates {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SendUpdates::All => "all",
+            SendUpdates::ExternalOnly => "externalOnly",
+            SendUpdates::None => "none",
+        }
+    }
+}
+
+/// 移動できなかったイベント
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveFailure {
+    pub event_id: String,
+    pub message: String,
+}
+
+/// カレンダー間の一括移動の進捗
+///
+/// 移動対象のイベントIDを一覧の全ページから集めてから移動するため、移動によって元のカレンダーの
+/// 一覧が変わってもページの取りこぼしはありません。途中でエラーになった場合や処理を中断した場合は、
+/// 保存しておいた進捗を `CalendarClient::move_events` に渡すと続きから再開します。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MoveProgress {
+    /// 次に読み込む一覧のページ。`listing_complete` が true の場合は使用しません
+    pub page_token: Option<String>,
+    /// 一覧をすべて読み込んだ場合は true
+    pub listing_complete: bool,
+    /// 移動待ちのイベントID（一覧の順）
+    pub pending: VecDeque<String>,
+    /// 移動済みのイベントID
+    pub moved: Vec<String>,
+    pub failed: Vec<MoveFailure>,
+    /// `pending`・`moved`・`failed` のいずれかに含まれるイベントID。保存せず、再開時に作り直します
+    #[serde(skip)]
+    seen: HashSet<String>,
+}
+
+impl PartialEq for MoveProgress {
+    fn eq(&self, other: &Self) -> bool {
+        self.page_token == other.page_token
+            && self.listing_complete == other.listing_complete
+            && self.pending == other.pending
+            && self.moved == other.moved
+            && self.failed == other.failed
+    }
+}
+
+impl Eq for MoveProgress {}
+
+impl MoveProgress {
+    pub fn new() -> Self {
+        MoveProgress::default()
+    }
+
+    /// 一覧の読み込みと移動がすべて終わった場合に true を返します
+    pub fn is_complete(&self) -> bool {
+        self.listing_complete && self.pending.is_empty()
+    }
+
+    /// 移動に失敗したイベントを移動待ちに戻します
+    pub fn retry_failed(&mut self) {
+        let failed = std::mem::take(&mut self.failed);
+        self.pending.extend(failed.into_iter().map(|f| f.event_id));
+    }
+
+    /// 一覧のイベントを移動待ちに追加します。既に追加済み・処理済みのイベントは無視します
+    ///
+    /// 移動できない種類のイベント（誕生日など）と削除済みのイベントは、移動せずに失敗として記録します。
+    pub(crate) fn enqueue(&mut self, events: &[Event]) {
+        self.sync_seen();
+        for event in events {
+            let Some(id) = &event.id else { continue };
+            if self.seen.contains(id) || event.status.as_deref() == Some("cancelled") {
+                continue;
+            }
+            self.seen.insert(id.clone());
+            match &event.event_type {
+                None | Some(EventType::Default) => self.pending.push_back(id.clone()),
+                Some(event_type) => self.failed.push(MoveFailure {
+                    event_id: id.clone(),
+                    message: format!(
+                        "この種類のイベントは移動できません: {}",
+                        event_type.as_str()
+                    ),
+                }),
+            }
+        }
+    }
+
+    /// 処理済みのイベントIDの集合を、保存から復元した場合や各一覧を直接変更した場合に作り直します
+    fn sync_seen(&mut self) {
+        let total = self.pending.len() + self.moved.len() + self.failed.len();
+        if self.seen.len() == total {
+            return;
+        }
+        self.seen = self
+            .pending
+            .iter()
+            .chain(&self.moved)
+            .chain(self.failed.iter().map(|f| &f.event_id))
+            .cloned()
+            .collect();
+    }
+}
+
+/// `CalendarClient::move_events` の1回の呼び出しの結果
+#[derive(Debug, Clone, Default)]
+pub struct MoveReport {
+    /// 移動したイベント（移動先のカレンダーのイベント）
+    pub moved: Vec<Event>,
+    pub failed: Vec<MoveFailure>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enqueue_and_retry() {
+        let event = |id: &str| Event {
+            id: Some(id.to_string()),
+            ..Default::default()
+        };
+        let mut progress = MoveProgress::new();
+        let birthday = Event {
+            event_type: Some(EventType::Birthday),
+            ..event("birthday")
+        };
+        let cancelled = Event {
+            status: Some("cancelled".to_string()),
+            ..event("cancelled")
+        };
+        progress.enqueue(&[event("a"), event("b"), birthday, cancelled, event("a")]);
+        assert_eq!(progress.pending, vec!["a", "b"]);
+        assert_eq!(progress.failed.len(), 1);
+        assert!(!progress.is_complete());
+
+        progress.pending.clear();
+        progress.listing_complete = true;
+        assert!(progress.is_complete());
+        progress.retry_failed();
+        assert_eq!(progress.pending, vec!["birthday"]);
+        assert!(progress.failed.is_empty());
+    }
+
+    #[test]
+    fn test_progress_serialization() {
+        let progress = MoveProgress {
+            page_token: Some("page2".to_string()),
+            pending: VecDeque::from(["a".to_string()]),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&progress).unwrap();
+        let mut restored = serde_json::from_str::<MoveProgress>(&json).unwrap();
+        assert_eq!(restored, progress);
+
+        // 再開後も移動待ちのイベントを重複して追加しない
+        let event = |id: &str| Event {
+            id: Some(id.to_string()),
+            ..Default::default()
+        };
+        restored.enqueue(&[event("a"), event("b")]);
+        assert_eq!(restored.pending, vec!["a", "b"]);
+        assert_eq!(
+            serde_json::to_value(SendUpdates::ExternalOnly).unwrap(),
+            "externalOnly"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// イベントの種類
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum EventType {
+    /// 通常のイベント
+    #[default]
+    Default,
+    /// 不在
+    OutOfOffice,
+    /// サイレント時間
+    FocusTime,
+    /// 勤務場所
+    WorkingLocation,
+    /// Gmail から作成されたイベント（読み取り専用）
+    FromGmail,
+    /// 誕生日（読み取り専用）
+    Birthday,
+    /// このライブラリが認識しない種類。APIから受け取った値をそのまま保持し、書き戻すときもその値を送ります
+    Other(String),
+}
+
+impl EventType {
+    /// APIの文字列表現から種類を返します。認識できない値は `Other` になります
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "default" => EventType::Default,
+            "outOfOffice" => EventType::OutOfOffice,
+            "focusTime" => EventType::FocusTime,
+            "workingLocation" => EventType::WorkingLocation,
+            "fromGmail" => EventType::FromGmail,
+            "birthday" => EventType::Birthday,
+            other => EventType::Other(other.to_string()),
+        }
+    }
+
+    /// 新しく作成するイベント・変更後の種類として指定できる場合に true を返します
+    ///
+    /// 読み取り専用の種類（`fromGmail`、`birthday`）と認識できない種類は指定できません。
+    pub fn is_writable(&self) -> bool {
+        !matches!(
+            self,
+            EventType::FromGmail | EventType::Birthday | EventType::Other(_)
+        )
+    }
+
+    /// APIで使用される文字列表現
+    pub fn as_str(&self) -> &str {
+        match self {
+            EventType::Default => "default",
+            EventType::OutOfOffice => "outOfOffice",
+            EventType::FocusTime => "focusTime",
+            EventType::WorkingLocation => "workingLocation",
+            EventType::FromGmail => "fromGmail",
+            EventType::Birthday => "birthday",
+            EventType::Other(value) => value,
+        }
+    }
+}
+
+impl Serialize for EventType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EventType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(EventType::parse(&value))
+    }
+}
+
+/// 期間中に届いた招待をどのように自動辞退するか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AutoDeclineMode {
+    /// 自動辞退しない
+    DeclineNone,
+    /// 既存・新規を問わず重なる招待をすべて辞退
+    DeclineAllConflictingInvitations,
+    /// 新たに届いた重なる招待のみ辞退
+    DeclineOnlyNewConflictingInvitations,
+}
+
+/// 不在イベントの設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutOfOfficeProperties {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_decline_mode: Option<AutoDeclineMode>,
+    /// 自動辞退時に返信されるメッセージ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decline_message: Option<String>,
+}
+
+impl OutOfOfficeProperties {
+    pub fn new(auto_decline_mode: AutoDeclineMode) -> Self {
+        OutOfOfficeProperties {
+            auto_decline_mode: Some(auto_decline_mode),
+            decline_message: None,
+        }
+    }
+
+    pub fn with_decline_message(mut self, message: impl Into<String>) -> Self {
+        self.decline_message = Some(message.into());
+        self
+    }
+}
+
+/// Google Chat のステータス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChatStatus {
+    Available,
+    DoNotDisturb,
+}
+
+/// サイレント時間イベントの設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusTimeProperties {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_decline_mode: Option<AutoDeclineMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decline_message: Option<String>,
+    /// 期間中の Google Chat のステータス
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_status: Option<ChatStatus>,
+}
+
+impl FocusTimeProperties {
+    pub fn new(auto_decline_mode: AutoDeclineMode) -> Self {
+        FocusTimeProperties {
+            auto_decline_mode: Some(auto_decline_mode),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_decline_message(mut self, message: impl Into<String>) -> Self {
+        self.decline_message = Some(message.into());
+        self
+    }
+
+    pub fn with_chat_status(mut self, chat_status: ChatStatus) -> Self {
+        self.chat_status = Some(chat_status);
+        self
+    }
+}
+
+/// 勤務場所の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorkingLocationType {
+    HomeOffice,
+    OfficeLocation,
+    CustomLocation,
+}
+
+/// 在宅勤務（追加の情報はありません）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HomeOffice {}
+
+/// オフィスでの勤務場所
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficeLocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub building_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor_section_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desk_id: Option<String>,
+    /// カレンダー上に表示される名前
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// 任意の勤務場所
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomLocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// 勤務場所イベントの設定
+///
+/// `location_type` に対応するフィールドのみを設定します。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkingLocationProperties {
+    #[serde(rename = "type")]
+    pub location_type: WorkingLocationType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_office: Option<HomeOffice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub office_location: Option<OfficeLocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_location: Option<CustomLocation>,
+}
+
+impl WorkingLocationProperties {
+    pub fn home_office() -> Self {
+        WorkingLocationProperties {
+            location_type: WorkingLocationType::HomeOffice,
+            home_office: Some(HomeOffice {}),
+            office_location: None,
+            custom_location: None,
+        }
+    }
+
+    pub fn office(office_location: OfficeLocation) -> Self {
+        WorkingLocationProperties {
+            location_type: WorkingLocationType::OfficeLocation,
+            home_office: None,
+            office_location: Some(office_location),
+            custom_location: None,
+        }
+    }
+
+    pub fn custom(label: impl Into<String>) -> Self {
+        WorkingLocationProperties {
+            location_type: WorkingLocationType::CustomLocation,
+            home_office: None,
+            office_location: None,
+            custom_location: Some(CustomLocation {
+                label: Some(label.into()),
+            }),
+        }
+    }
+
+    /// カレンダー上に表示される勤務場所の名前
+    pub fn label(&self) -> Option<&str> {
+        match self.location_type {
+            WorkingLocationType::HomeOffice => Some("自宅"),
+            WorkingLocationType::OfficeLocation => self
+                .office_location
+                .as_ref()
+                .and_then(|l| l.label.as_deref()),
+            WorkingLocationType::CustomLocation => self
+                .custom_location
+                .as_ref()
+                .and_then(|l| l.label.as_deref()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let matches = match self.location_type {
+            WorkingLocationType::HomeOffice => {
+                self.office_location.is_none() && self.custom_location.is_none()
+            }
+            WorkingLocationType::OfficeLocation => {
+                self.office_location.is_some()
+                    && self.home_office.is_none()
+                    && self.custom_location.is_none()
+            }
+            WorkingLocationType::CustomLocation => {
+                self.custom_location.is_some()
+                    && self.home_office.is_none()
+                    && self.office_location.is_none()
+            }
+        };
+        if !matches {
+            return Err(format!(
+                "勤務場所の種類({:?})と設定されている場所が一致しません",
+                self.location_type
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_serialization() {
+        assert_eq!(
+            serde_json::to_value(EventType::OutOfOffice).unwrap(),
+            "outOfOffice"
+        );
+        let parsed: EventType = serde_json::from_str("\"workingLocation\"").unwrap();
+        assert_eq!(parsed, EventType::WorkingLocation);
+        assert_eq!(parsed.as_str(), "workingLocation");
+        // 認識できない種類は元の値のまま読み書きする
+        let parsed: EventType = serde_json::from_str("\"someFutureType\"").unwrap();
+        assert_eq!(parsed, EventType::Other("someFutureType".to_string()));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), "someFutureType");
+        assert!(!parsed.is_writable());
+        assert!(!EventType::Birthday.is_writable());
+        assert!(EventType::FocusTime.is_writable());
+    }
+
+    #[test]
+    fn test_properties_serialization() {
+        let ooo = OutOfOfficeProperties::new(AutoDeclineMode::DeclineOnlyNewConflictingInvitations)
+            .with_decline_message("休暇中です");
+        assert_eq!(
+            serde_json::to_value(&ooo).unwrap(),
+            serde_json::json!({
+                "autoDeclineMode": "declineOnlyNewConflictingInvitations",
+                "declineMessage": "休暇中です"
+            })
+        );
+
+        let focus = FocusTimeProperties::new(AutoDeclineMode::DeclineNone)
+            .with_chat_status(ChatStatus::DoNotDisturb);
+        assert_eq!(
+            serde_json::to_value(&focus).unwrap()["chatStatus"],
+            "doNotDisturb"
+        );
+
+        assert_eq!(
+            serde_json::to_value(WorkingLocationProperties::home_office()).unwrap(),
+            serde_json::json!({"type": "homeOffice", "homeOffice": {}})
+        );
+    }
+
+    #[test]
+    fn test_working_location() {
+        let json = r#"{
+            "type": "officeLocation",
+            "officeLocation": {"buildingId": "tokyo-1", "floorId": "5", "label": "東京オフィス"}
+        }"#;
+        let props: WorkingLocationProperties = serde_json::from_str(json).unwrap();
+        assert_eq!(props.label(), Some("東京オフィス"));
+        assert!(props.validate().is_ok());
+        assert_eq!(
+            WorkingLocationProperties::custom("カフェ").label(),
+            Some("カフェ")
+        );
+
+        let mismatched = WorkingLocationProperties {
+            location_type: WorkingLocationType::CustomLocation,
+            ..WorkingLocationProperties::home_office()
+        };
+        assert!(mismatched.validate().is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// キーの最大文字数
+pub const MAX_KEY_LENGTH: usize = 44;
+/// 値の最大文字数
+pub const MAX_VALUE_LENGTH: usize = 1024;
+/// private / shared それぞれに保存できるプロパティ数の上限
+pub const MAX_PROPERTIES: usize = 300;
+/// private と shared を合わせたキーと値の合計サイズ(バイト)の上限
+pub const MAX_TOTAL_SIZE: usize = 32 * 1024;
+
+/// 拡張プロパティの保存先
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyScope {
+    /// このカレンダー上のイベントのコピーにのみ保存される
+    Private,
+    /// 参加者全員のイベントのコピーに保存される
+    Shared,
+}
+
+/// イベントに付与できる独自のキーと値
+///
+/// `private` は作成したカレンダーのコピーにのみ、`shared` は参加者全員のコピーに保存されます。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtendedProperties {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub private: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub shared: HashMap<String, String>,
+}
+
+impl ExtendedProperties {
+    pub fn map(&self, scope: PropertyScope) -> &HashMap<String, String> {
+        match scope {
+            PropertyScope::Private => &self.private,
+            PropertyScope::Shared => &self.shared,
+        }
+    }
+
+    pub fn map_mut(&mut self, scope: PropertyScope) -> &mut HashMap<String, String> {
+        match scope {
+            PropertyScope::Private => &mut self.private,
+            PropertyScope::Shared => &mut self.shared,
+        }
+    }
+
+    /// Googleのサイズ制限を満たしているかを検証します
+    ///
+    /// 制限を超えたプロパティはAPI側で黙って捨てられることがあるため、送信前に確認します。
+    pub fn validate(&self) -> Result<(), String> {
+        let mut total = 0;
+        for (scope, map) in [("private", &self.private), ("shared", &self.shared)] {
+            if map.len() > MAX_PROPERTIES {
+                return Err(format!(
+                    "{}拡張プロパティの数が上限({})を超えています: {}",
+                    scope,
+                    MAX_PROPERTIES,
+                    map.len()
+                ));
+            }
+            for (key, value) in map {
+                if key.is_empty() || key.chars().count() > MAX_KEY_LENGTH {
+                    return Err(format!(
+                        "拡張プロパティのキーは1〜{}文字である必要があります: {}",
+                        MAX_KEY_LENGTH, key
+                    ));
+                }
+                if value.chars().count() > MAX_VALUE_LENGTH {
+                    return Err(format!(
+                        "拡張プロパティの値が上限({}文字)を超えています: {}",
+                        MAX_VALUE_LENGTH, key
+                    ));
+                }
+                total += key.len() + value.len();
+            }
+        }
+        if total > MAX_TOTAL_SIZE {
+            return Err(format!(
+                "拡張プロパティの合計サイズが上限({}バイト)を超えています: {}",
+                MAX_TOTAL_SIZE, total
+            ));
+        }
+        Ok(())
+    }
+
+    /// 構造体の各フィールドを1つのプロパティとして保存します
+    ///
+    /// 対象はフィールドが文字列・数値・真偽値・ユニット列挙子のみの平坦な構造体です。
+    /// 値は文字列として保存されるため、そのまま一覧の絞り込み条件にも使えます。
+    /// `None` のフィールドは保存せず、既存の同名キーも変更しません。
+    /// 保存後にサイズ制限を超える場合は変更せずにエラーを返します。
+    pub fn store<T: Serialize>(&mut self, scope: PropertyScope, value: &T) -> Result<(), String> {
+        let encoded = serde_urlencoded::to_string(value)
+            .map_err(|e| format!("拡張プロパティに変換できません: {}", e))?;
+        let pairs: Vec<(String, String)> = serde_urlencoded::from_str(&encoded)
+            .map_err(|e| format!("拡張プロパティに変換できません: {}", e))?;
+
+        let mut updated = self.clone();
+        updated.map_mut(scope).extend(pairs);
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+
+    /// 保存されたプロパティから構造体を読み込みます
+    ///
+    /// 構造体に対応しないキーは無視されます。
+    pub fn load<T: DeserializeOwned>(&self, scope: PropertyScope) -> Result<T, String> {
+        let encoded = serde_urlencoded::to_string(self.map(scope))
+            .map_err(|e| format!("拡張プロパティを読み込めません: {}", e))?;
+        serde_urlencoded::from_str(&encoded)
+            .map_err(|e| format!("拡張プロパティを読み込めません: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Source {
+        Jira,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TicketMeta {
+        source: Source,
+        ticket: String,
+        priority: u8,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        assignee: Option<String>,
+    }
+
+    #[test]
+    fn test_store_and_load_roundtrip() {
+        let meta = TicketMeta {
+            source: Source::Jira,
+            ticket: "PROJ-1".to_string(),
+            priority: 2,
+            assignee: None,
+        };
+        let mut props = ExtendedProperties::default();
+        props.private.insert("other".to_string(), "x".to_string());
+        props.store(PropertyScope::Private, &meta).unwrap();
+
+        assert_eq!(props.private["source"], "jira");
+        assert_eq!(props.private["priority"], "2");
+        assert!(!props.private.contains_key("assignee"));
+        assert!(props.shared.is_empty());
+
+        let loaded: TicketMeta = props.load(PropertyScope::Private).unwrap();
+        assert_eq!(loaded, meta);
+        assert!(props.load::<TicketMeta>(PropertyScope::Shared).is_err());
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        let mut props = ExtendedProperties::default();
+        props.shared.insert("k".repeat(45), "v".to_string());
+        assert!(props.validate().is_err());
+
+        let mut props = ExtendedProperties::default();
+        props.shared.insert("k".to_string(), "v".repeat(1025));
+        assert!(props.validate().is_err());
+
+        let mut props = ExtendedProperties::default();
+        for i in 0..40 {
+            props.private.insert(format!("key{}", i), "v".repeat(1000));
+        }
+        // 個々は制限内でも合計が32KBを超える
+        assert!(props.validate().is_err());
+    }
+
+    #[test]
+    fn test_store_rejects_oversized_value() {
+        #[derive(Serialize)]
+        struct Note {
+            note: String,
+        }
+        let mut props = ExtendedProperties::default();
+        let result = props.store(
+            PropertyScope::Shared,
+            &Note {
+                note: "a".repeat(2000),
+            },
+        );
+        assert!(result.is_err());
+        assert!(props.shared.is_empty());
+    }
+}
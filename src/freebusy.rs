@@ -0,0 +1,128 @@
+use crate::interval::Interval;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// freeBusy.query のリクエストボディ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreeBusyRequest {
+    pub time_min: DateTime<Utc>,
+    pub time_max: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    pub items: Vec<FreeBusyRequestItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreeBusyRequestItem {
+    pub id: String,
+}
+
+impl FreeBusyRequest {
+    /// 指定したカレンダー群について、期間内の予定状況を問い合わせるリクエストを作成します
+    pub fn new<I, S>(time_min: DateTime<Utc>, time_max: DateTime<Utc>, calendar_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        FreeBusyRequest {
+            time_min,
+            time_max,
+            time_zone: None,
+            items: calendar_ids
+                .into_iter()
+                .map(|id| FreeBusyRequestItem { id: id.into() })
+                .collect(),
+        }
+    }
+}
+
+/// freeBusy.query のレスポンス
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreeBusyResponse {
+    pub time_min: DateTime<Utc>,
+    pub time_max: DateTime<Utc>,
+    #[serde(default)]
+    pub calendars: HashMap<String, FreeBusyCalendar>,
+}
+
+/// カレンダーごとの予定あり時間帯
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FreeBusyCalendar {
+    #[serde(default)]
+    pub busy: Vec<TimePeriod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FreeBusyError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimePeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// カレンダー単位で返されるエラー（例: 権限不足で予定状況が取得できない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreeBusyError {
+    pub domain: String,
+    pub reason: String,
+}
+
+impl TimePeriod {
+    /// 時間帯に変換します。終了が開始以前の場合は None を返します
+    pub fn to_interval(&self) -> Option<Interval> {
+        Interval::new(self.start, self.end)
+    }
+}
+
+impl FreeBusyResponse {
+    /// 指定カレンダーの予定あり時間帯を返します。存在しない場合は空スライスを返します
+    pub fn busy_for(&self, calendar_id: &str) -> &[TimePeriod] {
+        self.calendars
+            .get(calendar_id)
+            .map(|c| c.busy.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_request_serialization() {
+        let min = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let max = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let req = FreeBusyRequest::new(min, max, ["a@example.com", "b@example.com"]);
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["timeMin"], "2024-01-01T00:00:00Z");
+        assert_eq!(json["items"][1]["id"], "b@example.com");
+        assert!(json.get("timeZone").is_none());
+    }
+
+    #[test]
+    fn test_response_deserialization() {
+        let json = r#"{
+            "kind": "calendar#freeBusy",
+            "timeMin": "2024-01-01T00:00:00.000Z",
+            "timeMax": "2024-01-02T00:00:00.000Z",
+            "calendars": {
+                "a@example.com": {
+                    "busy": [{"start": "2024-01-01T01:00:00Z", "end": "2024-01-01T02:00:00Z"}]
+                },
+                "b@example.com": {
+                    "errors": [{"domain": "global", "reason": "notFound"}],
+                    "busy": []
+                }
+            }
+        }"#;
+        let resp: FreeBusyResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.busy_for("a@example.com").len(), 1);
+        assert!(resp.busy_for("b@example.com").is_empty());
+        assert!(resp.busy_for("unknown").is_empty());
+        assert_eq!(resp.calendars["b@example.com"].errors[0].reason, "notFound");
+    }
+}
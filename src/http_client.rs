@@ -1,8 +1,8 @@
-use crate::config::GCalConfig;
+use crate::config::{DefaultTimeZone, GCalConfig};
 use crate::error::{GCalError, Result};
 use chrono::{Duration as ChronoDuration, Utc};
 use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
-use reqwest::{Client, Response};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -72,11 +72,12 @@ impl HttpClient {
     }
     #[cfg(test)]
     pub fn mock() -> Result<Self> {
-        let config = GCalConfig {
-            api_base_url: "https://www.googleapis.com/calendar/v3".to_string(),
-            timeout_seconds: 30,
-            credentials: None,
-        };
+        Self::mock_with_config(GCalConfig::default())
+    }
+
+    /// 設定を指定してモッククライアントを作成します
+    #[cfg(test)]
+    pub fn mock_with_config(config: GCalConfig) -> Result<Self> {
         Ok(Self {
             client: Client::new(),
             config,
@@ -86,17 +87,222 @@ impl HttpClient {
     #[cfg(test)]
     pub async fn mock_post_response(
         &self,
-        _path: &str,
+        path: &str,
         json: impl serde::Serialize,
     ) -> Result<String> {
+        if path == "freeBusy" {
+            // 全カレンダーが空いているものとして返す
+            let req: crate::freebusy::FreeBusyRequest =
+                serde_json::from_value(serde_json::to_value(&json)?)?;
+            let resp = crate::freebusy::FreeBusyResponse {
+                time_min: req.time_min,
+                time_max: req.time_max,
+                calendars: req
+                    .items
+                    .into_iter()
+                    .map(|item| (item.id, Default::default()))
+                    .collect(),
+            };
+            return Ok(serde_json::to_string(&resp)?);
+        }
+        if let Some((_, query)) = path.split_once("/events/quickAdd?") {
+            // テキストをそのままタイトルにしたイベントを返す
+            let params: std::collections::HashMap<String, String> =
+                serde_urlencoded::from_str(query).unwrap_or_default();
+            let mut event = crate::mock::test_utils::create_test_event();
+            event.id = Some("quickadd0001".to_string());
+            event.summary = params.get("text").cloned();
+            return Ok(serde_json::to_string(&event)?);
+        }
+        if path == "channels/stop" {
+            let value = serde_json::to_value(&json)?;
+            if value["id"] == crate::mock::test_utils::STOP_FAILURE_CHANNEL_ID {
+                return Err(GCalError::ApiError {
+                    status: 404,
+                    message: "Channel not found.".to_string(),
+                });
+            }
+            return Ok(String::new());
+        }
+        if let Some((prefix, query)) = path.split_once("/move?") {
+            // 移動先のカレンダーが主催者になったイベントを返す
+            let event_id = prefix.rsplit('/').next().unwrap_or_default();
+            if event_id == crate::mock::test_utils::MOVE_FORBIDDEN_EVENT_ID {
+                return Err(GCalError::ApiError {
+                    status: 403,
+                    message: "You need to have writer access to this calendar.".to_string(),
+                });
+            }
+            let params: std::collections::HashMap<String, String> =
+                serde_urlencoded::from_str(query).unwrap_or_default();
+            let mut event = crate::mock::test_utils::create_test_event();
+            event.id = Some(event_id.to_string());
+            event.organizer = params
+                .get("destination")
+                .map(|calendar_id| crate::attendee::Organizer::new(calendar_id.clone()));
+            return Ok(serde_json::to_string(&event)?);
+        }
+        let mut value = serde_json::to_value(&json)?;
+        if value["id"] == crate::mock::test_utils::CONFLICT_EVENT_ID {
+            return Err(GCalError::AlreadyExists(
+                "The requested identifier already exists.".to_string(),
+            ));
+        }
+        if path.contains("/events/import") {
+            if value["iCalUID"] == crate::mock::test_utils::REJECTED_ICAL_UID {
+                return Err(GCalError::ApiError {
+                    status: 400,
+                    message: "Invalid value".to_string(),
+                });
+            }
+            // インポートされたイベントにはIDが割り当てられる
+            value["id"] = serde_json::Value::String(crate::event_id::generate_event_id());
+        }
+        // 会議の作成を要求された場合は、作成処理中として返す
+        if let Some(request) = value
+            .pointer_mut("/conferenceData/createRequest")
+            .and_then(|r| r.as_object_mut())
+        {
+            request.insert(
+                "status".to_string(),
+                serde_json::json!({"statusCode": "pending"}),
+            );
+        }
         // モックレスポンスとして、リクエストされたイベントをそのまま返す
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    #[cfg(test)]
+    pub async fn mock_put_response(
+        &self,
+        path: &str,
+        json: impl serde::Serialize,
+        etag: Option<&str>,
+    ) -> Result<String> {
+        Self::mock_check_if_match(path, etag)?;
+        // 更新後のリソースとして、リクエストされた内容をそのまま返す
         Ok(serde_json::to_string(&json)?)
     }
 
     #[cfg(test)]
-    pub async fn mock_get_response(&self, _path: &str) -> Result<String> {
+    pub async fn mock_patch_response(
+        &self,
+        path: &str,
+        json: impl serde::Serialize,
+        etag: Option<&str>,
+    ) -> Result<String> {
+        Self::mock_check_if_match(path, etag)?;
+        // テストイベントにパッチの内容を反映したものを返す
+        let path_only = path.split('?').next().unwrap_or(path);
+        let mut event = serde_json::to_value(crate::mock::test_utils::create_test_event())?;
+        event["id"] = path_only.rsplit('/').next().unwrap_or_default().into();
+        event["etag"] = crate::mock::test_utils::MOCK_ETAG.into();
+        if let (Some(target), serde_json::Value::Object(patch)) =
+            (event.as_object_mut(), serde_json::to_value(&json)?)
+        {
+            for (key, value) in patch {
+                target.insert(key, value);
+            }
+        }
+        Ok(serde_json::to_string(&event)?)
+    }
+
+    #[cfg(test)]
+    pub async fn mock_delete_response(&self, path: &str, etag: Option<&str>) -> Result<String> {
+        Self::mock_check_if_match(path, etag)?;
+        Ok(String::new())
+    }
+
+    /// モックのリソースのETagは常に `MOCK_ETAG` として、If-Match を検証します
+    #[cfg(test)]
+    fn mock_check_if_match(path: &str, etag: Option<&str>) -> Result<()> {
+        match etag {
+            Some(etag) if etag != crate::mock::test_utils::MOCK_ETAG => Err(
+                GCalError::PreconditionFailed(format!("Precondition Failed: {}", path)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub async fn mock_get_if_none_match(&self, path: &str, etag: &str) -> Result<Option<String>> {
+        if etag == crate::mock::test_utils::MOCK_ETAG {
+            return Ok(None);
+        }
+        self.mock_get_response(path).await.map(Some)
+    }
+
+    #[cfg(test)]
+    pub async fn mock_get_response(&self, path: &str) -> Result<String> {
+        let path_only = path.split('?').next().unwrap_or(path);
+        if path_only == "users/me/settings" {
+            return Ok(serde_json::to_string(
+                &crate::mock::test_utils::create_test_settings(),
+            )?);
+        }
+        if let Some(id) = path_only.strip_prefix("users/me/settings/") {
+            return crate::mock::test_utils::create_test_settings()
+                .items
+                .into_iter()
+                .find(|s| s.id == id)
+                .map(|s| Ok(serde_json::to_string(&s)?))
+                .unwrap_or_else(|| {
+                    Err(GCalError::ApiError {
+                        status: 404,
+                        message: "Not Found".to_string(),
+                    })
+                });
+        }
+        if path_only == "colors" {
+            return Ok(serde_json::to_string(
+                &crate::mock::test_utils::create_test_colors(),
+            )?);
+        }
+        if path_only == "users/me/calendarList" {
+            let list = crate::calendar_list::CalendarList {
+                items: vec![crate::mock::test_utils::create_test_calendar_list_entry(
+                    "primary",
+                )],
+                ..Default::default()
+            };
+            return Ok(serde_json::to_string(&list)?);
+        }
+        if let Some(id) = path_only.strip_prefix("users/me/calendarList/") {
+            let entry = crate::mock::test_utils::create_test_calendar_list_entry(id);
+            return Ok(serde_json::to_string(&entry)?);
+        }
+        if path_only.ends_with(crate::mock::test_utils::CONFERENCE_EVENT_ID) {
+            let event = crate::mock::test_utils::create_test_event_with_conference();
+            return Ok(serde_json::to_string(&event)?);
+        }
+        if path_only
+            == format!(
+                "calendars/{}/events",
+                crate::mock::test_utils::MOVE_SOURCE_CALENDAR_ID
+            )
+        {
+            let page_token = path.contains("pageToken=").then_some("page2");
+            return Ok(serde_json::to_string(
+                &crate::mock::test_utils::create_test_move_source_page(page_token),
+            )?);
+        }
+        if path_only.ends_with("/events") {
+            // イベント一覧として、IDを付けたテストイベントを1件返す
+            return Ok(serde_json::to_string(
+                &crate::mock::test_utils::create_test_event_list(),
+            )?);
+        }
         // テストイベントをJSONにしたものをレスポンスとして返す
-        let mock_event = crate::mock::test_utils::create_test_event();
+        let mut mock_event = crate::mock::test_utils::create_test_event();
+        mock_event.etag = Some(
+            if path_only.ends_with(crate::mock::test_utils::STALE_ETAG_EVENT_ID) {
+                // 取得後に他のクライアントが更新したものとして、古いETagを返す
+                "\"stale_etag\""
+            } else {
+                crate::mock::test_utils::MOCK_ETAG
+            }
+            .to_string(),
+        );
         Ok(serde_json::to_string(&mock_event)?)
     }
 
@@ -104,6 +310,27 @@ impl HttpClient {
         &self.config.api_base_url
     }
 
+    /// ベースURLのパス部分（例: "/calendar/v3"）
+    pub fn base_path(&self) -> &str {
+        let url = &self.config.api_base_url;
+        let after_scheme = url.find("://").map(|i| i + 3).unwrap_or(0);
+        match url[after_scheme..].find('/') {
+            Some(i) => url[after_scheme + i..].trim_end_matches('/'),
+            None => "",
+        }
+    }
+
+    pub(crate) fn default_time_zone(&self) -> DefaultTimeZone {
+        self.config.default_time_zone
+    }
+
+    /// バッチエンドポイントのURL（例: "https://www.googleapis.com/batch/calendar/v3"）
+    pub fn batch_url(&self) -> String {
+        let url = self.config.api_base_url.trim_end_matches('/');
+        let path = self.base_path();
+        format!("{}/batch{}", &url[..url.len() - path.len()], path)
+    }
+
     pub fn new(config: GCalConfig) -> Result<Self> {
         let client = Client::builder()
             .timeout(Duration::from_secs(config.timeout_seconds))
@@ -112,51 +339,139 @@ impl HttpClient {
         Ok(HttpClient { client, config })
     }
 
+    /// 認証情報が設定されている場合、アクセストークンを付与します
+    async fn authorize(&self, request: RequestBuilder) -> Result<RequestBuilder> {
+        if self.config.credentials.is_some() {
+            let token = self.get_access_token().await?;
+            Ok(request.header("Authorization", format!("Bearer {}", token)))
+        } else {
+            Ok(request)
+        }
+    }
+
     pub async fn get(&self, path: &str) -> Result<String> {
         let url = format!("{}/{}", self.config.api_base_url, path);
-        let resp = self.client.get(&url).send().await?;
+        let request = self.authorize(self.client.get(&url)).await?;
+        let resp = request.send().await?;
         self.handle_response(resp).await
     }
 
     pub async fn post(&self, path: &str, json: impl serde::Serialize) -> Result<String> {
         let url = format!("{}/{}", self.config.api_base_url, path);
-        let mut request = self.client.post(&url).json(&json);
-
-        // Add authorization if credentials are available
-        if self.config.credentials.is_some() {
-            let token = self.get_access_token().await?;
-            println!("Using authorization token: {}", token);
-            request = request.header("Authorization", format!("Bearer {}", token));
-        } else {
-            println!("No credentials available for authorization");
-        }
+        let request = self.authorize(self.client.post(&url).json(&json)).await?;
 
         println!("Sending request to URL: {}", url);
         let resp = request.send().await?;
         self.handle_response(resp).await
     }
 
+    /// If-None-Match を付けてGETします。リソースが変更されていない（304）場合は None を返します
+    pub async fn get_if_none_match(&self, path: &str, etag: &str) -> Result<Option<String>> {
+        let url = format!("{}/{}", self.config.api_base_url, path);
+        let request = self
+            .authorize(self.client.get(&url).header("If-None-Match", etag))
+            .await?;
+        let resp = request.send().await?;
+        if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        self.handle_response(resp).await.map(Some)
+    }
+
     pub async fn put(&self, path: &str, json: impl serde::Serialize) -> Result<String> {
+        self.put_if_match(path, json, None).await
+    }
+
+    /// `etag` を指定した場合は If-Match を付けてPUTします
+    pub async fn put_if_match(
+        &self,
+        path: &str,
+        json: impl serde::Serialize,
+        etag: Option<&str>,
+    ) -> Result<String> {
+        let url = format!("{}/{}", self.config.api_base_url, path);
+        let request = self
+            .authorize(if_match(self.client.put(&url).json(&json), etag))
+            .await?;
+        let resp = request.send().await?;
+        self.handle_response(resp).await
+    }
+
+    pub async fn patch(&self, path: &str, json: impl serde::Serialize) -> Result<String> {
+        self.patch_if_match(path, json, None).await
+    }
+
+    /// `etag` を指定した場合は If-Match を付けてPATCHします
+    pub async fn patch_if_match(
+        &self,
+        path: &str,
+        json: impl serde::Serialize,
+        etag: Option<&str>,
+    ) -> Result<String> {
         let url = format!("{}/{}", self.config.api_base_url, path);
-        let resp = self.client.put(&url).json(&json).send().await?;
+        let request = self
+            .authorize(if_match(self.client.patch(&url).json(&json), etag))
+            .await?;
+        let resp = request.send().await?;
         self.handle_response(resp).await
     }
 
     pub async fn delete(&self, path: &str) -> Result<String> {
+        self.delete_if_match(path, None).await
+    }
+
+    /// `etag` を指定した場合は If-Match を付けてDELETEします
+    pub async fn delete_if_match(&self, path: &str, etag: Option<&str>) -> Result<String> {
         let url = format!("{}/{}", self.config.api_base_url, path);
-        let resp = self.client.delete(&url).send().await?;
+        let request = self
+            .authorize(if_match(self.client.delete(&url), etag))
+            .await?;
+        let resp = request.send().await?;
         self.handle_response(resp).await
     }
 
+    /// multipart/mixed のバッチリクエストを送信し、レスポンスの Content-Type と本文を返します
+    pub async fn post_batch(&self, body: String, boundary: &str) -> Result<(String, String)> {
+        let request = self
+            .client
+            .post(self.batch_url())
+            .header(
+                "Content-Type",
+                format!("multipart/mixed; boundary={}", boundary),
+            )
+            .body(body);
+        let resp = self.authorize(request).await?.send().await?;
+        let content_type = resp
+            .headers()
+            .get("Content-Type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = self.handle_response(resp).await?;
+        Ok((content_type, body))
+    }
+
     async fn handle_response(&self, response: Response) -> Result<String> {
-        if response.status().is_success() {
+        let status = response.status();
+        if status.is_success() {
             Ok(response.text().await?)
+        } else if status == reqwest::StatusCode::CONFLICT {
+            Err(GCalError::AlreadyExists(response.text().await?))
+        } else if status == reqwest::StatusCode::PRECONDITION_FAILED {
+            Err(GCalError::PreconditionFailed(response.text().await?))
         } else {
-            Err(GCalError::Other(format!(
-                "APIエラー: ステータスコード {} - {}",
-                response.status(),
-                response.text().await?
-            )))
+            Err(GCalError::ApiError {
+                status: status.as_u16(),
+                message: response.text().await?,
+            })
         }
     }
 }
+
+/// `etag` が指定されている場合に If-Match ヘッダーを付与します
+fn if_match(request: RequestBuilder, etag: Option<&str>) -> RequestBuilder {
+    match etag {
+        Some(etag) => request.header("If-Match", etag),
+        None => request,
+    }
+}
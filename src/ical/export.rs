@@ -0,0 +1,507 @@
+use super::timezone::vtimezone;
+use super::{Component, IcalError, Property};
+use crate::attendee::{Attendee, ResponseStatus};
+use crate::event::{Event, EventDateTime};
+use crate::event_id::generate_event_id;
+use crate::reminders::{Reminder, ReminderMethod};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+/// 繰り返しイベントのために VTIMEZONE に含める年数
+///
+/// 繰り返しは終わりがないことがあるため、最初の回から指定した年数分の切り替えを出力します。
+pub const RECURRING_TIMEZONE_YEARS: i32 = 10;
+
+/// イベントを iCalendar の VCALENDAR に変換するエクスポーター
+#[derive(Debug, Clone)]
+pub struct IcalExporter {
+    prod_id: String,
+    calendar_name: Option<String>,
+    dtstamp: DateTime<Utc>,
+    default_reminders: Vec<Reminder>,
+}
+
+impl Default for IcalExporter {
+    fn default() -> Self {
+        IcalExporter::new()
+    }
+}
+
+impl IcalExporter {
+    pub fn new() -> Self {
+        IcalExporter {
+            prod_id: "-//google_calendar_rs//iCalendar Export//JA".to_string(),
+            calendar_name: None,
+            dtstamp: Utc::now(),
+            default_reminders: Vec::new(),
+        }
+    }
+
+    pub fn with_prod_id(mut self, prod_id: impl Into<String>) -> Self {
+        self.prod_id = prod_id.into();
+        self
+    }
+
+    /// カレンダー名（X-WR-CALNAME）
+    pub fn with_calendar_name(mut self, name: impl Into<String>) -> Self {
+        self.calendar_name = Some(name.into());
+        self
+    }
+
+    /// 各 VEVENT の DTSTAMP。既定ではエクスポーターの作成日時です
+    pub fn with_dtstamp(mut self, dtstamp: DateTime<Utc>) -> Self {
+        self.dtstamp = dtstamp;
+        self
+    }
+
+    /// `useDefault` のイベントに VALARM として出力するカレンダーのデフォルトリマインダー
+    pub fn with_default_reminders(mut self, reminders: Vec<Reminder>) -> Self {
+        self.default_reminders = reminders;
+        self
+    }
+
+    /// イベントを iCalendar テキストに変換します
+    pub fn export(&self, events: &[Event]) -> Result<String, IcalError> {
+        Ok(self.to_calendar(events)?.to_ics())
+    }
+
+    /// イベントを jCal（RFC 7265）に変換します
+    pub fn export_jcal(&self, events: &[Event]) -> Result<serde_json::Value, IcalError> {
+        Ok(self.to_calendar(events)?.to_jcal())
+    }
+
+    /// イベントを xCal（RFC 6321）の XML 文書に変換します
+    pub fn export_xcal(&self, events: &[Event]) -> Result<String, IcalError> {
+        Ok(self.to_calendar(events)?.to_xcal())
+    }
+
+    /// イベントを VCALENDAR コンポーネントに変換します
+    ///
+    /// 使用されているタイムゾーンごとに VTIMEZONE を生成し、VEVENT より前に配置します。
+    pub fn to_calendar(&self, events: &[Event]) -> Result<Component, IcalError> {
+        let mut calendar = Component::new("VCALENDAR");
+        calendar.add("VERSION", "2.0");
+        calendar.add_text("PRODID", &self.prod_id);
+        calendar.add("CALSCALE", "GREGORIAN");
+        if let Some(name) = &self.calendar_name {
+            calendar.add_text("X-WR-CALNAME", name);
+        }
+
+        let mut zones = TimezoneUsage::default();
+        let vevents = events
+            .iter()
+            .map(|event| self.event_to_vevent(event, &mut zones))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (tz, from, to) in zones.years.into_values() {
+            calendar.components.push(vtimezone(tz, from, to));
+        }
+        calendar.components.extend(vevents);
+        Ok(calendar)
+    }
+
+    /// 1つのイベントを VEVENT コンポーネントに変換します（VTIMEZONE は含みません）
+    pub fn to_vevent(&self, event: &Event) -> Result<Component, IcalError> {
+        self.event_to_vevent(event, &mut TimezoneUsage::default())
+    }
+
+    fn event_to_vevent(
+        &self,
+        event: &Event,
+        zones: &mut TimezoneUsage,
+    ) -> Result<Component, IcalError> {
+        let mut vevent = Component::new("VEVENT");
+        let uid = event
+            .i_cal_uid
+            .clone()
+            .or_else(|| event.id.as_ref().map(|id| format!("{}@google.com", id)))
+            .unwrap_or_else(|| format!("{}@google.com", generate_event_id()));
+        vevent.add_text("UID", &uid);
+        vevent.add("DTSTAMP", format_utc(&self.dtstamp));
+
+        let recurring = !event.recurrence.is_empty();
+        if let Some(start) = &event.start {
+            vevent.push(date_time_property("DTSTART", start, zones, recurring)?);
+        }
+        if let Some(end) = &event.end {
+            vevent.push(date_time_property("DTEND", end, zones, recurring)?);
+        }
+        for line in &event.recurrence {
+            let property = Property::parse(line).map_err(IcalError::InvalidEvent)?;
+            vevent.push(property);
+        }
+
+        if let Some(summary) = &event.summary {
+            vevent.add_text("SUMMARY", summary);
+        }
+        if let Some(description) = &event.description {
+            vevent.add_text("DESCRIPTION", description);
+        }
+        if let Some(location) = &event.location {
+            vevent.add_text("LOCATION", location);
+        }
+        if let Some(status) = &event.status {
+            vevent.add("STATUS", status.to_ascii_uppercase());
+        }
+        if let Some(created) = &event.created {
+            vevent.add("CREATED", format_utc(created));
+        }
+        if let Some(updated) = &event.updated {
+            vevent.add("LAST-MODIFIED", format_utc(updated));
+        }
+        if let Some(sequence) = event.sequence {
+            vevent.add("SEQUENCE", sequence.to_string());
+        }
+        if let Some(visibility) = event.extra.get("visibility").and_then(|v| v.as_str()) {
+            if visibility != "default" {
+                vevent.add("CLASS", visibility.to_ascii_uppercase());
+            }
+        }
+        if let Some(transparency) = event.extra.get("transparency").and_then(|v| v.as_str()) {
+            vevent.add("TRANSP", transparency.to_ascii_uppercase());
+        }
+        if let Some(link) = &event.html_link {
+            vevent.add("URL", link.clone());
+        }
+
+        if let Some(organizer) = &event.organizer {
+            if let Some(email) = &organizer.email {
+                let mut property = Property::new("ORGANIZER", format!("mailto:{}", email));
+                if let Some(name) = &organizer.display_name {
+                    property = property.with_param("CN", name);
+                }
+                vevent.push(property);
+            }
+        }
+        for attendee in &event.attendees {
+            vevent.push(attendee_property(attendee));
+        }
+
+        let reminders = match &event.reminders {
+            Some(reminders) => reminders.effective(&self.default_reminders),
+            None => &self.default_reminders,
+        };
+        let summary = event.summary.as_deref().unwrap_or_default();
+        for reminder in reminders {
+            if let Some(alarm) = valarm(reminder, summary, &event.attendees) {
+                vevent.components.push(alarm);
+            }
+        }
+        Ok(vevent)
+    }
+}
+
+/// 使用されたタイムゾーンと、VTIMEZONE に含める年の範囲（TZID順）
+#[derive(Default)]
+struct TimezoneUsage {
+    years: BTreeMap<&'static str, (Tz, i32, i32)>,
+}
+
+impl TimezoneUsage {
+    fn record(&mut self, tz: Tz, year: i32, recurring: bool) {
+        let to = if recurring {
+            year + RECURRING_TIMEZONE_YEARS
+        } else {
+            year
+        };
+        let entry = self.years.entry(tz.name()).or_insert((tz, year, to));
+        entry.1 = entry.1.min(year);
+        entry.2 = entry.2.max(to);
+    }
+}
+
+/// DTSTART / DTEND を作成します
+///
+/// 終日は `VALUE=DATE`、IANAタイムゾーンを持つ日時は `TZID` 付きのローカル時刻、
+/// それ以外はUTC（オフセットもタイムゾーンもない日時はフローティング）で出力します。
+fn date_time_property(
+    name: &str,
+    dt: &EventDateTime,
+    zones: &mut TimezoneUsage,
+    recurring: bool,
+) -> Result<Property, IcalError> {
+    if dt.is_all_day() {
+        let date = dt.date.as_deref().unwrap_or_default();
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| IcalError::InvalidEvent(format!("{}: {}: {}", name, date, e)))?;
+        return Ok(
+            Property::new(name, date.format("%Y%m%d").to_string()).with_param("VALUE", "DATE")
+        );
+    }
+
+    let invalid = |e: String| IcalError::InvalidEvent(format!("{}: {}: {}", name, dt.date_time, e));
+    let tz = dt
+        .time_zone
+        .parse::<Tz>()
+        .ok()
+        .filter(|tz| !matches!(tz, Tz::UTC | Tz::Etc__UTC));
+    let with_offset = DateTime::parse_from_rfc3339(&dt.date_time).ok();
+
+    if let Some(tz) = tz {
+        let local = match with_offset {
+            Some(instant) => instant.with_timezone(&tz).naive_local(),
+            None => parse_floating(&dt.date_time).map_err(invalid)?,
+        };
+        zones.record(tz, local.year(), recurring);
+        return Ok(
+            Property::new(name, local.format("%Y%m%dT%H%M%S").to_string())
+                .with_param("TZID", tz.name()),
+        );
+    }
+
+    let utc = match with_offset {
+        Some(instant) => instant.with_timezone(&Utc),
+        None => {
+            let local = parse_floating(&dt.date_time).map_err(invalid)?;
+            if dt.time_zone.is_empty() {
+                return Ok(Property::new(
+                    name,
+                    local.format("%Y%m%dT%H%M%S").to_string(),
+                ));
+            }
+            crate::timezone_utils::local_to_utc(local, &dt.time_zone)
+                .map_err(|e| invalid(e.to_string()))?
+        }
+    };
+    Ok(Property::new(name, format_utc(&utc)))
+}
+
+fn parse_floating(value: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").map_err(|e| e.to_string())
+}
+
+fn format_utc(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn attendee_property(attendee: &Attendee) -> Property {
+    let mut property = Property::new("ATTENDEE", format!("mailto:{}", attendee.email));
+    if let Some(name) = &attendee.display_name {
+        property = property.with_param("CN", name);
+    }
+    if attendee.resource == Some(true) {
+        property = property.with_param("CUTYPE", "RESOURCE");
+    }
+    let role = if attendee.optional == Some(true) {
+        "OPT-PARTICIPANT"
+    } else {
+        "REQ-PARTICIPANT"
+    };
+    property = property.with_param("ROLE", role);
+    if let Some(status) = attendee.response_status {
+        let partstat = match status {
+            ResponseStatus::NeedsAction => "NEEDS-ACTION",
+            ResponseStatus::Accepted => "ACCEPTED",
+            ResponseStatus::Declined => "DECLINED",
+            ResponseStatus::Tentative => "TENTATIVE",
+        };
+        property = property.with_param("PARTSTAT", partstat);
+    }
+    property
+}
+
+/// リマインダーを VALARM に変換します。メール通知の宛先にはユーザー自身の参加者を使用します
+fn valarm(reminder: &Reminder, summary: &str, attendees: &[Attendee]) -> Option<Component> {
+    let mut alarm = Component::new("VALARM");
+    match reminder.method {
+        ReminderMethod::Popup => {
+            alarm.add("ACTION", "DISPLAY");
+            alarm.add_text("DESCRIPTION", summary);
+        }
+        ReminderMethod::Email => {
+            alarm.add("ACTION", "EMAIL");
+            alarm.add_text("SUMMARY", summary);
+            alarm.add_text("DESCRIPTION", summary);
+            for attendee in attendees.iter().filter(|a| a.is_self == Some(true)) {
+                alarm.add("ATTENDEE", format!("mailto:{}", attendee.email));
+            }
+        }
+        ReminderMethod::Unsupported => return None,
+    }
+    alarm.add("TRIGGER", format_trigger(reminder.minutes));
+    Some(alarm)
+}
+
+/// 開始の何分前かを "-PT10M"、"-P1DT2H" のようなDURATION値にします
+pub(crate) fn format_trigger(minutes: u32) -> String {
+    if minutes == 0 {
+        return "PT0S".to_string();
+    }
+    let (days, hours, mins) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    let mut value = "-P".to_string();
+    if days > 0 {
+        value.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || mins > 0 {
+        value.push('T');
+        if hours > 0 {
+            value.push_str(&format!("{}H", hours));
+        }
+        if mins > 0 {
+            value.push_str(&format!("{}M", mins));
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reminders::Reminders;
+    use chrono::TimeZone;
+
+    fn exporter() -> IcalExporter {
+        IcalExporter::new()
+            .with_calendar_name("チーム")
+            .with_dtstamp(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+    }
+
+    fn unfolded(ics: &str) -> Vec<String> {
+        ics.replace("\r\n ", "")
+            .split("\r\n")
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_export_timed_recurring_event() {
+        let mut event = Event {
+            id: Some("abcde12345".to_string()),
+            summary: Some("定例, 週次".to_string()),
+            location: Some("会議室A".to_string()),
+            start: Some(EventDateTime {
+                date_time: "2024-01-15T10:00:00+09:00".to_string(),
+                date: None,
+                time_zone: "America/New_York".to_string(),
+            }),
+            end: Some(EventDateTime {
+                date_time: "2024-01-15T11:00:00+09:00".to_string(),
+                date: None,
+                time_zone: "America/New_York".to_string(),
+            }),
+            recurrence: vec![
+                "RRULE:FREQ=WEEKLY;BYDAY=SU".to_string(),
+                "EXDATE;TZID=America/New_York:20240121T200000".to_string(),
+            ],
+            attendees: vec![
+                Attendee::new("alice@example.com").with_display_name("Alice"),
+                Attendee {
+                    response_status: Some(ResponseStatus::Accepted),
+                    ..Attendee::new("bob@example.com").optional()
+                },
+            ],
+            reminders: Some(Reminders::with_overrides(vec![
+                Reminder::popup(10),
+                Reminder::email(1500),
+            ])),
+            ..Default::default()
+        };
+        event
+            .extra
+            .insert("transparency".to_string(), serde_json::json!("transparent"));
+
+        let lines = unfolded(&exporter().export(&[event]).unwrap());
+        let expected = [
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "X-WR-CALNAME:チーム",
+            "BEGIN:VTIMEZONE",
+            "TZID:America/New_York",
+            "BEGIN:VEVENT",
+            "UID:abcde12345@google.com",
+            "DTSTAMP:20240101T000000Z",
+            "DTSTART;TZID=America/New_York:20240114T200000",
+            "DTEND;TZID=America/New_York:20240114T210000",
+            "RRULE:FREQ=WEEKLY;BYDAY=SU",
+            "EXDATE;TZID=America/New_York:20240121T200000",
+            "SUMMARY:定例\\, 週次",
+            "TRANSP:TRANSPARENT",
+            "ATTENDEE;CN=Alice;ROLE=REQ-PARTICIPANT:mailto:alice@example.com",
+            "ATTENDEE;ROLE=OPT-PARTICIPANT;PARTSTAT=ACCEPTED:mailto:bob@example.com",
+            "BEGIN:VALARM",
+            "ACTION:DISPLAY",
+            "TRIGGER:-PT10M",
+            "ACTION:EMAIL",
+            "TRIGGER:-P1DT1H",
+            "END:VEVENT",
+            "END:VCALENDAR",
+        ];
+        let mut position = 0;
+        for line in expected {
+            let found = lines[position..]
+                .iter()
+                .position(|l| l == line)
+                .unwrap_or_else(|| panic!("{} が見つかりません: {:#?}", line, lines));
+            position += found + 1;
+        }
+        // 繰り返しイベントは10年分の切り替えを含む
+        let daylight = lines.iter().filter(|l| *l == "BEGIN:DAYLIGHT").count();
+        assert_eq!(daylight, RECURRING_TIMEZONE_YEARS as usize + 1);
+    }
+
+    #[test]
+    fn test_export_all_day_and_utc_events() {
+        let all_day = Event::builder("休日")
+            .all_day(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+            .build()
+            .unwrap();
+        let utc = Event {
+            summary: Some("UTC".to_string()),
+            start: Some(EventDateTime {
+                date_time: "2024-01-15T10:00:00Z".to_string(),
+                date: None,
+                time_zone: "UTC".to_string(),
+            }),
+            end: Some(EventDateTime {
+                date_time: "2024-01-15T11:00:00Z".to_string(),
+                date: None,
+                time_zone: String::new(),
+            }),
+            reminders: Some(Reminders::use_default()),
+            ..Default::default()
+        };
+        let exporter = exporter().with_default_reminders(vec![Reminder::popup(30)]);
+        let calendar = exporter.to_calendar(&[all_day, utc]).unwrap();
+        assert!(calendar.components_named("VTIMEZONE").next().is_none());
+
+        let events: Vec<&Component> = calendar.components_named("VEVENT").collect();
+        let start = events[0].property("DTSTART").unwrap();
+        assert_eq!(start.param("VALUE"), Some("DATE"));
+        assert_eq!(start.value, "20240115");
+        assert_eq!(events[0].property("DTEND").unwrap().value, "20240116");
+        assert_eq!(
+            events[1].property("DTSTART").unwrap().value,
+            "20240115T100000Z"
+        );
+        let alarm = events[1].components_named("VALARM").next().unwrap();
+        assert_eq!(alarm.property("TRIGGER").unwrap().value, "-PT30M");
+    }
+
+    #[test]
+    fn test_invalid_event() {
+        let event = Event {
+            summary: Some("不正".to_string()),
+            start: Some(EventDateTime {
+                date_time: "not a date".to_string(),
+                date: None,
+                time_zone: "Asia/Tokyo".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(matches!(
+            exporter().export(&[event]),
+            Err(IcalError::InvalidEvent(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_trigger() {
+        assert_eq!(format_trigger(0), "PT0S");
+        assert_eq!(format_trigger(10), "-PT10M");
+        assert_eq!(format_trigger(60), "-PT1H");
+        assert_eq!(format_trigger(2880), "-P2D");
+        assert_eq!(format_trigger(1441), "-P1DT1M");
+    }
+}
@@ -0,0 +1,698 @@
+use super::parse::parse_components_lenient;
+use super::timezone::{parse_local, resolve_iana, EmbeddedTimezone};
+use super::{Component, IcalError, Property};
+use crate::attendee::{Attendee, Organizer, ResponseStatus};
+use crate::event::{Event, EventDateTime};
+use crate::reminders::{Reminder, Reminders, MAX_REMINDER_MINUTES, MAX_REMINDER_OVERRIDES};
+use chrono::{Duration, NaiveDate, NaiveDateTime, SecondsFormat};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+/// iCalendar の VEVENT を `Event` に変換するインポーター
+#[derive(Debug, Clone, Default)]
+pub struct IcalImporter {
+    default_time_zone: Option<Tz>,
+}
+
+/// 変換結果。変換できなかった VEVENT は `failures` に含まれます
+#[derive(Debug, Clone, Default)]
+pub struct IcalConversion {
+    pub events: Vec<Event>,
+    pub failures: Vec<ImportFailure>,
+    /// `events` の各イベントの VEVENT の位置
+    pub(crate) indices: Vec<usize>,
+}
+
+/// 変換またはインポートできなかった VEVENT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportFailure {
+    /// VCALENDAR 内での VEVENT の位置（0始まり）
+    pub index: usize,
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub message: String,
+}
+
+/// `CalendarClient::import_ics` の結果
+#[derive(Debug, Clone, Default)]
+pub struct IcsImportReport {
+    /// インポートされたイベント（APIの応答）
+    pub imported: Vec<Event>,
+    pub failures: Vec<ImportFailure>,
+}
+
+/// TZID ごとのタイムゾーンの解決結果
+#[derive(Debug, Clone)]
+enum ResolvedZone {
+    Iana(Tz),
+    /// IANAタイムゾーンに対応付けられない埋め込みの VTIMEZONE
+    Embedded(EmbeddedTimezone),
+}
+
+impl IcalImporter {
+    pub fn new() -> Self {
+        IcalImporter::default()
+    }
+
+    /// TZID も UTC 指定もない日時（フローティング）に使うタイムゾーン
+    ///
+    /// 指定しない場合、フローティングの日時を含む VEVENT は変換に失敗します。
+    pub fn with_default_time_zone(mut self, tz: Tz) -> Self {
+        self.default_time_zone = Some(tz);
+        self
+    }
+
+    /// iCalendar テキストを解析し、含まれるすべての VEVENT を変換します
+    ///
+    /// テキスト自体を解析できない場合のみエラーを返し、個々の VEVENT の失敗は結果に含めます。
+    /// VEVENT 内の解析できない行は、その VEVENT の失敗として扱います。
+    pub fn convert(&self, ics: &str) -> Result<IcalConversion, IcalError> {
+        let components = parse_components_lenient(ics)?;
+        let mut conversion = IcalConversion::default();
+        let mut index = 0;
+        for calendar in components.iter().filter(|c| c.name == "VCALENDAR") {
+            let part = self.convert_calendar(calendar);
+            conversion.events.extend(part.events);
+            conversion
+                .indices
+                .extend(part.indices.into_iter().map(|i| i + index));
+            conversion
+                .failures
+                .extend(part.failures.into_iter().map(|mut failure| {
+                    failure.index += index;
+                    failure
+                }));
+            index += calendar.components_named("VEVENT").count();
+        }
+        Ok(conversion)
+    }
+
+    /// jCal の VCALENDAR に含まれる VEVENT を変換します
+    pub fn convert_jcal(&self, jcal: &serde_json::Value) -> Result<IcalConversion, IcalError> {
+        Ok(self.convert_calendar(&Component::from_jcal(jcal)?))
+    }
+
+    /// xCal の XML 文書に含まれる VEVENT を変換します
+    pub fn convert_xcal(&self, xml: &str) -> Result<IcalConversion, IcalError> {
+        Ok(self.convert_calendar(&Component::from_xcal(xml)?))
+    }
+
+    /// VCALENDAR コンポーネントに含まれる VEVENT を変換します
+    pub fn convert_calendar(&self, calendar: &Component) -> IcalConversion {
+        let zones = resolve_zones(calendar);
+        let mut conversion = IcalConversion::default();
+        for (index, vevent) in calendar.components_named("VEVENT").enumerate() {
+            match self.vevent_to_event(vevent, &zones) {
+                Ok(event) => {
+                    conversion.events.push(event);
+                    conversion.indices.push(index);
+                }
+                Err(message) => conversion.failures.push(ImportFailure {
+                    index,
+                    uid: vevent.property("UID").map(|p| p.text_value()),
+                    summary: vevent.property("SUMMARY").map(|p| p.text_value()),
+                    message,
+                }),
+            }
+        }
+        conversion
+    }
+
+    fn vevent_to_event(
+        &self,
+        vevent: &Component,
+        zones: &HashMap<String, Result<ResolvedZone, String>>,
+    ) -> Result<Event, String> {
+        if let Some(message) = first_parse_error(vevent) {
+            return Err(format!("解析できない行があります: {}", message));
+        }
+        if vevent.property("RECURRENCE-ID").is_some() {
+            return Err("繰り返しの例外（RECURRENCE-ID）のインポートには対応していません".into());
+        }
+        let uid = vevent
+            .property("UID")
+            .map(|p| p.text_value())
+            .filter(|uid| !uid.is_empty())
+            .ok_or("UID がありません")?;
+
+        let mut event = Event {
+            i_cal_uid: Some(uid),
+            summary: vevent.property("SUMMARY").map(|p| p.text_value()),
+            description: vevent.property("DESCRIPTION").map(|p| p.text_value()),
+            location: vevent.property("LOCATION").map(|p| p.text_value()),
+            status: vevent
+                .property("STATUS")
+                .map(|p| p.value.to_ascii_lowercase()),
+            ..Default::default()
+        };
+        if let Some(sequence) = vevent.property("SEQUENCE") {
+            event.sequence = Some(
+                sequence
+                    .value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("SEQUENCE が不正です: {}", sequence.value))?,
+            );
+        }
+
+        let start = vevent.property("DTSTART").ok_or("DTSTART がありません")?;
+        let start = self.date_time(start, zones)?;
+        let end = match (vevent.property("DTEND"), vevent.property("DURATION")) {
+            (Some(end), _) => self.date_time(end, zones)?,
+            (None, Some(duration)) => {
+                let duration = parse_duration(&duration.value)
+                    .ok_or_else(|| format!("DURATION が不正です: {}", duration.value))?;
+                start.add(duration)?
+            }
+            // RFC 5545: DATE は1日、DATE-TIME は開始と同時刻で終わる
+            (None, None) => match start {
+                IcalDateTime::Date(_) => start.add(Duration::days(1))?,
+                _ => start.clone(),
+            },
+        };
+
+        for name in ["RRULE", "RDATE", "EXDATE", "EXRULE"] {
+            for property in vevent.properties_named(name) {
+                event.recurrence.push(recurrence_line(property, zones)?);
+            }
+        }
+        if !event.recurrence.is_empty() && matches!(start, IcalDateTime::Utc(_, false)) {
+            return Err(
+                "繰り返しイベントの TZID をIANAタイムゾーンに対応付けられません".to_string(),
+            );
+        }
+        event.start = Some(start.to_event_date_time());
+        event.end = Some(end.to_event_date_time());
+
+        if let Some(class) = vevent.property("CLASS") {
+            let visibility = class.value.to_ascii_lowercase();
+            if matches!(visibility.as_str(), "public" | "private" | "confidential") {
+                event.extra.insert("visibility".into(), visibility.into());
+            }
+        }
+        if let Some(transp) = vevent.property("TRANSP") {
+            let transparency = transp.value.to_ascii_lowercase();
+            event
+                .extra
+                .insert("transparency".into(), transparency.into());
+        }
+        event.organizer = vevent.property("ORGANIZER").map(|organizer| Organizer {
+            display_name: organizer.param("CN").map(str::to_string),
+            ..Organizer::new(mailto(&organizer.value))
+        });
+        event.attendees = vevent
+            .properties_named("ATTENDEE")
+            .map(attendee_from_property)
+            .collect();
+
+        let alarms: Vec<Reminder> = vevent
+            .components_named("VALARM")
+            .filter_map(reminder_from_valarm)
+            .take(MAX_REMINDER_OVERRIDES)
+            .collect();
+        if !alarms.is_empty() {
+            event.reminders = Some(Reminders::with_overrides(alarms));
+        }
+
+        event.validate().map_err(|e| e.to_string())?;
+        Ok(event)
+    }
+
+    fn date_time(
+        &self,
+        property: &Property,
+        zones: &HashMap<String, Result<ResolvedZone, String>>,
+    ) -> Result<IcalDateTime, String> {
+        let value = property.value.trim();
+        let invalid = || format!("{} の値が不正です: {}", property.name, value);
+        if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+            return NaiveDate::parse_from_str(value, "%Y%m%d")
+                .map(IcalDateTime::Date)
+                .map_err(|_| invalid());
+        }
+        if let Some(utc) = value.strip_suffix('Z') {
+            return parse_local(utc)
+                .map(|dt| IcalDateTime::Utc(dt, true))
+                .ok_or_else(invalid);
+        }
+        let local = parse_local(value).ok_or_else(invalid)?;
+        match property.param("TZID") {
+            Some(tzid) => match lookup_zone(tzid, zones)? {
+                ResolvedZone::Iana(tz) => Ok(IcalDateTime::Zoned(local, tz)),
+                ResolvedZone::Embedded(zone) => {
+                    Ok(IcalDateTime::Utc(zone.to_utc(local).naive_utc(), false))
+                }
+            },
+            None => self
+                .default_time_zone
+                .map(|tz| IcalDateTime::Zoned(local, tz))
+                .ok_or_else(|| {
+                    format!(
+                        "{} がフローティング時刻です。デフォルトのタイムゾーンを指定してください",
+                        property.name
+                    )
+                }),
+        }
+    }
+}
+
+/// VEVENT の DTSTART / DTEND の値
+#[derive(Debug, Clone)]
+enum IcalDateTime {
+    Date(NaiveDate),
+    /// UTCの日時。値が UTC（Z）で指定されていた場合は true、埋め込みの VTIMEZONE から変換した場合は false
+    Utc(NaiveDateTime, bool),
+    Zoned(NaiveDateTime, Tz),
+}
+
+impl IcalDateTime {
+    fn add(&self, duration: Duration) -> Result<IcalDateTime, String> {
+        let overflow = || "日時が範囲外です".to_string();
+        Ok(match *self {
+            IcalDateTime::Date(date) => {
+                IcalDateTime::Date(date.checked_add_signed(duration).ok_or_else(overflow)?)
+            }
+            IcalDateTime::Utc(dt, iana) => {
+                IcalDateTime::Utc(dt.checked_add_signed(duration).ok_or_else(overflow)?, iana)
+            }
+            IcalDateTime::Zoned(dt, tz) => {
+                IcalDateTime::Zoned(dt.checked_add_signed(duration).ok_or_else(overflow)?, tz)
+            }
+        })
+    }
+
+    fn to_event_date_time(&self) -> EventDateTime {
+        match self {
+            IcalDateTime::Date(date) => EventDateTime::from_date(*date),
+            IcalDateTime::Utc(dt, _) => EventDateTime {
+                date_time: dt.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true),
+                date: None,
+                time_zone: "UTC".to_string(),
+            },
+            IcalDateTime::Zoned(dt, tz) => EventDateTime {
+                date_time: dt.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                date: None,
+                time_zone: tz.name().to_string(),
+            },
+        }
+    }
+}
+
+/// VCALENDAR 内の VTIMEZONE を TZID ごとに解決します
+fn resolve_zones(calendar: &Component) -> HashMap<String, Result<ResolvedZone, String>> {
+    calendar
+        .components_named("VTIMEZONE")
+        .filter_map(|vtimezone| {
+            let tzid = vtimezone.property("TZID")?.value.clone();
+            let zone = match resolve_iana(&tzid, Some(vtimezone)) {
+                Some(tz) => Ok(ResolvedZone::Iana(tz)),
+                None => EmbeddedTimezone::from_component(vtimezone)
+                    .map(ResolvedZone::Embedded)
+                    .map_err(|e| format!("TZID {}: {}", tzid, e)),
+            };
+            Some((tzid, zone))
+        })
+        .collect()
+}
+
+fn lookup_zone(
+    tzid: &str,
+    zones: &HashMap<String, Result<ResolvedZone, String>>,
+) -> Result<ResolvedZone, String> {
+    match zones.get(tzid) {
+        Some(zone) => zone.clone(),
+        None => resolve_iana(tzid, None)
+            .map(ResolvedZone::Iana)
+            .ok_or_else(|| format!("不明な TZID です: {}", tzid)),
+    }
+}
+
+/// 繰り返しのプロパティを Google Calendar の recurrence の行に変換します
+///
+/// TZID はIANA名に置き換え、埋め込みの VTIMEZONE にしかない TZID はUTCの値に変換します。
+fn recurrence_line(
+    property: &Property,
+    zones: &HashMap<String, Result<ResolvedZone, String>>,
+) -> Result<String, String> {
+    let Some(tzid) = property.param("TZID") else {
+        return Ok(property.to_content_line());
+    };
+    let mut line = Property::new(&property.name, property.value.clone());
+    line.params = property
+        .params
+        .iter()
+        .filter(|(name, _)| name != "TZID")
+        .cloned()
+        .collect();
+    match lookup_zone(tzid, zones)? {
+        ResolvedZone::Iana(tz) => line = line.with_param("TZID", tz.name()),
+        ResolvedZone::Embedded(zone) => {
+            line.value = property
+                .value
+                .split(',')
+                .map(|value| {
+                    parse_local(value)
+                        .map(|local| zone.to_utc(local).format("%Y%m%dT%H%M%SZ").to_string())
+                        .ok_or_else(|| format!("{} の値が不正です: {}", property.name, value))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .join(",");
+        }
+    }
+    Ok(line.to_content_line())
+}
+
+fn mailto(value: &str) -> String {
+    let value = value.trim();
+    match value.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => value[7..].to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn attendee_from_property(property: &Property) -> Attendee {
+    let mut attendee = Attendee::new(mailto(&property.value));
+    attendee.display_name = property.param("CN").map(str::to_string);
+    if property.param("ROLE") == Some("OPT-PARTICIPANT") {
+        attendee.optional = Some(true);
+    }
+    if matches!(property.param("CUTYPE"), Some("RESOURCE" | "ROOM")) {
+        attendee.resource = Some(true);
+    }
+    attendee.response_status = property.param("PARTSTAT").and_then(|partstat| {
+        match partstat.to_ascii_uppercase().as_str() {
+            "NEEDS-ACTION" => Some(ResponseStatus::NeedsAction),
+            "ACCEPTED" => Some(ResponseStatus::Accepted),
+            "DECLINED" => Some(ResponseStatus::Declined),
+            "TENTATIVE" => Some(ResponseStatus::Tentative),
+            _ => None,
+        }
+    });
+    attendee
+}
+
+/// VALARM をリマインダーに変換します
+///
+/// Google Calendar で表現できるのは開始前（または開始時）の相対的な通知のみのため、
+/// 絶対時刻・終了基準・開始後の TRIGGER は無視します。
+fn reminder_from_valarm(alarm: &Component) -> Option<Reminder> {
+    let trigger = alarm.property("TRIGGER")?;
+    if trigger.param("VALUE") == Some("DATE-TIME") || trigger.param("RELATED") == Some("END") {
+        return None;
+    }
+    let before = -parse_duration(&trigger.value)?;
+    if before < Duration::zero() {
+        return None;
+    }
+    let minutes = u32::try_from(before.num_minutes())
+        .ok()
+        .filter(|m| *m <= MAX_REMINDER_MINUTES)?;
+    match alarm
+        .property("ACTION")?
+        .value
+        .to_ascii_uppercase()
+        .as_str()
+    {
+        "DISPLAY" | "AUDIO" => Some(Reminder::popup(minutes)),
+        "EMAIL" => Some(Reminder::email(minutes)),
+        _ => None,
+    }
+}
+
+/// "-P1DT2H30M"、"PT15M"、"P1W" のようなDURATION値を解析します
+pub(crate) fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, rest) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let rest = rest.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut has_unit = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                has_unit = true;
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    if !number.is_empty() || !has_unit {
+        return None;
+    }
+    Some(if negative { -total } else { total })
+}
+
+/// コンポーネントとその子コンポーネントに記録された最初の解析エラー
+fn first_parse_error(component: &Component) -> Option<String> {
+    component
+        .parse_errors
+        .first()
+        .cloned()
+        .or_else(|| component.components.iter().find_map(first_parse_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ical::IcalExporter;
+    use crate::reminders::ReminderMethod;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//EN\r\n\
+BEGIN:VTIMEZONE\r\n\
+TZID:Tokyo Standard Time\r\n\
+BEGIN:STANDARD\r\n\
+DTSTART:16010101T000000\r\n\
+TZOFFSETFROM:+0900\r\n\
+TZOFFSETTO:+0900\r\n\
+END:STANDARD\r\n\
+END:VTIMEZONE\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-1@example.com\r\n\
+SUMMARY:定例会議\\, 第1週\r\n\
+DTSTART;TZID=/mozilla.org/20070129_1/America/New_York:20240115T100000\r\n\
+DURATION:PT30M\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+EXDATE;TZID=/mozilla.org/20070129_1/America/New_York:20240122T100000\r\n\
+ORGANIZER;CN=Alice:mailto:alice@example.com\r\n\
+ATTENDEE;CN=\"Smith, Bob\";ROLE=OPT-PARTICIPANT;PARTSTAT=ACCEPTED:mailto:bob@example.com\r\n\
+ATTENDEE;CUTYPE=RESOURCE:mailto:room@example.com\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+TRIGGER:-PT15M\r\n\
+END:VALARM\r\n\
+BEGIN:VALARM\r\n\
+ACTION:EMAIL\r\n\
+TRIGGER;RELATED=START:-P1D\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:holiday@example.com\r\n\
+SUMMARY:休日\r\n\
+DTSTART;VALUE=DATE:20240211\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:windows@example.com\r\n\
+SUMMARY:外部会議\r\n\
+DTSTART;TZID=Tokyo Standard Time:20240115T100000\r\n\
+DTEND;TZID=Tokyo Standard Time:20240115T110000\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:UIDなし\r\n\
+DTSTART:20240115T010000Z\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:floating@example.com\r\n\
+SUMMARY:フローティング\r\n\
+DTSTART:20240115T100000\r\n\
+DTEND:20240115T110000\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_convert_events() {
+        let conversion = IcalImporter::new().convert(ICS).unwrap();
+        assert_eq!(conversion.events.len(), 3);
+
+        let weekly = &conversion.events[0];
+        assert_eq!(weekly.i_cal_uid.as_deref(), Some("weekly-1@example.com"));
+        assert_eq!(weekly.summary.as_deref(), Some("定例会議, 第1週"));
+        let start = weekly.start.as_ref().unwrap();
+        assert_eq!(start.date_time, "2024-01-15T10:00:00");
+        assert_eq!(start.time_zone, "America/New_York");
+        assert_eq!(
+            weekly.end.as_ref().unwrap().date_time,
+            "2024-01-15T10:30:00"
+        );
+        assert_eq!(
+            weekly.recurrence,
+            vec![
+                "RRULE:FREQ=WEEKLY;BYDAY=MO",
+                "EXDATE;TZID=America/New_York:20240122T100000"
+            ]
+        );
+        let organizer = weekly.organizer.as_ref().unwrap();
+        assert_eq!(organizer.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(organizer.display_name.as_deref(), Some("Alice"));
+        assert_eq!(weekly.attendees[0].email, "bob@example.com");
+        assert_eq!(
+            weekly.attendees[0].display_name.as_deref(),
+            Some("Smith, Bob")
+        );
+        assert_eq!(weekly.attendees[0].optional, Some(true));
+        assert_eq!(
+            weekly.attendees[0].response_status,
+            Some(ResponseStatus::Accepted)
+        );
+        assert_eq!(weekly.attendees[1].resource, Some(true));
+        let reminders = weekly.reminders.as_ref().unwrap();
+        assert!(!reminders.use_default);
+        assert_eq!(reminders.overrides[0], Reminder::popup(15));
+        assert_eq!(reminders.overrides[1].method, ReminderMethod::Email);
+        assert_eq!(reminders.overrides[1].minutes, 1440);
+
+        let holiday = &conversion.events[1];
+        assert_eq!(
+            holiday.start.as_ref().unwrap().date.as_deref(),
+            Some("2024-02-11")
+        );
+        assert_eq!(
+            holiday.end.as_ref().unwrap().date.as_deref(),
+            Some("2024-02-12")
+        );
+
+        // IANAに対応付けられない TZID は埋め込みの定義でUTCに変換する
+        let windows = &conversion.events[2];
+        assert_eq!(
+            windows.start.as_ref().unwrap().date_time,
+            "2024-01-15T01:00:00Z"
+        );
+        assert_eq!(windows.start.as_ref().unwrap().time_zone, "UTC");
+    }
+
+    #[test]
+    fn test_convert_failures() {
+        let conversion = IcalImporter::new().convert(ICS).unwrap();
+        assert_eq!(conversion.failures.len(), 2);
+        assert_eq!(conversion.failures[0].index, 3);
+        assert_eq!(conversion.failures[0].uid, None);
+        assert_eq!(conversion.failures[0].summary.as_deref(), Some("UIDなし"));
+        assert_eq!(
+            conversion.failures[1].uid.as_deref(),
+            Some("floating@example.com")
+        );
+
+        // デフォルトのタイムゾーンを指定するとフローティング時刻も変換できる
+        let conversion = IcalImporter::new()
+            .with_default_time_zone(chrono_tz::Asia::Tokyo)
+            .convert(ICS)
+            .unwrap();
+        assert_eq!(conversion.events.len(), 4);
+        assert_eq!(
+            conversion.events[3].start.as_ref().unwrap().time_zone,
+            "Asia/Tokyo"
+        );
+
+        assert!(IcalImporter::new().convert("BEGIN:VCALENDAR\r\n").is_err());
+    }
+
+    #[test]
+    fn test_convert_reports_unparsable_lines_per_event() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:ok@example.com\r\n\
+            SUMMARY:定例\r\n\
+            DTSTART:20240115T010000Z\r\n\
+            DTEND:20240115T020000Z\r\n\
+            ATTENDEE;MEMBER=\"mailto:a@example.com\",\"mailto:b@example.com\";CN=X:mailto:c@example.com\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:broken@example.com\r\n\
+            SUMMARY:壊れた行\r\n\
+            DTSTART:20240115T010000Z\r\n\
+            DTEND:20240115T020000Z\r\n\
+            ATTENDEE;CN=\"unterminated:mailto:d@example.com\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+        let conversion = IcalImporter::new().convert(ics).unwrap();
+        assert_eq!(conversion.events.len(), 1);
+        assert_eq!(conversion.events[0].attendees[0].email, "c@example.com");
+        assert_eq!(conversion.failures.len(), 1);
+        assert_eq!(conversion.failures[0].index, 1);
+        assert_eq!(
+            conversion.failures[0].uid.as_deref(),
+            Some("broken@example.com")
+        );
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let conversion = IcalImporter::new().convert(ICS).unwrap();
+        let ics = IcalExporter::new().export(&conversion.events).unwrap();
+        let round_trip = IcalImporter::new().convert(&ics).unwrap();
+        assert!(round_trip.failures.is_empty(), "{:?}", round_trip.failures);
+        for (before, after) in conversion.events.iter().zip(&round_trip.events) {
+            assert_eq!(before.i_cal_uid, after.i_cal_uid);
+            assert_eq!(before.interval().unwrap(), after.interval().unwrap());
+            assert_eq!(before.recurrence, after.recurrence);
+            assert_eq!(before.organizer, after.organizer);
+            assert_eq!(before.attendees.len(), after.attendees.len());
+        }
+    }
+
+    #[test]
+    fn test_jcal_and_xcal_round_trip() {
+        let conversion = IcalImporter::new().convert(ICS).unwrap();
+        let exporter = IcalExporter::new();
+        let calendar = exporter.to_calendar(&conversion.events).unwrap();
+
+        let jcal = exporter.export_jcal(&conversion.events).unwrap();
+        assert_eq!(Component::from_jcal(&jcal).unwrap(), calendar);
+        let xcal = exporter.export_xcal(&conversion.events).unwrap();
+        assert_eq!(Component::from_xcal(&xcal).unwrap(), calendar);
+
+        for round_trip in [
+            IcalImporter::new().convert_jcal(&jcal).unwrap(),
+            IcalImporter::new().convert_xcal(&xcal).unwrap(),
+        ] {
+            assert!(round_trip.failures.is_empty(), "{:?}", round_trip.failures);
+            assert_eq!(round_trip.events.len(), conversion.events.len());
+            for (before, after) in conversion.events.iter().zip(&round_trip.events) {
+                assert_eq!(before.i_cal_uid, after.i_cal_uid);
+                assert_eq!(before.interval().unwrap(), after.interval().unwrap());
+                assert_eq!(before.recurrence, after.recurrence);
+                assert_eq!(before.reminders, after.reminders);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT15M"), Some(Duration::minutes(15)));
+        assert_eq!(
+            parse_duration("-P1DT2H30M"),
+            Some(-(Duration::days(1) + Duration::minutes(150)))
+        );
+        assert_eq!(parse_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("PT0S"), Some(Duration::zero()));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("PT15"), None);
+        assert_eq!(parse_duration("P1H"), None);
+    }
+}
@@ -0,0 +1,282 @@
+use super::value::{
+    is_integer_recur_part, property_from_typed, typed_values, TypedValue, ValueType,
+};
+use super::{Component, IcalError, Property};
+use serde_json::{json, Map, Value};
+
+impl Component {
+    /// jCal（RFC 7265）の配列 `["vcalendar", [プロパティ], [コンポーネント]]` に変換します
+    pub fn to_jcal(&self) -> Value {
+        json!([
+            self.name.to_ascii_lowercase(),
+            self.properties
+                .iter()
+                .map(property_to_jcal)
+                .collect::<Vec<_>>(),
+            self.components
+                .iter()
+                .map(Component::to_jcal)
+                .collect::<Vec<_>>(),
+        ])
+    }
+
+    /// jCal の配列からコンポーネントを作成します
+    pub fn from_jcal(value: &Value) -> Result<Component, IcalError> {
+        let invalid = |message: &str| IcalError::Jcal(format!("{}: {}", message, value));
+        let [name, properties, components] = value
+            .as_array()
+            .map(Vec::as_slice)
+            .and_then(|items| <&[Value; 3]>::try_from(items).ok())
+            .ok_or_else(|| invalid("コンポーネントは3要素の配列である必要があります"))?;
+        let name = name
+            .as_str()
+            .ok_or_else(|| invalid("コンポーネント名が文字列ではありません"))?;
+
+        let mut component = Component::new(name);
+        for property in properties
+            .as_array()
+            .ok_or_else(|| invalid("プロパティが配列ではありません"))?
+        {
+            component.push(property_from_jcal(property)?);
+        }
+        for child in components
+            .as_array()
+            .ok_or_else(|| invalid("子コンポーネントが配列ではありません"))?
+        {
+            component.components.push(Component::from_jcal(child)?);
+        }
+        Ok(component)
+    }
+}
+
+/// `["dtstart", {"tzid": "Asia/Tokyo"}, "date-time", "2024-01-15T10:00:00"]` の形式に変換します
+fn property_to_jcal(property: &Property) -> Value {
+    let (ty, values) = typed_values(property);
+    let mut params = Map::new();
+    for (name, value) in &property.params {
+        if ty != ValueType::Unknown && name == "VALUE" {
+            continue;
+        }
+        params.insert(name.to_ascii_lowercase(), Value::String(value.clone()));
+    }
+    let mut item = vec![
+        Value::String(property.name.to_ascii_lowercase()),
+        Value::Object(params),
+        Value::String(ty.as_str().to_string()),
+    ];
+    item.extend(values.into_iter().map(value_to_jcal));
+    Value::Array(item)
+}
+
+fn value_to_jcal(value: TypedValue) -> Value {
+    match value {
+        TypedValue::String(s) => Value::String(s),
+        TypedValue::Integer(n) => json!(n),
+        TypedValue::Float(f) => json!(f),
+        TypedValue::Boolean(b) => json!(b),
+        TypedValue::Period(start, end) => Value::String(format!("{}/{}", start, end)),
+        TypedValue::Geo(lat, lon) => json!([lat, lon]),
+        TypedValue::Recur(parts) => {
+            let mut object = Map::new();
+            for (key, values) in parts {
+                let mut values: Vec<Value> = values
+                    .into_iter()
+                    .map(|v| match v.parse::<i64>() {
+                        Ok(n) if is_integer_recur_part(&key) => json!(n),
+                        _ => Value::String(v),
+                    })
+                    .collect();
+                let value = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    Value::Array(values)
+                };
+                object.insert(key, value);
+            }
+            Value::Object(object)
+        }
+    }
+}
+
+fn property_from_jcal(value: &Value) -> Result<Property, IcalError> {
+    let invalid = |message: &str| IcalError::Jcal(format!("{}: {}", message, value));
+    let items = value
+        .as_array()
+        .filter(|items| items.len() >= 4)
+        .ok_or_else(|| invalid("プロパティは4要素以上の配列である必要があります"))?;
+    let name = items[0]
+        .as_str()
+        .ok_or_else(|| invalid("プロパティ名が文字列ではありません"))?;
+    let params = items[1]
+        .as_object()
+        .ok_or_else(|| invalid("パラメータがオブジェクトではありません"))?
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                // 複数の値を持つパラメータ
+                Value::Array(values) => values
+                    .iter()
+                    .map(|v| {
+                        v.as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| v.to_string())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+                other => other.to_string(),
+            };
+            (name.to_ascii_uppercase(), value)
+        })
+        .collect();
+    let ty = items[2]
+        .as_str()
+        .and_then(ValueType::parse)
+        .ok_or_else(|| invalid("値の型が不正です"))?;
+    let values = items[3..]
+        .iter()
+        .map(|v| value_from_jcal(ty, v))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| invalid("値が型と一致しません"))?;
+    property_from_typed(name, params, ty, &values).map_err(IcalError::Jcal)
+}
+
+fn value_from_jcal(ty: ValueType, value: &Value) -> Option<TypedValue> {
+    Some(match (ty, value) {
+        (ValueType::Integer, Value::Number(n)) => TypedValue::Integer(n.as_i64()?),
+        (ValueType::Float, Value::Number(n)) => TypedValue::Float(n.as_f64()?),
+        (ValueType::Float, Value::Array(pair)) if pair.len() == 2 => {
+            TypedValue::Geo(pair[0].as_f64()?, pair[1].as_f64()?)
+        }
+        (ValueType::Boolean, Value::Bool(b)) => TypedValue::Boolean(*b),
+        (ValueType::Period, Value::String(s)) => {
+            let (start, end) = s.split_once('/')?;
+            TypedValue::Period(start.to_string(), end.to_string())
+        }
+        (ValueType::Recur, Value::Object(object)) => TypedValue::Recur(
+            object
+                .iter()
+                .map(|(key, value)| {
+                    let values = match value {
+                        Value::Array(values) => values.iter().map(recur_value).collect(),
+                        value => Some(vec![recur_value(value)?]),
+                    }?;
+                    Some((key.to_ascii_lowercase(), values))
+                })
+                .collect::<Option<_>>()?,
+        ),
+        (_, Value::String(s)) => TypedValue::String(s.clone()),
+        _ => return None,
+    })
+}
+
+fn recur_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_calendar() -> Component {
+        Component::parse(
+            "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//JA\r\n\
+X-WR-CALNAME:チーム\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-1@example.com\r\n\
+DTSTAMP:20240101T000000Z\r\n\
+DTSTART;TZID=Asia/Tokyo:20240115T100000\r\n\
+DTEND;TZID=Asia/Tokyo:20240115T110000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20241231T000000Z\r\n\
+EXDATE;TZID=Asia/Tokyo:20240117T100000,20240122T100000\r\n\
+SUMMARY:定例会議\\, 第1週\r\n\
+GEO:35.681236;139.767125\r\n\
+ATTENDEE;CN=\"Smith, Bob\";PARTSTAT=ACCEPTED:mailto:bob@example.com\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+TRIGGER:-PT15M\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:holiday@example.com\r\n\
+DTSTART;VALUE=DATE:20240211\r\n\
+SEQUENCE:2\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_jcal() {
+        let jcal = sample_calendar().to_jcal();
+        assert_eq!(jcal[0], "vcalendar");
+        assert_eq!(jcal[1][2], json!(["x-wr-calname", {}, "unknown", "チーム"]));
+
+        let event = &jcal[2][0];
+        assert_eq!(event[0], "vevent");
+        let properties = event[1].as_array().unwrap();
+        assert!(properties.contains(&json!([
+            "dtstart",
+            {"tzid": "Asia/Tokyo"},
+            "date-time",
+            "2024-01-15T10:00:00"
+        ])));
+        assert!(properties.contains(&json!([
+            "rrule",
+            {},
+            "recur",
+            {"freq": "WEEKLY", "byday": ["MO", "WE"], "until": "2024-12-31T00:00:00Z"}
+        ])));
+        assert!(properties.contains(&json!([
+            "exdate",
+            {"tzid": "Asia/Tokyo"},
+            "date-time",
+            "2024-01-17T10:00:00",
+            "2024-01-22T10:00:00"
+        ])));
+        assert!(properties.contains(&json!(["summary", {}, "text", "定例会議, 第1週"])));
+        assert!(properties.contains(&json!(["geo", {}, "float", [35.681236, 139.767125]])));
+        assert_eq!(
+            event[2][0][1][1],
+            json!(["trigger", {}, "duration", "-PT15M"])
+        );
+
+        let holiday = jcal[2][1][1].as_array().unwrap();
+        assert!(holiday.contains(&json!(["dtstart", {}, "date", "2024-02-11"])));
+        assert!(holiday.contains(&json!(["sequence", {}, "integer", 2])));
+    }
+
+    #[test]
+    fn test_jcal_round_trip() {
+        let calendar = sample_calendar();
+        let jcal = calendar.to_jcal();
+        assert_eq!(Component::from_jcal(&jcal).unwrap(), calendar);
+
+        // JSON テキストを経由しても変わらない
+        let text = serde_json::to_string(&jcal).unwrap();
+        let parsed: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(Component::from_jcal(&parsed).unwrap(), calendar);
+    }
+
+    #[test]
+    fn test_from_jcal_errors() {
+        assert!(Component::from_jcal(&json!(["vcalendar", []])).is_err());
+        assert!(
+            Component::from_jcal(&json!(["vcalendar", [["summary", {}, "text"]], []])).is_err()
+        );
+        assert!(Component::from_jcal(&json!([
+            "vcalendar",
+            [["sequence", {}, "integer", "two"]],
+            []
+        ]))
+        .is_err());
+        assert!(Component::from_jcal(&json!(["vcalendar", [["x", {}, "nope", "a"]], []])).is_err());
+    }
+}
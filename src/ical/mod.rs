@@ -0,0 +1,397 @@
+mod export;
+mod import;
+mod jcal;
+mod parse;
+mod timezone;
+mod value;
+mod xcal;
+
+pub use export::IcalExporter;
+pub use import::{IcalConversion, IcalImporter, IcsImportReport, ImportFailure};
+pub use parse::parse_components;
+pub use xcal::XCAL_NAMESPACE;
+
+use thiserror::Error;
+
+/// 1行あたりの最大オクテット数（改行を除く）
+pub const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Error, Debug)]
+pub enum IcalError {
+    #[error("イベントを変換できません: {0}")]
+    InvalidEvent(String),
+    #[error("iCalendar を解析できません（{line}行目）: {message}")]
+    Parse { line: usize, message: String },
+    #[error("jCal を解析できません: {0}")]
+    Jcal(String),
+    #[error("xCal を解析できません: {0}")]
+    Xcal(String),
+}
+
+/// BEGIN と END で囲まれたコンポーネント（VCALENDAR、VEVENT、VALARM など）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Component {
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub components: Vec<Component>,
+    /// 解析できなかった行（インポート時に、このコンポーネントの変換を失敗させます）
+    pub(crate) parse_errors: Vec<String>,
+}
+
+impl Component {
+    pub fn new(name: impl Into<String>) -> Self {
+        Component {
+            name: name.into().to_ascii_uppercase(),
+            properties: Vec::new(),
+            components: Vec::new(),
+            parse_errors: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, property: Property) {
+        self.properties.push(property);
+    }
+
+    /// 値をエスケープせずにそのまま持つプロパティを追加します
+    pub fn add(&mut self, name: &str, value: impl Into<String>) {
+        self.push(Property::new(name, value));
+    }
+
+    /// TEXT型の値をエスケープしてプロパティを追加します
+    pub fn add_text(&mut self, name: &str, value: &str) {
+        self.push(Property::text(name, value));
+    }
+
+    /// 指定した名前の最初のプロパティ
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 指定した名前のプロパティをすべて返します
+    pub fn properties_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties
+            .iter()
+            .filter(move |p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 指定した名前の子コンポーネントをすべて返します
+    pub fn components_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Component> {
+        self.components
+            .iter()
+            .filter(move |c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 折り返し済みの iCalendar テキスト（改行は CRLF）に変換します
+    pub fn to_ics(&self) -> String {
+        let mut out = String::new();
+        self.write_to(&mut out);
+        out
+    }
+
+    fn write_to(&self, out: &mut String) {
+        write_folded(out, &format!("BEGIN:{}", self.name));
+        for property in &self.properties {
+            write_folded(out, &property.to_content_line());
+        }
+        for component in &self.components {
+            component.write_to(out);
+        }
+        write_folded(out, &format!("END:{}", self.name));
+    }
+}
+
+/// コンポーネントのプロパティ（1つのコンテンツ行）
+///
+/// `value` は iCalendar 上の表現のまま（TEXT型ならエスケープ済み）で保持します。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    pub fn new(name: &str, value: impl Into<String>) -> Self {
+        Property {
+            name: name.to_ascii_uppercase(),
+            params: Vec::new(),
+            value: value.into(),
+        }
+    }
+
+    /// TEXT型の値をエスケープして作成します
+    pub fn text(name: &str, value: &str) -> Self {
+        Property::new(name, escape_text(value))
+    }
+
+    pub fn with_param(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.params.push((name.to_ascii_uppercase(), value.into()));
+        self
+    }
+
+    /// 指定した名前のパラメータの値
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 折り返しを解除した1行（"NAME;PARAM=VALUE:value"）を解析します
+    ///
+    /// パラメータ値はカンマ区切りの複数値（`MEMBER="mailto:a@x","mailto:b@x"`）を受け付け、
+    /// カンマで連結して保持します。値の中の RFC 6868 のエスケープ（`^'`、`^^`、`^n`）は解除します。
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut chars = line.char_indices().peekable();
+        let mut name_end = line.len();
+        while let Some(&(i, c)) = chars.peek() {
+            if c == ';' || c == ':' {
+                name_end = i;
+                break;
+            }
+            chars.next();
+        }
+        let name = &line[..name_end];
+        if name.is_empty() {
+            return Err(format!("プロパティ名がありません: {}", line));
+        }
+
+        let mut property = Property::new(name, "");
+        // パラメータを読み、値の開始位置（':' の次）を求める
+        let mut rest = &line[name_end..];
+        loop {
+            match rest.chars().next() {
+                Some(':') => {
+                    property.value = rest[1..].to_string();
+                    return Ok(property);
+                }
+                Some(';') => {
+                    let eq = rest
+                        .find('=')
+                        .ok_or_else(|| format!("パラメータの形式が正しくありません: {}", line))?;
+                    let param_name = &rest[1..eq];
+                    rest = &rest[eq + 1..];
+                    let mut values = Vec::new();
+                    loop {
+                        let (value, remaining) = if let Some(quoted) = rest.strip_prefix('"') {
+                            let close = quoted
+                                .find('"')
+                                .ok_or_else(|| format!("引用符が閉じられていません: {}", line))?;
+                            (&quoted[..close], &quoted[close + 1..])
+                        } else {
+                            let end = rest.find([';', ':', ',']).unwrap_or(rest.len());
+                            (&rest[..end], &rest[end..])
+                        };
+                        values.push(decode_param_value(value));
+                        match remaining.strip_prefix(',') {
+                            Some(next) => rest = next,
+                            None => {
+                                rest = remaining;
+                                break;
+                            }
+                        }
+                    }
+                    property = property.with_param(param_name, values.join(","));
+                }
+                _ => return Err(format!("値がありません: {}", line)),
+            }
+        }
+    }
+
+    /// TEXT型としてエスケープを解除した値
+    pub fn text_value(&self) -> String {
+        unescape_text(&self.value)
+    }
+
+    /// 折り返し前のコンテンツ行
+    pub fn to_content_line(&self) -> String {
+        let mut line = self.name.clone();
+        for (name, value) in &self.params {
+            line.push(';');
+            line.push_str(name);
+            line.push('=');
+            if MULTI_VALUE_PARAMS.contains(&name.as_str()) {
+                // 複数値のパラメータは値ごとに引用符で囲む
+                let values: Vec<String> = value.split(',').map(quote_param_value).collect();
+                line.push_str(&values.join(","));
+            } else {
+                line.push_str(&quote_param_value(value));
+            }
+        }
+        line.push(':');
+        line.push_str(&self.value);
+        line
+    }
+}
+
+/// 値としてカレンダーアドレスの一覧をとるパラメータ
+const MULTI_VALUE_PARAMS: [&str; 3] = ["MEMBER", "DELEGATED-TO", "DELEGATED-FROM"];
+
+/// パラメータ値を RFC 6868 でエスケープし、必要な場合は引用符で囲みます
+fn quote_param_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '^' => encoded.push_str("^^"),
+            '"' => encoded.push_str("^'"),
+            '\n' => encoded.push_str("^n"),
+            '\r' => {}
+            c => encoded.push(c),
+        }
+    }
+    if encoded.contains([':', ';', ',']) {
+        format!("\"{}\"", encoded)
+    } else {
+        encoded
+    }
+}
+
+/// RFC 6868 のエスケープを解除します。規定外の `^` の組はそのまま残します
+fn decode_param_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '^' {
+            out.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('^') => out.push('^'),
+            Some('\'') => out.push('"'),
+            Some('n') => out.push('\n'),
+            _ => {
+                out.push('^');
+                continue;
+            }
+        }
+        chars.next();
+    }
+    out
+}
+
+/// TEXT型の値をエスケープします
+pub fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// TEXT型の値のエスケープを解除します
+pub fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// 75オクテットを超える行を、UTF-8の文字境界で CRLF と空白により折り返して書き込みます
+fn write_folded(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        // 継続行は先頭の空白を含めて75オクテットに収める
+        if width + len > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        let text = "会議室A; 2F, 奥\n\\備考";
+        let escaped = escape_text(text);
+        assert_eq!(escaped, "会議室A\\; 2F\\, 奥\\n\\\\備考");
+        assert_eq!(unescape_text(&escaped), text);
+    }
+
+    #[test]
+    fn test_content_line_and_folding() {
+        let property = Property::new("ATTENDEE", "mailto:alice@example.com")
+            .with_param("CN", "Smith, Alice")
+            .with_param("ROLE", "REQ-PARTICIPANT");
+        assert_eq!(
+            property.to_content_line(),
+            "ATTENDEE;CN=\"Smith, Alice\";ROLE=REQ-PARTICIPANT:mailto:alice@example.com"
+        );
+
+        assert_eq!(
+            Property::parse(&property.to_content_line()).unwrap(),
+            property
+        );
+        let exdate = Property::parse("EXDATE;TZID=Asia/Tokyo:20240117T100000").unwrap();
+        assert_eq!(exdate.param("tzid"), Some("Asia/Tokyo"));
+        assert_eq!(exdate.value, "20240117T100000");
+        assert!(Property::parse("NOVALUE").is_err());
+
+        // 複数値のパラメータ
+        let attendee =
+            Property::parse("ATTENDEE;MEMBER=\"mailto:a@x\",\"mailto:b@x\";CN=X:mailto:c@x")
+                .unwrap();
+        assert_eq!(attendee.param("MEMBER"), Some("mailto:a@x,mailto:b@x"));
+        assert_eq!(attendee.param("CN"), Some("X"));
+        assert_eq!(attendee.value, "mailto:c@x");
+        assert_eq!(
+            attendee.to_content_line(),
+            "ATTENDEE;MEMBER=\"mailto:a@x\",\"mailto:b@x\";CN=X:mailto:c@x"
+        );
+
+        let mut event = Component::new("vevent");
+        event.add_text("DESCRIPTION", &"あいうえお".repeat(20));
+        let ics = event.to_ics();
+        assert!(ics.starts_with("BEGIN:VEVENT\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\n"));
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "{}", line);
+        }
+        // 折り返しを解除すると元の行に戻る
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("DESCRIPTION:{}", "あいうえお".repeat(20))));
+    }
+
+    #[test]
+    fn test_param_value_rfc6868() {
+        let property = Property::new("ATTENDEE", "mailto:bob@example.com")
+            .with_param("CN", "Bob \"B\": Smith ^_^")
+            .with_param("X-NOTE", "1行目\n2行目");
+        let line = property.to_content_line();
+        assert_eq!(
+            line,
+            "ATTENDEE;CN=\"Bob ^'B^': Smith ^^_^^\";X-NOTE=1行目^n2行目:mailto:bob@example.com"
+        );
+        assert_eq!(Property::parse(&line).unwrap(), property);
+        // 規定外の組はそのまま
+        assert_eq!(
+            Property::parse("X;A=a^b:v").unwrap().param("A"),
+            Some("a^b")
+        );
+    }
+}
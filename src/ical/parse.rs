@@ -0,0 +1,156 @@
+use super::{Component, IcalError, Property};
+
+impl Component {
+    /// iCalendar テキストを解析します。最上位のコンポーネントは1つである必要があります
+    pub fn parse(text: &str) -> Result<Component, IcalError> {
+        let mut components = parse_components(text)?;
+        match components.len() {
+            1 => Ok(components.remove(0)),
+            n => Err(IcalError::Parse {
+                line: 0,
+                message: format!("最上位のコンポーネントは1つである必要があります: {}個", n),
+            }),
+        }
+    }
+}
+
+/// iCalendar テキストを解析し、最上位のコンポーネントをすべて返します
+///
+/// 改行は CRLF と LF のどちらも受け付け、空白またはタブで始まる行は前の行に連結します。
+pub fn parse_components(text: &str) -> Result<Vec<Component>, IcalError> {
+    parse_components_with(text, false)
+}
+
+/// 解析できないプロパティ行を、エラーにせずその行を含むコンポーネントに記録して解析します
+///
+/// インポートで、1つの VEVENT の不正な行がファイル全体の失敗にならないようにするために使います。
+/// BEGIN / END の対応が取れない場合はエラーを返します。
+pub(crate) fn parse_components_lenient(text: &str) -> Result<Vec<Component>, IcalError> {
+    parse_components_with(text, true)
+}
+
+fn parse_components_with(text: &str, lenient: bool) -> Result<Vec<Component>, IcalError> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+
+    for (line_number, line) in unfold(text) {
+        let error = |message: String| IcalError::Parse {
+            line: line_number,
+            message,
+        };
+        let property = match (Property::parse(&line), stack.last_mut()) {
+            (Ok(property), _) => property,
+            (Err(message), Some(component)) if lenient => {
+                component
+                    .parse_errors
+                    .push(format!("{}行目: {}", line_number, message));
+                continue;
+            }
+            (Err(message), _) => return Err(error(message)),
+        };
+        match property.name.as_str() {
+            "BEGIN" => stack.push(Component::new(property.value.trim())),
+            "END" => {
+                let component = stack
+                    .pop()
+                    .ok_or_else(|| error(format!("対応する BEGIN がありません: {}", line)))?;
+                if !component.name.eq_ignore_ascii_case(property.value.trim()) {
+                    return Err(error(format!(
+                        "BEGIN:{} が END:{} で閉じられています",
+                        component.name, property.value
+                    )));
+                }
+                match stack.last_mut() {
+                    Some(parent) => parent.components.push(component),
+                    None => roots.push(component),
+                }
+            }
+            _ => stack
+                .last_mut()
+                .ok_or_else(|| {
+                    error(format!(
+                        "コンポーネントの外にプロパティがあります: {}",
+                        line
+                    ))
+                })?
+                .push(property),
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(IcalError::Parse {
+            line: 0,
+            message: format!("BEGIN:{} が閉じられていません", open.name),
+        });
+    }
+    Ok(roots)
+}
+
+/// 折り返された行を連結し、(開始行番号, 論理行) の一覧を返します
+fn unfold(text: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (i, raw) in text.split('\n').enumerate() {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(continuation) = raw.strip_prefix([' ', '\t']) {
+            if let Some((_, last)) = lines.last_mut() {
+                last.push_str(continuation);
+                continue;
+            }
+        }
+        if !raw.is_empty() {
+            lines.push((i + 1, raw.to_string()));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let mut calendar = Component::new("VCALENDAR");
+        calendar.add("VERSION", "2.0");
+        let mut event = Component::new("VEVENT");
+        event.add_text("SUMMARY", &"長いタイトル, その2".repeat(10));
+        event.push(Property::new("ATTENDEE", "mailto:a@example.com").with_param("CN", "A, B"));
+        calendar.components.push(event);
+
+        let parsed = Component::parse(&calendar.to_ics()).unwrap();
+        assert_eq!(parsed, calendar);
+    }
+
+    #[test]
+    fn test_parse_lf_and_tab_folding() {
+        let text =
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:前半\n\t後半\nEND:VEVENT\nEND:VCALENDAR\n";
+        let calendar = Component::parse(text).unwrap();
+        let event = calendar.components_named("VEVENT").next().unwrap();
+        assert_eq!(event.property("SUMMARY").unwrap().text_value(), "前半後半");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let unclosed = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nEND:VCALENDAR\r\n";
+        assert!(matches!(
+            Component::parse(unclosed),
+            Err(IcalError::Parse { line: 3, .. })
+        ));
+        assert!(Component::parse("SUMMARY:外\r\n").is_err());
+        assert!(Component::parse("BEGIN:VCALENDAR\r\n").is_err());
+
+        // 寛容な解析では不正な行をコンポーネントに記録して続ける
+        let text =
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nBROKEN\r\nUID:a\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        assert!(matches!(
+            parse_components(text),
+            Err(IcalError::Parse { line: 3, .. })
+        ));
+        let calendar = parse_components_lenient(text).unwrap().remove(0);
+        let event = &calendar.components[0];
+        assert_eq!(event.properties.len(), 1);
+        assert_eq!(event.parse_errors.len(), 1);
+        assert!(event.parse_errors[0].starts_with("3行目"));
+    }
+}
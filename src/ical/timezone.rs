@@ -0,0 +1,432 @@
+use super::Component;
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc, Weekday,
+};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+/// ある時点で適用されているオフセットの情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Observance {
+    /// UTCからのオフセット(秒)
+    offset: i32,
+    daylight: bool,
+    name: Option<String>,
+}
+
+fn observance_at(tz: Tz, at: DateTime<Utc>) -> Observance {
+    let offset = tz.offset_from_utc_datetime(&at.naive_utc());
+    Observance {
+        offset: offset.fix().local_minus_utc(),
+        daylight: offset.dst_offset() != Duration::zero(),
+        name: offset.abbreviation().map(str::to_string),
+    }
+}
+
+/// `from_year` の年初から `to_year` の年末までの切り替えを含む VTIMEZONE を作成します
+///
+/// chrono-tz のタイムゾーンデータから、期間中のオフセットの切り替えを1件ずつ
+/// STANDARD / DAYLIGHT として書き出します。期間の開始時点のオフセットも含めるため、
+/// 切り替えのないタイムゾーンでは1件のみになります。
+pub(crate) fn vtimezone(tz: Tz, from_year: i32, to_year: i32) -> Component {
+    let year_start = |year: i32| {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc())
+    };
+    let mut vtimezone = Component::new("VTIMEZONE");
+    vtimezone.add("TZID", tz.name());
+
+    let (Some(start), Some(end)) = (year_start(from_year), year_start(to_year + 1)) else {
+        return vtimezone;
+    };
+
+    let mut current = observance_at(tz, start);
+    vtimezone
+        .components
+        .push(observance_component(&current, current.offset, start));
+
+    let mut day = start;
+    while day < end {
+        let next = day + Duration::days(1);
+        let next_observance = observance_at(tz, next);
+        if next_observance != current {
+            // 1日の中で切り替わった瞬間を二分探索で求める
+            let (mut lo, mut hi) = (day, next);
+            while hi - lo > Duration::seconds(1) {
+                let mid = lo + (hi - lo) / 2;
+                if observance_at(tz, mid) == current {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            let changed = observance_at(tz, hi);
+            vtimezone
+                .components
+                .push(observance_component(&changed, current.offset, hi));
+            current = changed;
+        }
+        day = next;
+    }
+    vtimezone
+}
+
+/// `at` から `observance` が適用される STANDARD / DAYLIGHT コンポーネント
+fn observance_component(observance: &Observance, offset_from: i32, at: DateTime<Utc>) -> Component {
+    let mut component = Component::new(if observance.daylight {
+        "DAYLIGHT"
+    } else {
+        "STANDARD"
+    });
+    // DTSTART は切り替え前のオフセットでのローカル時刻
+    let local = at.naive_utc() + Duration::seconds(offset_from as i64);
+    component.add("DTSTART", local.format("%Y%m%dT%H%M%S").to_string());
+    component.add("TZOFFSETFROM", format_utc_offset(offset_from));
+    component.add("TZOFFSETTO", format_utc_offset(observance.offset));
+    if let Some(name) = &observance.name {
+        component.add_text("TZNAME", name);
+    }
+    component
+}
+
+/// UTCオフセットを "+0900" のような形式にします。秒がある場合は "+051736" とします
+pub(crate) fn format_utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let abs = seconds.abs();
+    let (h, m, s) = (abs / 3600, abs / 60 % 60, abs % 60);
+    if s == 0 {
+        format!("{}{:02}{:02}", sign, h, m)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, h, m, s)
+    }
+}
+
+/// TZID をIANAタイムゾーンに対応付けます
+///
+/// IANA名そのもの、"/mozilla.org/20070129_1/Europe/Berlin" のように末尾にIANA名を含むもの、
+/// 埋め込みの VTIMEZONE の X-LIC-LOCATION を順に試します。
+pub(crate) fn resolve_iana(tzid: &str, vtimezone: Option<&Component>) -> Option<Tz> {
+    if let Ok(tz) = tzid.parse::<Tz>() {
+        return Some(tz);
+    }
+    let segments: Vec<&str> = tzid.split('/').filter(|s| !s.is_empty()).collect();
+    for take in (1..=segments.len().min(3)).rev() {
+        let candidate = segments[segments.len() - take..].join("/");
+        if let Ok(tz) = candidate.parse::<Tz>() {
+            return Some(tz);
+        }
+    }
+    vtimezone
+        .and_then(|c| c.property("X-LIC-LOCATION"))
+        .and_then(|p| p.value.parse::<Tz>().ok())
+}
+
+/// 埋め込みの VTIMEZONE の定義
+///
+/// IANAタイムゾーンに対応付けられない場合に、STANDARD / DAYLIGHT の定義から
+/// ローカル時刻のUTCオフセットを求めるために使用します。
+#[derive(Debug, Clone)]
+pub(crate) struct EmbeddedTimezone {
+    observances: Vec<EmbeddedObservance>,
+}
+
+#[derive(Debug, Clone)]
+struct EmbeddedObservance {
+    start: NaiveDateTime,
+    offset_to: i32,
+    rule: Option<YearlyRule>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+/// VTIMEZONE でよく使われる年単位の RRULE（BYMONTH と BYDAY または BYMONTHDAY）
+#[derive(Debug, Clone)]
+struct YearlyRule {
+    month: u32,
+    day: RuleDay,
+    until: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RuleDay {
+    /// 月の第n（負の場合は最後からn番目）の曜日
+    Weekday(i8, Weekday),
+    MonthDay(u32),
+}
+
+impl EmbeddedTimezone {
+    pub(crate) fn from_component(vtimezone: &Component) -> Result<Self, String> {
+        let mut observances = Vec::new();
+        for component in &vtimezone.components {
+            if !matches!(component.name.as_str(), "STANDARD" | "DAYLIGHT") {
+                continue;
+            }
+            let start = component
+                .property("DTSTART")
+                .ok_or("VTIMEZONE の定義に DTSTART がありません")
+                .and_then(|p| parse_local(&p.value).ok_or("DTSTART の形式が正しくありません"))?;
+            let offset_to = component
+                .property("TZOFFSETTO")
+                .and_then(|p| parse_utc_offset(&p.value))
+                .ok_or("TZOFFSETTO の形式が正しくありません")?;
+            let rule = match component.property("RRULE") {
+                Some(p) => Some(parse_yearly_rule(&p.value)?),
+                None => None,
+            };
+            let rdates = component
+                .properties_named("RDATE")
+                .flat_map(|p| p.value.split(','))
+                .filter_map(parse_local)
+                .collect();
+            observances.push(EmbeddedObservance {
+                start,
+                offset_to,
+                rule,
+                rdates,
+            });
+        }
+        if observances.is_empty() {
+            return Err("VTIMEZONE に STANDARD / DAYLIGHT がありません".to_string());
+        }
+        Ok(EmbeddedTimezone { observances })
+    }
+
+    /// ローカル時刻 `local` におけるUTCオフセット(秒)
+    pub(crate) fn offset_at(&self, local: NaiveDateTime) -> i32 {
+        let mut latest: Option<(NaiveDateTime, i32)> = None;
+        for observance in &self.observances {
+            for onset in observance.onsets_until(local) {
+                if latest.map_or(true, |(t, _)| onset > t) {
+                    latest = Some((onset, observance.offset_to));
+                }
+            }
+        }
+        // すべての定義より前の時刻は、最も早い定義のオフセットとする
+        latest.map(|(_, offset)| offset).unwrap_or_else(|| {
+            self.observances
+                .iter()
+                .min_by_key(|o| o.start)
+                .map(|o| o.offset_to)
+                .unwrap_or(0)
+        })
+    }
+
+    /// ローカル時刻をUTCに変換します
+    pub(crate) fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        (local - Duration::seconds(self.offset_at(local) as i64)).and_utc()
+    }
+}
+
+impl EmbeddedObservance {
+    /// `local` 以前の開始時刻のうち、直近の候補を返します
+    fn onsets_until(&self, local: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let mut onsets: Vec<NaiveDateTime> = std::iter::once(self.start)
+            .chain(self.rdates.iter().copied())
+            .filter(|t| *t <= local)
+            .collect();
+        if let Some(rule) = &self.rule {
+            for year in [local.year() - 1, local.year()] {
+                let Some(onset) = rule.onset(year, self.start.time()) else {
+                    continue;
+                };
+                let within_until = rule.until.map_or(true, |until| onset <= until);
+                if onset >= self.start && onset <= local && within_until {
+                    onsets.push(onset);
+                }
+            }
+        }
+        onsets
+    }
+}
+
+impl YearlyRule {
+    fn onset(&self, year: i32, time: chrono::NaiveTime) -> Option<NaiveDateTime> {
+        let date = match self.day {
+            RuleDay::MonthDay(day) => NaiveDate::from_ymd_opt(year, self.month, day)?,
+            RuleDay::Weekday(n, weekday) if n > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, self.month, weekday, n as u8)?
+            }
+            RuleDay::Weekday(n, weekday) => {
+                // 月末から数える
+                let first_of_next = if self.month == 12 {
+                    NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+                } else {
+                    NaiveDate::from_ymd_opt(year, self.month + 1, 1)?
+                };
+                let mut date = first_of_next.pred_opt()?;
+                while date.weekday() != weekday {
+                    date = date.pred_opt()?;
+                }
+                date - Duration::weeks((-n - 1) as i64)
+            }
+        };
+        Some(date.and_time(time))
+    }
+}
+
+fn parse_yearly_rule(value: &str) -> Result<YearlyRule, String> {
+    let mut freq = None;
+    let mut month = None;
+    let mut day = None;
+    let mut until = None;
+    for part in value.split(';') {
+        let (key, val) = part.split_once('=').unwrap_or((part, ""));
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => freq = Some(val.to_ascii_uppercase()),
+            "BYMONTH" => month = val.parse::<u32>().ok(),
+            "BYMONTHDAY" => day = val.parse::<u32>().ok().map(RuleDay::MonthDay),
+            "BYDAY" => day = parse_nth_weekday(val),
+            "UNTIL" => until = parse_local(val.trim_end_matches('Z')),
+            _ => {}
+        }
+    }
+    match (freq.as_deref(), month, day) {
+        (Some("YEARLY"), Some(month), Some(day)) => Ok(YearlyRule { month, day, until }),
+        _ => Err(format!("未対応の VTIMEZONE の RRULE です: {}", value)),
+    }
+}
+
+/// "2SU"、"-1SU" のような曜日指定を解析します
+fn parse_nth_weekday(value: &str) -> Option<RuleDay> {
+    let split = value.len().checked_sub(2)?;
+    let (n, day) = value.split_at(split);
+    let n: i8 = match n {
+        "" | "+" => return None,
+        n => n.trim_start_matches('+').parse().ok()?,
+    };
+    let weekday = match day.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    (n != 0).then_some(RuleDay::Weekday(n, weekday))
+}
+
+/// "20240115T100000" 形式のローカル時刻を解析します
+pub(crate) fn parse_local(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim(), "%Y%m%dT%H%M%S").ok()
+}
+
+/// "+0900"、"-0430"、"+051736" 形式のUTCオフセットを秒で返します
+pub(crate) fn parse_utc_offset(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let (sign, digits) = match value.split_at_checked(1)? {
+        ("+", d) => (1, d),
+        ("-", d) => (-1, d),
+        _ => return None,
+    };
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let h: i32 = digits[0..2].parse().ok()?;
+    let m: i32 = digits[2..4].parse().ok()?;
+    let s: i32 = digits.get(4..6).map_or(Some(0), |s| s.parse().ok())?;
+    Some(sign * (h * 3600 + m * 60 + s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vtimezone_with_dst() {
+        let component = vtimezone(chrono_tz::America::New_York, 2024, 2024);
+        assert_eq!(
+            component.property("TZID").unwrap().value,
+            "America/New_York"
+        );
+        let names: Vec<&str> = component
+            .components
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["STANDARD", "DAYLIGHT", "STANDARD"]);
+
+        // 2024-03-10 02:00 EST に EDT へ切り替わる
+        let daylight = &component.components[1];
+        assert_eq!(
+            daylight.property("DTSTART").unwrap().value,
+            "20240310T020000"
+        );
+        assert_eq!(daylight.property("TZOFFSETFROM").unwrap().value, "-0500");
+        assert_eq!(daylight.property("TZOFFSETTO").unwrap().value, "-0400");
+        assert_eq!(daylight.property("TZNAME").unwrap().value, "EDT");
+
+        let standard = &component.components[2];
+        assert_eq!(
+            standard.property("DTSTART").unwrap().value,
+            "20241103T020000"
+        );
+    }
+
+    #[test]
+    fn test_vtimezone_without_dst() {
+        let component = vtimezone(chrono_tz::Asia::Tokyo, 2024, 2025);
+        assert_eq!(component.components.len(), 1);
+        let standard = &component.components[0];
+        assert_eq!(standard.property("TZOFFSETTO").unwrap().value, "+0900");
+        assert_eq!(format_utc_offset(-(4 * 3600 + 30 * 60)), "-0430");
+        assert_eq!(format_utc_offset(5 * 3600 + 17 * 60 + 36), "+051736");
+    }
+
+    #[test]
+    fn test_resolve_iana() {
+        assert_eq!(
+            resolve_iana("Asia/Tokyo", None),
+            Some(chrono_tz::Asia::Tokyo)
+        );
+        assert_eq!(
+            resolve_iana("/mozilla.org/20070129_1/Europe/Berlin", None),
+            Some(chrono_tz::Europe::Berlin)
+        );
+        let mut vtimezone = Component::new("VTIMEZONE");
+        vtimezone.add("X-LIC-LOCATION", "America/New_York");
+        assert_eq!(
+            resolve_iana("Eastern Standard Time", Some(&vtimezone)),
+            Some(chrono_tz::America::New_York)
+        );
+        assert_eq!(resolve_iana("Eastern Standard Time", None), None);
+    }
+
+    #[test]
+    fn test_embedded_timezone() {
+        let text = "BEGIN:VTIMEZONE\r\n\
+TZID:Custom Eastern\r\n\
+BEGIN:STANDARD\r\n\
+DTSTART:20071104T020000\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n\
+TZOFFSETFROM:-0400\r\n\
+TZOFFSETTO:-0500\r\n\
+END:STANDARD\r\n\
+BEGIN:DAYLIGHT\r\n\
+DTSTART:20070311T020000\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r\n\
+TZOFFSETFROM:-0500\r\n\
+TZOFFSETTO:-0400\r\n\
+END:DAYLIGHT\r\n\
+END:VTIMEZONE\r\n";
+        let tz = EmbeddedTimezone::from_component(&Component::parse(text).unwrap()).unwrap();
+        let local = |s: &str| parse_local(s).unwrap();
+        assert_eq!(tz.offset_at(local("20240115T100000")), -5 * 3600);
+        assert_eq!(tz.offset_at(local("20240710T100000")), -4 * 3600);
+        assert_eq!(tz.offset_at(local("20240310T030000")), -4 * 3600);
+        assert_eq!(tz.offset_at(local("20241103T030000")), -5 * 3600);
+        assert_eq!(
+            tz.to_utc(local("20240710T100000")),
+            Utc.with_ymd_and_hms(2024, 7, 10, 14, 0, 0).unwrap()
+        );
+
+        // 最終日曜日の規則
+        let rule = parse_yearly_rule("FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU").unwrap();
+        assert_eq!(
+            rule.onset(2024, chrono::NaiveTime::from_hms_opt(3, 0, 0).unwrap()),
+            Some(local("20241027T030000"))
+        );
+        assert_eq!(parse_utc_offset("+0530"), Some(19800));
+        assert_eq!(parse_utc_offset("0530"), None);
+    }
+}
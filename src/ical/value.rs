@@ -0,0 +1,446 @@
+use super::{escape_text, unescape_text, Property};
+
+/// jCal / xCal で値に付ける型（RFC 5545 の VALUE パラメータに対応）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValueType {
+    Binary,
+    Boolean,
+    CalAddress,
+    Date,
+    DateTime,
+    Duration,
+    Float,
+    Integer,
+    Period,
+    Recur,
+    Text,
+    Time,
+    Uri,
+    UtcOffset,
+    /// 型が不明、または値を型どおりに解釈できないもの。値はそのまま保持します
+    Unknown,
+}
+
+impl ValueType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ValueType::Binary => "binary",
+            ValueType::Boolean => "boolean",
+            ValueType::CalAddress => "cal-address",
+            ValueType::Date => "date",
+            ValueType::DateTime => "date-time",
+            ValueType::Duration => "duration",
+            ValueType::Float => "float",
+            ValueType::Integer => "integer",
+            ValueType::Period => "period",
+            ValueType::Recur => "recur",
+            ValueType::Text => "text",
+            ValueType::Time => "time",
+            ValueType::Uri => "uri",
+            ValueType::UtcOffset => "utc-offset",
+            ValueType::Unknown => "unknown",
+        }
+    }
+
+    pub(crate) fn parse(name: &str) -> Option<ValueType> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "binary" => ValueType::Binary,
+            "boolean" => ValueType::Boolean,
+            "cal-address" => ValueType::CalAddress,
+            "date" => ValueType::Date,
+            "date-time" => ValueType::DateTime,
+            "duration" => ValueType::Duration,
+            "float" => ValueType::Float,
+            "integer" => ValueType::Integer,
+            "period" => ValueType::Period,
+            "recur" => ValueType::Recur,
+            "text" => ValueType::Text,
+            "time" => ValueType::Time,
+            "uri" => ValueType::Uri,
+            "utc-offset" => ValueType::UtcOffset,
+            "unknown" => ValueType::Unknown,
+            _ => return None,
+        })
+    }
+
+    /// VALUE パラメータがない場合のプロパティの型
+    pub(crate) fn default_for(property: &str) -> ValueType {
+        match property.to_ascii_uppercase().as_str() {
+            "DTSTART" | "DTEND" | "DTSTAMP" | "DUE" | "CREATED" | "LAST-MODIFIED" | "COMPLETED"
+            | "RECURRENCE-ID" | "EXDATE" | "RDATE" => ValueType::DateTime,
+            "DURATION" | "TRIGGER" => ValueType::Duration,
+            "RRULE" | "EXRULE" => ValueType::Recur,
+            "SEQUENCE" | "PRIORITY" | "PERCENT-COMPLETE" | "REPEAT" => ValueType::Integer,
+            "TZOFFSETFROM" | "TZOFFSETTO" => ValueType::UtcOffset,
+            "ATTENDEE" | "ORGANIZER" => ValueType::CalAddress,
+            "URL" | "TZURL" | "ATTACH" => ValueType::Uri,
+            "GEO" => ValueType::Float,
+            "FREEBUSY" => ValueType::Period,
+            "VERSION" | "PRODID" | "CALSCALE" | "METHOD" | "UID" | "SUMMARY" | "DESCRIPTION"
+            | "LOCATION" | "STATUS" | "CLASS" | "TRANSP" | "COMMENT" | "CONTACT" | "CATEGORIES"
+            | "RESOURCES" | "RELATED-TO" | "ACTION" | "TZID" | "TZNAME" | "REQUEST-STATUS" => {
+                ValueType::Text
+            }
+            _ => ValueType::Unknown,
+        }
+    }
+}
+
+/// 型付きの値（jCal / xCal の1つの値）
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TypedValue {
+    /// 文字列として表す値（日付・日時は "2024-01-15T10:00:00Z" のような拡張形式）
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// RRULE の各要素（キーは小文字）
+    Recur(Vec<(String, Vec<String>)>),
+    /// 開始と、終了または期間
+    Period(String, String),
+    /// GEO の緯度と経度
+    Geo(f64, f64),
+}
+
+/// 型と値を求めます。型どおりに解釈できない場合は `ValueType::Unknown` として元の値を返します
+///
+/// 戻り値の型が `Unknown` でない場合、VALUE パラメータは型として表されるため
+/// 呼び出し側はパラメータから取り除いてください。
+pub(crate) fn typed_values(property: &Property) -> (ValueType, Vec<TypedValue>) {
+    let explicit = property.param("VALUE").map(ValueType::parse);
+    let ty = match explicit {
+        Some(Some(ty)) => ty,
+        Some(None) => ValueType::Unknown,
+        None => ValueType::default_for(&property.name),
+    };
+    match to_typed(ty, &property.name, &property.value) {
+        Some(values) => (ty, values),
+        None => (
+            ValueType::Unknown,
+            vec![TypedValue::String(property.value.clone())],
+        ),
+    }
+}
+
+/// 型付きの値から iCalendar 上の値を組み立て、VALUE パラメータを補ったプロパティを返します
+///
+/// 既定と異なる型の場合は VALUE パラメータを先頭に追加します。
+pub(crate) fn property_from_typed(
+    name: &str,
+    params: Vec<(String, String)>,
+    ty: ValueType,
+    values: &[TypedValue],
+) -> Result<Property, String> {
+    let mut property = Property::new(name, from_typed(ty, name, values)?);
+    if ty != ValueType::Unknown && ty != ValueType::default_for(name) {
+        property
+            .params
+            .push(("VALUE".to_string(), ty.as_str().to_ascii_uppercase()));
+    }
+    property.params.extend(params);
+    Ok(property)
+}
+
+/// カンマ区切りで複数の値を持てるプロパティ
+fn is_multi_valued(property: &str) -> bool {
+    matches!(
+        property.to_ascii_uppercase().as_str(),
+        "EXDATE" | "RDATE" | "FREEBUSY" | "CATEGORIES" | "RESOURCES"
+    )
+}
+
+fn to_typed(ty: ValueType, name: &str, raw: &str) -> Option<Vec<TypedValue>> {
+    let parts: Vec<&str> = if is_multi_valued(name) {
+        split_unescaped(raw, ',')
+    } else {
+        vec![raw]
+    };
+    parts
+        .into_iter()
+        .map(|part| to_typed_single(ty, part))
+        .collect()
+}
+
+fn to_typed_single(ty: ValueType, raw: &str) -> Option<TypedValue> {
+    Some(match ty {
+        ValueType::Text => TypedValue::String(unescape_text(raw)),
+        ValueType::Date => TypedValue::String(date_to_extended(raw)?),
+        ValueType::DateTime => TypedValue::String(date_time_to_extended(raw)?),
+        ValueType::Time => TypedValue::String(time_to_extended(raw)?),
+        ValueType::UtcOffset => TypedValue::String(offset_to_extended(raw)?),
+        ValueType::Integer => TypedValue::Integer(raw.parse().ok()?),
+        ValueType::Boolean => match raw.to_ascii_uppercase().as_str() {
+            "TRUE" => TypedValue::Boolean(true),
+            "FALSE" => TypedValue::Boolean(false),
+            _ => return None,
+        },
+        ValueType::Float => match raw.split_once(';') {
+            Some((lat, lon)) => TypedValue::Geo(lat.parse().ok()?, lon.parse().ok()?),
+            None => TypedValue::Float(raw.parse().ok()?),
+        },
+        ValueType::Period => {
+            let (start, end) = raw.split_once('/')?;
+            let end = if end.starts_with(['P', '+', '-']) {
+                end.to_string()
+            } else {
+                date_time_to_extended(end)?
+            };
+            TypedValue::Period(date_time_to_extended(start)?, end)
+        }
+        ValueType::Recur => TypedValue::Recur(recur_to_parts(raw)?),
+        ValueType::Binary
+        | ValueType::CalAddress
+        | ValueType::Duration
+        | ValueType::Uri
+        | ValueType::Unknown => TypedValue::String(raw.to_string()),
+    })
+}
+
+fn from_typed(ty: ValueType, name: &str, values: &[TypedValue]) -> Result<String, String> {
+    let parts = values
+        .iter()
+        .map(|value| from_typed_single(ty, value))
+        .collect::<Result<Vec<_>, _>>()?;
+    if parts.len() > 1 && !is_multi_valued(name) && ty != ValueType::Text {
+        return Err(format!("{} は複数の値を持てません", name));
+    }
+    Ok(parts.join(","))
+}
+
+fn from_typed_single(ty: ValueType, value: &TypedValue) -> Result<String, String> {
+    let invalid = || format!("{} 型の値が不正です: {:?}", ty.as_str(), value);
+    match (ty, value) {
+        (ValueType::Text, TypedValue::String(s)) => Ok(escape_text(s)),
+        (ValueType::Date, TypedValue::String(s)) => Ok(s.replace('-', "")),
+        (ValueType::DateTime, TypedValue::String(s))
+        | (ValueType::Time, TypedValue::String(s))
+        | (ValueType::UtcOffset, TypedValue::String(s)) => Ok(basic_format(s)),
+        (ValueType::Integer, TypedValue::Integer(n)) => Ok(n.to_string()),
+        (ValueType::Float, TypedValue::Float(f)) => Ok(f.to_string()),
+        (ValueType::Float, TypedValue::Geo(lat, lon)) => Ok(format!("{};{}", lat, lon)),
+        (ValueType::Boolean, TypedValue::Boolean(b)) => {
+            Ok(if *b { "TRUE" } else { "FALSE" }.to_string())
+        }
+        (ValueType::Period, TypedValue::Period(start, end)) => {
+            Ok(format!("{}/{}", basic_format(start), basic_format(end)))
+        }
+        (ValueType::Recur, TypedValue::Recur(parts)) => Ok(recur_from_parts(parts)),
+        (
+            ValueType::Binary
+            | ValueType::CalAddress
+            | ValueType::Duration
+            | ValueType::Uri
+            | ValueType::Unknown,
+            TypedValue::String(s),
+        ) => Ok(s.clone()),
+        _ => Err(invalid()),
+    }
+}
+
+/// RRULE の要素のうち整数の値を持つもの
+pub(crate) fn is_integer_recur_part(key: &str) -> bool {
+    matches!(
+        key,
+        "count"
+            | "interval"
+            | "bysecond"
+            | "byminute"
+            | "byhour"
+            | "bymonthday"
+            | "byyearday"
+            | "byweekno"
+            | "bymonth"
+            | "bysetpos"
+    )
+}
+
+fn recur_to_parts(raw: &str) -> Option<Vec<(String, Vec<String>)>> {
+    raw.split(';')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (key, value) = part.split_once('=')?;
+            let key = key.to_ascii_lowercase();
+            let values = if key == "until" {
+                let until = if value.len() == 8 {
+                    date_to_extended(value)?
+                } else {
+                    date_time_to_extended(value)?
+                };
+                vec![until]
+            } else {
+                value.split(',').map(str::to_string).collect()
+            };
+            Some((key, values))
+        })
+        .collect()
+}
+
+fn recur_from_parts(parts: &[(String, Vec<String>)]) -> String {
+    parts
+        .iter()
+        .map(|(key, values)| {
+            let values: Vec<String> = if key == "until" {
+                values.iter().map(|v| basic_format(v)).collect()
+            } else {
+                values.clone()
+            };
+            format!("{}={}", key.to_ascii_uppercase(), values.join(","))
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// エスケープされていない区切り文字で分割します
+fn split_unescaped(raw: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in raw.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            c if c == separator && !escaped => {
+                parts.push(&raw[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => escaped = false,
+        }
+    }
+    parts.push(&raw[start..]);
+    parts
+}
+
+/// "20240115" → "2024-01-15"
+fn date_to_extended(raw: &str) -> Option<String> {
+    if raw.len() != 8 || !raw.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}-{}-{}", &raw[0..4], &raw[4..6], &raw[6..8]))
+}
+
+/// "100000" / "100000Z" → "10:00:00" / "10:00:00Z"
+fn time_to_extended(raw: &str) -> Option<String> {
+    let (digits, utc) = match raw.strip_suffix('Z') {
+        Some(digits) => (digits, "Z"),
+        None => (raw, ""),
+    };
+    if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!(
+        "{}:{}:{}{}",
+        &digits[0..2],
+        &digits[2..4],
+        &digits[4..6],
+        utc
+    ))
+}
+
+/// "20240115T100000Z" → "2024-01-15T10:00:00Z"
+fn date_time_to_extended(raw: &str) -> Option<String> {
+    let (date, time) = raw.split_once('T')?;
+    Some(format!(
+        "{}T{}",
+        date_to_extended(date)?,
+        time_to_extended(time)?
+    ))
+}
+
+/// "+0900" → "+09:00"、"+051736" → "+05:17:36"
+fn offset_to_extended(raw: &str) -> Option<String> {
+    let (sign, digits) = raw.split_at_checked(1)?;
+    if !matches!(sign, "+" | "-")
+        || !(digits.len() == 4 || digits.len() == 6)
+        || !digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut out = format!("{}{}:{}", sign, &digits[0..2], &digits[2..4]);
+    if digits.len() == 6 {
+        out.push(':');
+        out.push_str(&digits[4..6]);
+    }
+    Some(out)
+}
+
+/// 拡張形式から区切りの "-" と ":" を取り除きます（期間の値はそのまま）
+fn basic_format(value: &str) -> String {
+    if value.trim_start_matches(['+', '-']).starts_with('P') {
+        return value.to_string();
+    }
+    let sign = value
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '+' | '-'))
+        .map(String::from)
+        .unwrap_or_default();
+    let body = &value[sign.len()..];
+    format!("{}{}", sign, body.replace([':', '-'], ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_values_round_trip() {
+        let cases = [
+            Property::new("DTSTART", "20240115T100000").with_param("TZID", "Asia/Tokyo"),
+            Property::new("DTSTART", "20240115").with_param("VALUE", "DATE"),
+            Property::new("EXDATE", "20240122T010000Z,20240129T010000Z"),
+            Property::new(
+                "RRULE",
+                "FREQ=WEEKLY;UNTIL=20241231T000000Z;BYDAY=MO,WE;COUNT=10",
+            ),
+            Property::new("TZOFFSETFROM", "-0500"),
+            Property::new("SEQUENCE", "3"),
+            Property::new("GEO", "35.681236;139.767125"),
+            Property::new("TRIGGER", "-PT15M"),
+            Property::new("RDATE", "20240101T000000Z/PT1H").with_param("VALUE", "PERIOD"),
+            Property::new("CATEGORIES", "会議,社外\\,重要"),
+            Property::text("SUMMARY", "定例, 第1週; 会議室A"),
+            Property::new("X-WR-CALNAME", "チーム"),
+            // 型どおりに解釈できない値はそのまま保持する
+            Property::new("DTSTART", "2024-01-15"),
+        ];
+        for property in cases {
+            let (ty, values) = typed_values(&property);
+            let params: Vec<_> = property
+                .params
+                .iter()
+                .filter(|(name, _)| ty == ValueType::Unknown || name != "VALUE")
+                .cloned()
+                .collect();
+            let restored = property_from_typed(&property.name, params, ty, &values).unwrap();
+            assert_eq!(restored, property);
+        }
+    }
+
+    #[test]
+    fn test_typed_value_formats() {
+        let (ty, values) = typed_values(&Property::new("DTSTART", "20240115T100000Z"));
+        assert_eq!(ty, ValueType::DateTime);
+        assert_eq!(
+            values,
+            vec![TypedValue::String("2024-01-15T10:00:00Z".to_string())]
+        );
+
+        let (ty, values) = typed_values(&Property::new("RRULE", "FREQ=DAILY;COUNT=5"));
+        assert_eq!(ty, ValueType::Recur);
+        assert_eq!(
+            values,
+            vec![TypedValue::Recur(vec![
+                ("freq".to_string(), vec!["DAILY".to_string()]),
+                ("count".to_string(), vec!["5".to_string()]),
+            ])]
+        );
+
+        let (ty, values) = typed_values(&Property::text("CATEGORIES", "A,B"));
+        assert_eq!(ty, ValueType::Text);
+        assert_eq!(values.len(), 1);
+        assert_eq!(typed_values(&Property::new("CATEGORIES", "A,B")).1.len(), 2);
+        assert_eq!(
+            typed_values(&Property::new("TZOFFSETTO", "+0900")).1,
+            vec![TypedValue::String("+09:00".to_string())]
+        );
+    }
+}
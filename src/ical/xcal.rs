@@ -0,0 +1,351 @@
+use super::value::{property_from_typed, typed_values, TypedValue, ValueType};
+use super::{Component, IcalError, Property};
+
+/// xCal の XML 名前空間
+pub const XCAL_NAMESPACE: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+
+impl Component {
+    /// xCal（RFC 6321）の XML 文書に変換します
+    pub fn to_xcal(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        out.push_str(&format!("<icalendar xmlns=\"{}\">\n", XCAL_NAMESPACE));
+        write_component(&mut out, self, 1);
+        out.push_str("</icalendar>\n");
+        out
+    }
+
+    /// xCal の XML 文書からコンポーネントを作成します。`icalendar` 要素の子は1つである必要があります
+    pub fn from_xcal(xml: &str) -> Result<Component, IcalError> {
+        let document =
+            roxmltree::Document::parse(xml).map_err(|e| IcalError::Xcal(e.to_string()))?;
+        let root = document.root_element();
+        if root.tag_name().name() != "icalendar" {
+            return Err(IcalError::Xcal(format!(
+                "ルート要素が icalendar ではありません: {}",
+                root.tag_name().name()
+            )));
+        }
+        let mut components: Vec<_> = root.children().filter(|n| n.is_element()).collect();
+        match components.len() {
+            1 => component_from_xcal(components.remove(0)),
+            n => Err(IcalError::Xcal(format!(
+                "icalendar 要素の子は1つである必要があります: {}個",
+                n
+            ))),
+        }
+    }
+}
+
+fn indent(out: &mut String, depth: usize) {
+    out.push_str(&"  ".repeat(depth));
+}
+
+fn write_component(out: &mut String, component: &Component, depth: usize) {
+    let name = component.name.to_ascii_lowercase();
+    indent(out, depth);
+    out.push_str(&format!("<{}>\n", name));
+    if !component.properties.is_empty() {
+        indent(out, depth + 1);
+        out.push_str("<properties>\n");
+        for property in &component.properties {
+            indent(out, depth + 2);
+            write_property(out, property);
+            out.push('\n');
+        }
+        indent(out, depth + 1);
+        out.push_str("</properties>\n");
+    }
+    if !component.components.is_empty() {
+        indent(out, depth + 1);
+        out.push_str("<components>\n");
+        for child in &component.components {
+            write_component(out, child, depth + 2);
+        }
+        indent(out, depth + 1);
+        out.push_str("</components>\n");
+    }
+    indent(out, depth);
+    out.push_str(&format!("</{}>\n", name));
+}
+
+/// `<dtstart><parameters>…</parameters><date-time>…</date-time></dtstart>` を1行で書き込みます
+fn write_property(out: &mut String, property: &Property) {
+    let (ty, values) = typed_values(property);
+    let name = property.name.to_ascii_lowercase();
+    out.push_str(&format!("<{}>", name));
+
+    let params: Vec<_> = property
+        .params
+        .iter()
+        .filter(|(name, _)| ty == ValueType::Unknown || name != "VALUE")
+        .collect();
+    if !params.is_empty() {
+        out.push_str("<parameters>");
+        for (name, value) in params {
+            let name = name.to_ascii_lowercase();
+            let element = param_value_type(&name);
+            out.push_str(&format!(
+                "<{0}><{1}>{2}</{1}></{0}>",
+                name,
+                element,
+                escape_xml(value)
+            ));
+        }
+        out.push_str("</parameters>");
+    }
+
+    for value in values {
+        write_value(out, ty, value);
+    }
+    out.push_str(&format!("</{}>", name));
+}
+
+fn write_value(out: &mut String, ty: ValueType, value: TypedValue) {
+    let element = |out: &mut String, name: &str, text: &str| {
+        out.push_str(&format!("<{0}>{1}</{0}>", name, escape_xml(text)));
+    };
+    match value {
+        TypedValue::String(s) => element(out, ty.as_str(), &s),
+        TypedValue::Integer(n) => element(out, ty.as_str(), &n.to_string()),
+        TypedValue::Float(f) => element(out, ty.as_str(), &f.to_string()),
+        TypedValue::Boolean(b) => element(out, ty.as_str(), &b.to_string()),
+        TypedValue::Geo(lat, lon) => {
+            element(out, "latitude", &lat.to_string());
+            element(out, "longitude", &lon.to_string());
+        }
+        TypedValue::Period(start, end) => {
+            out.push_str("<period>");
+            element(out, "start", &start);
+            let end_name = if end.trim_start_matches(['+', '-']).starts_with('P') {
+                "duration"
+            } else {
+                "end"
+            };
+            element(out, end_name, &end);
+            out.push_str("</period>");
+        }
+        TypedValue::Recur(parts) => {
+            out.push_str("<recur>");
+            for (key, values) in parts {
+                for value in values {
+                    element(out, &key, &value);
+                }
+            }
+            out.push_str("</recur>");
+        }
+    }
+}
+
+/// パラメータの値の型
+fn param_value_type(name: &str) -> &'static str {
+    match name {
+        "delegated-from" | "delegated-to" | "member" | "sent-by" => "cal-address",
+        "altrep" | "dir" => "uri",
+        _ => "text",
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn elements<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children().filter(|n| n.is_element())
+}
+
+fn text(node: roxmltree::Node) -> String {
+    node.text().unwrap_or_default().to_string()
+}
+
+fn component_from_xcal(node: roxmltree::Node) -> Result<Component, IcalError> {
+    let mut component = Component::new(node.tag_name().name());
+    for child in elements(node) {
+        match child.tag_name().name() {
+            "properties" => {
+                for property in elements(child) {
+                    component.push(property_from_xcal(property)?);
+                }
+            }
+            "components" => {
+                for sub in elements(child) {
+                    component.components.push(component_from_xcal(sub)?);
+                }
+            }
+            other => {
+                return Err(IcalError::Xcal(format!(
+                    "{} 要素に不明な子要素があります: {}",
+                    component.name, other
+                )))
+            }
+        }
+    }
+    Ok(component)
+}
+
+fn property_from_xcal(node: roxmltree::Node) -> Result<Property, IcalError> {
+    let name = node.tag_name().name();
+    let invalid = |message: &str| IcalError::Xcal(format!("{}: {}", name, message));
+
+    let mut params = Vec::new();
+    let mut value_nodes = Vec::new();
+    for child in elements(node) {
+        if child.tag_name().name() == "parameters" {
+            for param in elements(child) {
+                let value = elements(param).map(text).collect::<Vec<_>>().join(",");
+                params.push((param.tag_name().name().to_ascii_uppercase(), value));
+            }
+        } else {
+            value_nodes.push(child);
+        }
+    }
+
+    // GEO は値の型の要素を持たず、緯度と経度を直接含む
+    if let [lat, lon] = value_nodes.as_slice() {
+        if lat.tag_name().name() == "latitude" && lon.tag_name().name() == "longitude" {
+            let parse = |n: &roxmltree::Node| {
+                text(*n)
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| invalid("緯度・経度が数値ではありません"))
+            };
+            let geo = TypedValue::Geo(parse(lat)?, parse(lon)?);
+            return property_from_typed(name, params, ValueType::Float, &[geo])
+                .map_err(IcalError::Xcal);
+        }
+    }
+
+    let ty_name = value_nodes
+        .first()
+        .map(|n| n.tag_name().name())
+        .ok_or_else(|| invalid("値がありません"))?;
+    let ty = ValueType::parse(ty_name).ok_or_else(|| invalid("値の型が不正です"))?;
+    let values = value_nodes
+        .iter()
+        .map(|n| {
+            if n.tag_name().name() != ty_name {
+                return Err(invalid("値の型が混在しています"));
+            }
+            value_from_xcal(ty, *n).ok_or_else(|| invalid("値が型と一致しません"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    property_from_typed(name, params, ty, &values).map_err(IcalError::Xcal)
+}
+
+fn value_from_xcal(ty: ValueType, node: roxmltree::Node) -> Option<TypedValue> {
+    Some(match ty {
+        ValueType::Integer => TypedValue::Integer(text(node).trim().parse().ok()?),
+        ValueType::Float => TypedValue::Float(text(node).trim().parse().ok()?),
+        ValueType::Boolean => TypedValue::Boolean(text(node).trim().parse().ok()?),
+        ValueType::Period => {
+            let mut start = None;
+            let mut end = None;
+            for child in elements(node) {
+                match child.tag_name().name() {
+                    "start" => start = Some(text(child)),
+                    "end" | "duration" => end = Some(text(child)),
+                    _ => return None,
+                }
+            }
+            TypedValue::Period(start?, end?)
+        }
+        ValueType::Recur => {
+            let mut parts: Vec<(String, Vec<String>)> = Vec::new();
+            for child in elements(node) {
+                let key = child.tag_name().name().to_ascii_lowercase();
+                match parts.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, values)) => values.push(text(child)),
+                    None => parts.push((key, vec![text(child)])),
+                }
+            }
+            TypedValue::Recur(parts)
+        }
+        _ => TypedValue::String(text(node)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_calendar() -> Component {
+        Component::parse(
+            "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//JA\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-1@example.com\r\n\
+DTSTART;TZID=Asia/Tokyo:20240115T100000\r\n\
+DURATION:PT1H\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=10\r\n\
+RDATE;VALUE=PERIOD:20240301T010000Z/PT2H\r\n\
+SUMMARY:R&D <定例>\\, 第1週\r\n\
+GEO:35.681236;139.767125\r\n\
+ATTENDEE;CN=\"Smith, Bob\";DELEGATED-FROM=\"mailto:a@example.com\":mailto:bob@example.com\r\n\
+X-CUSTOM;VALUE=X-UNKNOWN:raw\\;value\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+TRIGGER:-PT15M\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_xcal() {
+        let xml = sample_calendar().to_xcal();
+        assert!(xml.contains(&format!("<icalendar xmlns=\"{}\">", XCAL_NAMESPACE)));
+        assert!(xml.contains(
+            "<dtstart><parameters><tzid><text>Asia/Tokyo</text></tzid></parameters>\
+<date-time>2024-01-15T10:00:00</date-time></dtstart>"
+        ));
+        assert!(xml.contains(
+            "<rrule><recur><freq>WEEKLY</freq><byday>MO</byday><byday>WE</byday>\
+<count>10</count></recur></rrule>"
+        ));
+        assert!(xml.contains(
+            "<rdate><period><start>2024-03-01T01:00:00Z</start><duration>PT2H</duration>\
+</period></rdate>"
+        ));
+        assert!(xml.contains("<summary><text>R&amp;D &lt;定例&gt;, 第1週</text></summary>"));
+        assert!(xml.contains(
+            "<geo><latitude>35.681236</latitude><longitude>139.767125</longitude></geo>"
+        ));
+        assert!(xml.contains("<delegated-from><cal-address>mailto:a@example.com</cal-address>"));
+    }
+
+    #[test]
+    fn test_xcal_round_trip() {
+        let calendar = sample_calendar();
+        assert_eq!(Component::from_xcal(&calendar.to_xcal()).unwrap(), calendar);
+    }
+
+    #[test]
+    fn test_from_xcal_errors() {
+        assert!(Component::from_xcal("<icalendar>").is_err());
+        assert!(Component::from_xcal("<vcalendar/>").is_err());
+        assert!(Component::from_xcal("<icalendar><vcalendar/><vcalendar/></icalendar>").is_err());
+        assert!(Component::from_xcal(
+            "<icalendar><vcalendar><properties><version><nope>2.0</nope></version>\
+</properties></vcalendar></icalendar>"
+        )
+        .is_err());
+        assert!(Component::from_xcal(
+            "<icalendar><vcalendar><properties><sequence><integer>x</integer></sequence>\
+</properties></vcalendar></icalendar>"
+        )
+        .is_err());
+    }
+}
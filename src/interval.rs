@@ -0,0 +1,254 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// 半開区間 [start, end) で表される時間帯
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Interval {
+    /// 時間帯を作成します。終了が開始以前の場合は None を返します
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Option<Self> {
+        if start < end {
+            Some(Interval { start, end })
+        } else {
+            None
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, instant: DateTime<Utc>) -> bool {
+        self.start <= instant && instant < self.end
+    }
+
+    /// 端点が接しているだけの場合は重なりとみなしません
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// 前後に指定した時間だけ広げた時間帯を返します
+    pub fn expand(&self, before: Duration, after: Duration) -> Interval {
+        Interval {
+            start: self.start - before,
+            end: (self.end + after).max(self.start - before),
+        }
+    }
+}
+
+/// 重なりのない時間帯を開始順に保持する集合
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// 任意の時間帯から集合を作成します。重なる・接する時間帯はまとめられます
+    pub fn from_intervals<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut intervals: Vec<Interval> = intervals.into_iter().collect();
+        intervals.sort();
+        IntervalSet {
+            intervals: merge_sorted(intervals),
+        }
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let pos = self.intervals.partition_point(|i| i < &interval);
+        self.intervals.insert(pos, interval);
+        self.intervals = merge_sorted(std::mem::take(&mut self.intervals));
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.intervals
+            .iter()
+            .fold(Duration::zero(), |acc, i| acc + i.duration())
+    }
+
+    pub fn contains(&self, instant: DateTime<Utc>) -> bool {
+        let pos = self.intervals.partition_point(|i| i.end <= instant);
+        self.intervals.get(pos).is_some_and(|i| i.contains(instant))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from_intervals(self.iter().chain(other.iter()).copied())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if let Some(overlap) = a[i].intersection(&b[j]) {
+                result.push(overlap);
+            }
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// self から other に含まれる時間帯を取り除いた集合を返します
+    pub fn subtract(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = Vec::new();
+        let mut j = 0;
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            // other は開始順なので、この区間より前に終わるものは以降も不要
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < interval.end {
+                let cut = other.intervals[k];
+                if cut.start > start {
+                    result.push(Interval {
+                        start,
+                        end: cut.start,
+                    });
+                }
+                start = start.max(cut.end);
+                k += 1;
+            }
+            if start < interval.end {
+                result.push(Interval {
+                    start,
+                    end: interval.end,
+                });
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// 指定期間内で、集合に含まれない時間帯（空き）を返します
+    pub fn gaps(&self, within: Interval) -> IntervalSet {
+        IntervalSet::from_intervals([within]).subtract(self)
+    }
+
+    /// 各時間帯を前後に広げた集合を返します
+    pub fn expand(&self, before: Duration, after: Duration) -> IntervalSet {
+        IntervalSet::from_intervals(self.iter().map(|i| i.expand(before, after)))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        IntervalSet::from_intervals(iter)
+    }
+}
+
+impl<'a> IntoIterator for &'a IntervalSet {
+    type Item = &'a Interval;
+    type IntoIter = std::slice::Iter<'a, Interval>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+fn merge_sorted(intervals: Vec<Interval>) -> Vec<Interval> {
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        if interval.start >= interval.end {
+            continue;
+        }
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn iv(start: u32, end: u32) -> Interval {
+        Interval::new(
+            Utc.with_ymd_and_hms(2024, 1, 1, start, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, end, 0, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn set(spans: &[(u32, u32)]) -> IntervalSet {
+        spans.iter().map(|&(s, e)| iv(s, e)).collect()
+    }
+
+    #[test]
+    fn test_interval_basics() {
+        assert!(Interval::new(iv(1, 2).end, iv(1, 2).start).is_none());
+        assert!(iv(1, 3).overlaps(&iv(2, 4)));
+        assert!(!iv(1, 2).overlaps(&iv(2, 3)));
+        assert_eq!(iv(1, 3).intersection(&iv(2, 4)), Some(iv(2, 3)));
+        assert_eq!(iv(1, 3).duration(), Duration::hours(2));
+    }
+
+    #[test]
+    fn test_merging() {
+        let s = set(&[(5, 6), (1, 2), (2, 3), (4, 6)]);
+        assert_eq!(s.intervals(), &[iv(1, 3), iv(4, 6)]);
+
+        let mut s = set(&[(1, 2), (5, 6)]);
+        s.insert(iv(2, 5));
+        assert_eq!(s.intervals(), &[iv(1, 6)]);
+    }
+
+    #[test]
+    fn test_union_intersection_subtract() {
+        let a = set(&[(1, 4), (6, 9)]);
+        let b = set(&[(3, 7), (10, 11)]);
+        assert_eq!(a.union(&b).intervals(), &[iv(1, 9), iv(10, 11)]);
+        assert_eq!(a.intersection(&b).intervals(), &[iv(3, 4), iv(6, 7)]);
+        assert_eq!(a.subtract(&b).intervals(), &[iv(1, 3), iv(7, 9)]);
+        assert_eq!(
+            set(&[(0, 10)])
+                .subtract(&set(&[(1, 2), (3, 4)]))
+                .intervals(),
+            &[iv(0, 1), iv(2, 3), iv(4, 10)]
+        );
+    }
+
+    #[test]
+    fn test_gaps_and_contains() {
+        let busy = set(&[(9, 10), (12, 13)]);
+        assert_eq!(
+            busy.gaps(iv(8, 18)).intervals(),
+            &[iv(8, 9), iv(10, 12), iv(13, 18)]
+        );
+        assert!(busy.contains(iv(9, 10).start));
+        assert!(!busy.contains(iv(10, 11).start));
+        assert_eq!(busy.total_duration(), Duration::hours(2));
+    }
+}
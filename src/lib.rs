@@ -1,11 +1,37 @@
+pub mod attachment;
+pub mod attendee;
+pub mod batch;
 pub mod calendar_client;
+pub mod calendar_list;
+pub mod change_feed;
+pub mod channel;
+pub mod colors;
+pub mod conference;
 pub mod config;
 pub mod error;
 pub mod event;
+pub mod event_builder;
+pub mod event_csv;
+pub mod event_id;
+pub mod event_import;
+pub mod event_list;
+pub mod event_move;
+pub mod event_type;
+pub mod extended_properties;
+pub mod freebusy;
 pub mod http_client;
+pub mod ical;
+pub mod interval;
 #[cfg(test)]
 pub mod mock;
+pub mod notification;
+pub mod patch;
+pub mod quick_add;
+pub mod reminders;
+pub mod scheduling;
+pub mod settings;
 pub mod timezone_utils;
+pub mod validation;
 
 pub use calendar_client::CalendarClient;
 pub use event::Event;
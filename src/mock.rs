@@ -1,8 +1,33 @@
 #[cfg(test)]
 pub mod test_utils {
+    use crate::calendar_list::CalendarListEntry;
+    use crate::colors::{ColorDefinition, Colors, EventColor};
+    use crate::conference::{
+        ConferenceData, ConferenceRequestStatus, ConferenceStatusCode, EntryPoint,
+    };
     use crate::event::Event;
+    use crate::event_list::EventList;
+    use crate::reminders::Reminder;
+    use crate::settings::{Setting, SettingList};
     use chrono::{Duration, Utc};
 
+    /// モックの作成APIが409(既に存在)を返すイベントID
+    pub const CONFLICT_EVENT_ID: &str = "conflict0000";
+    /// モックの取得APIが会議作成済みのイベントを返すイベントID
+    pub const CONFERENCE_EVENT_ID: &str = "conference0000";
+    /// モックのリソースの現在のETag
+    pub const MOCK_ETAG: &str = "\"mock_etag\"";
+    /// モックの取得APIが現在と異なる（古い）ETagを返すイベントID
+    pub const STALE_ETAG_EVENT_ID: &str = "stale0000";
+    /// モックの一覧APIが2ページに分けてイベントを返すカレンダーID
+    pub const MOVE_SOURCE_CALENDAR_ID: &str = "move_source";
+    /// モックの移動APIが403を返すイベントID
+    pub const MOVE_FORBIDDEN_EVENT_ID: &str = "forbidden0000";
+    /// モックのインポートAPIが400を返す iCalUID
+    pub const REJECTED_ICAL_UID: &str = "rejected@example.com";
+    /// モックの停止APIが404を返すチャネルID
+    pub const STOP_FAILURE_CHANNEL_ID: &str = "stop-failure-channel";
+
     pub fn create_test_event() -> Event {
         let now = Utc::now();
         Event::new(
@@ -11,8 +36,112 @@ pub mod test_utils {
             now + Duration::hours(1),
             Some("テストの説明".to_string()),
             None,
-            None, // デフォルトでUTCを使用
+            None, // 既定のタイムゾーン（未設定のためUTC）を使用
         )
         .expect("テストイベントの作成に失敗")
     }
+
+    pub fn create_test_event_with_conference() -> Event {
+        let mut event = create_test_event();
+        event.id = Some(CONFERENCE_EVENT_ID.to_string());
+        let mut conference =
+            ConferenceData::google_meet().with_entry_point(EntryPoint::video(MEET_URI));
+        if let Some(request) = conference.create_request.as_mut() {
+            request.status = Some(ConferenceRequestStatus {
+                status_code: ConferenceStatusCode::Success,
+            });
+        }
+        event.conference_data = Some(conference);
+        event.hangout_link = Some(MEET_URI.to_string());
+        event
+    }
+
+    const MEET_URI: &str = "https://meet.google.com/aaa-bbbb-ccc";
+
+    pub fn create_test_event_list() -> EventList {
+        let mut event = create_test_event();
+        event.id = Some("mock_event_1".to_string());
+        EventList {
+            items: vec![event],
+            next_page_token: None,
+            next_sync_token: Some("mock_sync_token".to_string()),
+            time_zone: Some("UTC".to_string()),
+        }
+    }
+
+    /// `MOVE_SOURCE_CALENDAR_ID` のイベント一覧のページ。`page_token` が None の場合は1ページ目
+    pub fn create_test_move_source_page(page_token: Option<&str>) -> EventList {
+        let event = |id: &str| {
+            let mut event = create_test_event();
+            event.id = Some(id.to_string());
+            event
+        };
+        match page_token {
+            None => EventList {
+                items: vec![event("move0001"), event("move0002")],
+                next_page_token: Some("page2".to_string()),
+                ..Default::default()
+            },
+            Some(_) => EventList {
+                items: vec![event("move0003"), event(MOVE_FORBIDDEN_EVENT_ID)],
+                ..Default::default()
+            },
+        }
+    }
+
+    /// タイムゾーンが Asia/Tokyo のユーザー設定
+    pub fn create_test_settings() -> SettingList {
+        let setting = |id: &str, value: &str| Setting {
+            id: id.to_string(),
+            value: value.to_string(),
+            etag: None,
+        };
+        SettingList {
+            items: vec![
+                setting("timezone", "Asia/Tokyo"),
+                setting("weekStart", "1"),
+                setting("format24HourTime", "true"),
+                setting("locale", "ja"),
+                setting("defaultEventLength", "30"),
+                setting("hideWeekends", "false"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    /// 既定のイベントの色と、カレンダーの色2色を持つパレット
+    pub fn create_test_colors() -> Colors {
+        let color = |background: String| ColorDefinition {
+            background,
+            foreground: "#1d1d1d".to_string(),
+        };
+        Colors {
+            updated: None,
+            calendar: [("1", "#ac725e"), ("2", "#d06b64")]
+                .into_iter()
+                .map(|(id, bg)| (id.to_string(), color(bg.to_string())))
+                .collect(),
+            event: EventColor::ALL
+                .into_iter()
+                .map(|c| {
+                    (
+                        c.id().to_string(),
+                        color(c.default_background().to_string()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn create_test_calendar_list_entry(calendar_id: &str) -> CalendarListEntry {
+        CalendarListEntry {
+            id: calendar_id.to_string(),
+            summary: Some("テストカレンダー".to_string()),
+            time_zone: Some("Asia/Tokyo".to_string()),
+            access_role: Some("owner".to_string()),
+            primary: calendar_id == "primary",
+            default_reminders: vec![Reminder::popup(10)],
+            ..Default::default()
+        }
+    }
 }
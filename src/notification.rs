@@ -0,0 +1,539 @@
+use crate::calendar_client::CalendarClient;
+use crate::channel::Channel;
+use crate::error::{GCalError, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+pub const HEADER_CHANNEL_ID: &str = "X-Goog-Channel-ID";
+pub const HEADER_CHANNEL_TOKEN: &str = "X-Goog-Channel-Token";
+pub const HEADER_CHANNEL_EXPIRATION: &str = "X-Goog-Channel-Expiration";
+pub const HEADER_RESOURCE_STATE: &str = "X-Goog-Resource-State";
+pub const HEADER_RESOURCE_ID: &str = "X-Goog-Resource-ID";
+pub const HEADER_RESOURCE_URI: &str = "X-Goog-Resource-URI";
+pub const HEADER_MESSAGE_NUMBER: &str = "X-Goog-Message-Number";
+
+/// 通知の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceState {
+    /// チャネル作成直後に送られる確認用の通知
+    Sync,
+    /// 監視対象のリソースが変更された
+    Exists,
+    /// 監視対象のリソースが存在しなくなった
+    NotExists,
+}
+
+impl std::str::FromStr for ResourceState {
+    type Err = GCalError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sync" => Ok(ResourceState::Sync),
+            "exists" => Ok(ResourceState::Exists),
+            "not_exists" => Ok(ResourceState::NotExists),
+            other => Err(GCalError::validation(format!(
+                "不明なリソース状態です: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Webhookで受信したプッシュ通知
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushNotification {
+    pub channel_id: String,
+    pub channel_token: Option<String>,
+    pub channel_expiration: Option<DateTime<Utc>>,
+    pub resource_state: ResourceState,
+    pub resource_id: Option<String>,
+    pub resource_uri: Option<String>,
+    pub message_number: Option<u64>,
+}
+
+impl PushNotification {
+    /// ヘッダー名から値を引く関数を受け取り、通知を組み立てます
+    ///
+    /// HTTPサーバーの実装に依存しないよう、ヘッダーの取得方法は呼び出し側に任せます。
+    pub fn from_headers<'a, F>(header: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<&'a str>,
+    {
+        let required = |name: &str| {
+            header(name)
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| GCalError::validation(format!("{} ヘッダーがありません", name)))
+        };
+
+        let channel_id = required(HEADER_CHANNEL_ID)?.to_string();
+        let resource_state = required(HEADER_RESOURCE_STATE)?.parse()?;
+        let message_number = header(HEADER_MESSAGE_NUMBER)
+            .map(|v| {
+                v.parse::<u64>()
+                    .map_err(|e| GCalError::validation(format!("{}: {}", HEADER_MESSAGE_NUMBER, e)))
+            })
+            .transpose()?;
+        // 例: "Tue, 19 Nov 2013 01:13:52 GMT"
+        let channel_expiration = header(HEADER_CHANNEL_EXPIRATION)
+            .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        Ok(PushNotification {
+            channel_id,
+            channel_token: header(HEADER_CHANNEL_TOKEN).map(str::to_string),
+            channel_expiration,
+            resource_state,
+            resource_id: header(HEADER_RESOURCE_ID).map(str::to_string),
+            resource_uri: header(HEADER_RESOURCE_URI).map(str::to_string),
+            message_number,
+        })
+    }
+}
+
+/// 受信を許可するチャネルとトークンの一覧
+///
+/// 登録されていないチャネルや、トークンが一致しない通知は拒否されます。
+#[derive(Debug, Clone, Default)]
+pub struct ChannelRegistry {
+    channels: Arc<RwLock<HashMap<String, Option<String>>>>,
+}
+
+impl ChannelRegistry {
+    pub fn new() -> Self {
+        ChannelRegistry::default()
+    }
+
+    pub fn register(&self, channel: &Channel) {
+        self.channels
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(channel.id.clone(), channel.token.clone());
+    }
+
+    pub fn unregister(&self, channel_id: &str) {
+        self.channels
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(channel_id);
+    }
+
+    pub fn contains(&self, channel_id: &str) -> bool {
+        self.channels
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(channel_id)
+    }
+
+    /// 通知が登録済みチャネルからのもので、トークンが一致するかを検証します
+    pub fn verify(&self, notification: &PushNotification) -> Result<()> {
+        let channels = self.channels.read().unwrap_or_else(PoisonError::into_inner);
+        let expected = channels.get(&notification.channel_id).ok_or_else(|| {
+            GCalError::AuthError(format!("未登録のチャネルです: {}", notification.channel_id))
+        })?;
+        if expected.is_some() && expected != &notification.channel_token {
+            return Err(GCalError::AuthError(format!(
+                "チャネルトークンが一致しません: {}",
+                notification.channel_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum_support {
+    use super::{ChannelRegistry, PushNotification};
+    use axum::extract::{FromRef, FromRequestParts};
+    use axum::http::request::Parts;
+    use axum::http::StatusCode;
+
+    /// ハンドラーの引数に `PushNotification` を指定すると、ヘッダーの解析と
+    /// チャネルトークンの検証を行った通知を受け取れます。
+    /// アプリケーションの状態から `ChannelRegistry` を取得できる必要があります。
+    #[axum::async_trait]
+    impl<S> FromRequestParts<S> for PushNotification
+    where
+        S: Send + Sync,
+        ChannelRegistry: FromRef<S>,
+    {
+        type Rejection = (StatusCode, String);
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            state: &S,
+        ) -> std::result::Result<Self, Self::Rejection> {
+            let notification = PushNotification::from_headers(|name| {
+                parts.headers.get(name).and_then(|v| v.to_str().ok())
+            })
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+            ChannelRegistry::from_ref(state)
+                .verify(&notification)
+                .map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()))?;
+
+            Ok(notification)
+        }
+    }
+}
+
+/// チャネルの自動更新中に発生したエラー
+#[derive(Debug)]
+pub enum RenewalError {
+    /// チャネルの張り直しに失敗した
+    Renew {
+        calendar_id: String,
+        error: GCalError,
+    },
+    /// 置き換えられたチャネルの停止に失敗した（期限切れで自然に無効になります）
+    Stop {
+        channel_id: String,
+        error: GCalError,
+    },
+}
+
+impl std::fmt::Display for RenewalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenewalError::Renew { calendar_id, error } => {
+                write!(
+                    f,
+                    "チャネルの更新に失敗しました ({}): {}",
+                    calendar_id, error
+                )
+            }
+            RenewalError::Stop { channel_id, error } => {
+                write!(
+                    f,
+                    "チャネルの停止に失敗しました ({}): {}",
+                    channel_id, error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenewalError {}
+
+type ErrorHandler = Box<dyn Fn(RenewalError) + Send + Sync>;
+
+/// 期限切れ前にチャネルを張り直す管理役
+///
+/// 監視中のカレンダーごとにチャネルを保持し、期限の `margin` 前になると
+/// 新しいチャネルで events.watch をやり直して古いチャネルを停止します。
+/// 失敗は `with_error_handler` で指定したハンドラーに通知されます。
+pub struct ChannelRenewalManager {
+    client: Arc<CalendarClient>,
+    registry: ChannelRegistry,
+    margin: Duration,
+    watches: Mutex<HashMap<String, Channel>>,
+    error_handler: Option<ErrorHandler>,
+}
+
+impl ChannelRenewalManager {
+    pub fn new(client: Arc<CalendarClient>, registry: ChannelRegistry) -> Self {
+        ChannelRenewalManager {
+            client,
+            registry,
+            margin: Duration::hours(1),
+            watches: Mutex::new(HashMap::new()),
+            error_handler: None,
+        }
+    }
+
+    pub fn with_margin(mut self, margin: Duration) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// バックグラウンドでの更新や古いチャネルの停止に失敗したときに呼び出すハンドラーを指定します
+    pub fn with_error_handler(
+        mut self,
+        handler: impl Fn(RenewalError) + Send + Sync + 'static,
+    ) -> Self {
+        self.error_handler = Some(Box::new(handler));
+        self
+    }
+
+    fn report(&self, error: RenewalError) {
+        if let Some(handler) = &self.error_handler {
+            handler(error);
+        }
+    }
+
+    pub fn registry(&self) -> &ChannelRegistry {
+        &self.registry
+    }
+
+    /// カレンダーの監視を開始し、チャネルを登録します
+    pub async fn watch(&self, calendar_id: &str, channel: Channel) -> Result<Channel> {
+        let created = self.client.watch_events(calendar_id, &channel).await?;
+        self.registry.register(&created);
+        let replaced = self
+            .watches
+            .lock()
+            .await
+            .insert(calendar_id.to_string(), created.clone());
+        if let Some(old) = replaced {
+            self.retire(&old).await;
+        }
+        Ok(created)
+    }
+
+    /// カレンダーの監視を停止します
+    pub async fn unwatch(&self, calendar_id: &str) -> Result<()> {
+        let removed = self.watches.lock().await.remove(calendar_id);
+        if let Some(channel) = removed {
+            self.registry.unregister(&channel.id);
+            self.client.stop_channel(&channel).await?;
+        }
+        Ok(())
+    }
+
+    /// 監視中のチャネル一覧（カレンダーID, チャネル）
+    pub async fn channels(&self) -> Vec<(String, Channel)> {
+        self.watches
+            .lock()
+            .await
+            .iter()
+            .map(|(id, ch)| (id.clone(), ch.clone()))
+            .collect()
+    }
+
+    /// 期限が近いチャネルを張り直し、カレンダーごとの結果を返します
+    pub async fn renew_due(&self, now: DateTime<Utc>) -> Vec<(String, Result<Channel>)> {
+        let due: Vec<(String, Channel)> = self
+            .channels()
+            .await
+            .into_iter()
+            .filter(|(_, ch)| ch.expires_within(now, self.margin))
+            .collect();
+
+        let mut results = Vec::with_capacity(due.len());
+        for (calendar_id, old) in due {
+            let result = self.watch(&calendar_id, old.renewed()).await;
+            results.push((calendar_id, result));
+        }
+        results
+    }
+
+    /// 一定間隔で期限を確認し、チャネルを張り直すバックグラウンドタスクを起動します
+    pub fn spawn(self: Arc<Self>, check_interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(check_interval);
+            loop {
+                ticker.tick().await;
+                for (calendar_id, result) in self.renew_due(Utc::now()).await {
+                    if let Err(error) = result {
+                        self.report(RenewalError::Renew { calendar_id, error });
+                    }
+                }
+            }
+        })
+    }
+
+    /// 置き換えられたチャネルを停止します。停止に失敗しても期限切れで自然に無効になります
+    async fn retire(&self, channel: &Channel) {
+        self.registry.unregister(&channel.id);
+        if channel.resource_id.is_some() {
+            if let Err(error) = self.client.stop_channel(channel).await {
+                self.report(RenewalError::Stop {
+                    channel_id: channel.id.clone(),
+                    error,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_client::HttpClient;
+    use chrono::TimeZone;
+
+    fn headers<'a>(pairs: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<&'a str> {
+        move |name| {
+            pairs
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| *v)
+        }
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let pairs = [
+            ("x-goog-channel-id", "ch-1"),
+            ("x-goog-channel-token", "secret"),
+            ("x-goog-resource-state", "exists"),
+            ("x-goog-resource-id", "res-1"),
+            ("x-goog-message-number", "2"),
+            ("x-goog-channel-expiration", "Tue, 19 Nov 2013 01:13:52 GMT"),
+        ];
+        let n = PushNotification::from_headers(headers(&pairs)).unwrap();
+        assert_eq!(n.channel_id, "ch-1");
+        assert_eq!(n.resource_state, ResourceState::Exists);
+        assert_eq!(n.message_number, Some(2));
+        assert_eq!(
+            n.channel_expiration,
+            Some(Utc.with_ymd_and_hms(2013, 11, 19, 1, 13, 52).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_from_headers_missing_or_invalid() {
+        let pairs = [("x-goog-resource-state", "exists")];
+        assert!(PushNotification::from_headers(headers(&pairs)).is_err());
+
+        let pairs = [
+            ("x-goog-channel-id", "ch-1"),
+            ("x-goog-resource-state", "unknown"),
+        ];
+        assert!(PushNotification::from_headers(headers(&pairs)).is_err());
+    }
+
+    #[test]
+    fn test_registry_verify() {
+        let registry = ChannelRegistry::new();
+        let channel = Channel::web_hook("https://example.com/hook")
+            .with_id("ch-1")
+            .with_token("secret");
+        registry.register(&channel);
+
+        let pairs = [
+            ("x-goog-channel-id", "ch-1"),
+            ("x-goog-channel-token", "secret"),
+            ("x-goog-resource-state", "sync"),
+        ];
+        let n = PushNotification::from_headers(headers(&pairs)).unwrap();
+        assert!(registry.verify(&n).is_ok());
+
+        let forged = PushNotification {
+            channel_token: Some("wrong".to_string()),
+            ..n.clone()
+        };
+        assert!(matches!(
+            registry.verify(&forged),
+            Err(GCalError::AuthError(_))
+        ));
+
+        registry.unregister("ch-1");
+        assert!(registry.verify(&n).is_err());
+    }
+
+    #[test]
+    fn test_registry_survives_poisoned_lock() {
+        let registry = ChannelRegistry::new();
+        let shared = registry.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = shared.channels.write().unwrap();
+            panic!("ロックを保持したままパニック");
+        })
+        .join();
+        assert!(registry.channels.is_poisoned());
+
+        let channel = Channel::web_hook("https://example.com/hook").with_id("ch-1");
+        registry.register(&channel);
+        assert!(registry.contains("ch-1"));
+        registry.unregister("ch-1");
+        assert!(!registry.contains("ch-1"));
+    }
+
+    #[tokio::test]
+    async fn test_renewal_manager_renews_expiring_channel() {
+        let client = Arc::new(CalendarClient::new(
+            HttpClient::mock().expect("failed to create mock client"),
+        ));
+        let manager = ChannelRenewalManager::new(client, ChannelRegistry::new());
+        let exp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let channel = Channel::web_hook("https://example.com/hook")
+            .with_token("secret")
+            .with_expiration(exp);
+        let first = manager.watch("primary", channel).await.unwrap();
+        assert!(manager.registry().contains(&first.id));
+
+        // 期限まで十分ある場合は何もしない
+        assert!(manager.renew_due(exp - Duration::hours(5)).await.is_empty());
+
+        let results = manager.renew_due(exp - Duration::minutes(30)).await;
+        assert_eq!(results.len(), 1);
+        let renewed = results[0].1.as_ref().unwrap();
+        assert_ne!(renewed.id, first.id);
+        assert!(manager.registry().contains(&renewed.id));
+        assert!(!manager.registry().contains(&first.id));
+    }
+
+    #[tokio::test]
+    async fn test_renewal_manager_reports_stop_failure() {
+        use crate::mock::test_utils::STOP_FAILURE_CHANNEL_ID;
+
+        let client = Arc::new(CalendarClient::new(
+            HttpClient::mock().expect("failed to create mock client"),
+        ));
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let manager = ChannelRenewalManager::new(client, ChannelRegistry::new())
+            .with_error_handler(move |e| sink.lock().unwrap().push(e));
+        let exp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        // 停止リクエストがモックで失敗するチャネル
+        let mut channel = Channel::web_hook("https://example.com/hook")
+            .with_id(STOP_FAILURE_CHANNEL_ID)
+            .with_expiration(exp);
+        channel.resource_id = Some("res-1".to_string());
+        manager
+            .watches
+            .lock()
+            .await
+            .insert("primary".to_string(), channel);
+
+        let results = manager.renew_due(exp - Duration::minutes(30)).await;
+        assert!(results[0].1.is_ok());
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        assert!(matches!(
+            &reported[0],
+            RenewalError::Stop { channel_id, .. } if channel_id == STOP_FAILURE_CHANNEL_ID
+        ));
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn test_axum_extractor() {
+        use axum::extract::FromRequestParts;
+        use axum::http::{Request, StatusCode};
+
+        let registry = ChannelRegistry::new();
+        registry.register(
+            &Channel::web_hook("https://example.com/hook")
+                .with_id("ch-1")
+                .with_token("secret"),
+        );
+
+        let request = Request::builder()
+            .header(HEADER_CHANNEL_ID, "ch-1")
+            .header(HEADER_CHANNEL_TOKEN, "secret")
+            .header(HEADER_RESOURCE_STATE, "exists")
+            .body(())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+        let n = PushNotification::from_request_parts(&mut parts, &registry)
+            .await
+            .unwrap();
+        assert_eq!(n.resource_state, ResourceState::Exists);
+
+        let request = Request::builder()
+            .header(HEADER_CHANNEL_ID, "ch-1")
+            .header(HEADER_CHANNEL_TOKEN, "wrong")
+            .header(HEADER_RESOURCE_STATE, "exists")
+            .body(())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+        let rejection = PushNotification::from_request_parts(&mut parts, &registry)
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.0, StatusCode::UNAUTHORIZED);
+    }
+}
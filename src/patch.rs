@@ -0,0 +1,370 @@
+use crate::attachment::{self, Attachment};
+use crate::attendee::Attendee;
+use crate::colors::EventColor;
+use crate::conference::ConferenceData;
+use crate::event::{Event, EventDateTime, ExtendedProperties};
+use crate::event_type::{
+    EventType, FocusTimeProperties, OutOfOfficeProperties, WorkingLocationProperties,
+};
+use crate::reminders::{self, Reminders};
+use crate::validation::ValidationErrors;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// PATCHリクエストにおける1つのフィールドの状態
+///
+/// `Absent` は送信されず（変更なし）、`Null` は `null` として送信され（値を消去）、
+/// `Value` は指定した値で更新します。構造体のフィールドとして使う場合は
+/// `#[serde(default, skip_serializing_if = "Patch::is_absent")]` を付けてください。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    /// 変更しない
+    #[default]
+    Absent,
+    /// 値を消去する
+    Null,
+    /// 値を設定する
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Patch::Absent)
+    }
+
+    pub fn as_value(&self) -> Option<&T> {
+        match self {
+            Patch::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    /// `None` は消去、`Some` は設定として扱います
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => Patch::Value(v),
+            None => Patch::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Patch::Value(v) => v.serialize(serializer),
+            Patch::Absent | Patch::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    /// フィールドが存在しない場合は `#[serde(default)]` により `Absent` になります
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Option::<T>::deserialize(deserializer)?.into())
+    }
+}
+
+/// イベントの部分更新（events.patch）の内容
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPatch {
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub status: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub summary: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub description: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub location: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub color_id: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub start: Patch<EventDateTime>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub end: Patch<EventDateTime>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub sequence: Patch<u32>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub attendees: Patch<Vec<Attendee>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub recurrence: Patch<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub extended_properties: Patch<ExtendedProperties>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub reminders: Patch<Reminders>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub conference_data: Patch<ConferenceData>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub attachments: Patch<Vec<Attachment>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub event_type: Patch<EventType>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub out_of_office_properties: Patch<OutOfOfficeProperties>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub focus_time_properties: Patch<FocusTimeProperties>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub working_location_properties: Patch<WorkingLocationProperties>,
+    /// 型として定義されていないフィールド。`null` を指定すると消去します
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl EventPatch {
+    pub fn new() -> Self {
+        EventPatch::default()
+    }
+
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Patch::Value(summary.into());
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Patch::Value(description.into());
+        self
+    }
+
+    pub fn clear_description(mut self) -> Self {
+        self.description = Patch::Null;
+        self
+    }
+
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Patch::Value(location.into());
+        self
+    }
+
+    pub fn clear_location(mut self) -> Self {
+        self.location = Patch::Null;
+        self
+    }
+
+    pub fn with_color(mut self, color: EventColor) -> Self {
+        self.color_id = Patch::Value(color.id().to_string());
+        self
+    }
+
+    /// 色をカレンダーの色に戻します
+    pub fn clear_color(mut self) -> Self {
+        self.color_id = Patch::Null;
+        self
+    }
+
+    pub fn with_time(mut self, start: EventDateTime, end: EventDateTime) -> Self {
+        self.start = Patch::Value(start);
+        self.end = Patch::Value(end);
+        self
+    }
+
+    pub fn with_reminders(mut self, reminders: Reminders) -> Self {
+        self.reminders = Patch::Value(reminders);
+        self
+    }
+
+    pub fn with_conference_data(mut self, conference_data: ConferenceData) -> Self {
+        self.conference_data = Patch::Value(conference_data);
+        self
+    }
+
+    /// 変更内容が1つもない場合に true を返します
+    pub fn is_empty(&self) -> bool {
+        serde_json::to_value(self)
+            .map(|v| v.as_object().is_some_and(|o| o.is_empty()))
+            .unwrap_or(false)
+    }
+
+    /// `before` を `after` に変更するための最小のパッチを作成します
+    ///
+    /// 値が等しいフィールドは含まれず、`after` で消えたフィールドは `null` になります。
+    pub fn diff(before: &Event, after: &Event) -> Self {
+        let mut extra = serde_json::Map::new();
+        for (key, value) in &after.extra {
+            if before.extra.get(key) != Some(value) {
+                extra.insert(key.clone(), value.clone());
+            }
+        }
+        for key in before.extra.keys() {
+            if !after.extra.contains_key(key) {
+                extra.insert(key.clone(), serde_json::Value::Null);
+            }
+        }
+
+        EventPatch {
+            status: diff_field(&before.status, &after.status),
+            summary: diff_field(&before.summary, &after.summary),
+            description: diff_field(&before.description, &after.description),
+            location: diff_field(&before.location, &after.location),
+            color_id: diff_field(&before.color_id, &after.color_id),
+            start: diff_field(&before.start, &after.start),
+            end: diff_field(&before.end, &after.end),
+            sequence: diff_field(&before.sequence, &after.sequence),
+            attendees: diff_list(&before.attendees, &after.attendees),
+            recurrence: diff_list(&before.recurrence, &after.recurrence),
+            extended_properties: diff_field(
+                &before.extended_properties,
+                &after.extended_properties,
+            ),
+            reminders: diff_field(&before.reminders, &after.reminders),
+            conference_data: diff_field(&before.conference_data, &after.conference_data),
+            attachments: diff_list(&before.attachments, &after.attachments),
+            event_type: diff_field(&before.event_type, &after.event_type),
+            out_of_office_properties: diff_field(
+                &before.out_of_office_properties,
+                &after.out_of_office_properties,
+            ),
+            focus_time_properties: diff_field(
+                &before.focus_time_properties,
+                &after.focus_time_properties,
+            ),
+            working_location_properties: diff_field(
+                &before.working_location_properties,
+                &after.working_location_properties,
+            ),
+            extra,
+        }
+    }
+
+    /// 設定する値のうち、単独で検証できるものを検証し、見つかったエラーをフィールドのパスとともに返します
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Patch::Null = self.summary {
+            errors.add("summary", "イベントタイトル(summary)は消去できません");
+        }
+        if let Some(event_type) = self.event_type.as_value().filter(|t| !t.is_writable()) {
+            errors.add(
+                "eventType",
+                format!("この種類には変更できません: {}", event_type.as_str()),
+            );
+        }
+        if let Some(properties) = self.extended_properties.as_value() {
+            errors.check("extendedProperties", properties.validate());
+        }
+        if let Some(conference) = self.conference_data.as_value() {
+            errors.check("conferenceData", conference.validate());
+        }
+        if let Some(reminders) = self.reminders.as_value() {
+            reminders::validate_reminders(reminders, &mut errors);
+        }
+        if let Some(attachments) = self.attachments.as_value() {
+            attachment::validate_attachments(attachments, &mut errors);
+        }
+        if let Some(properties) = self.working_location_properties.as_value() {
+            errors.check("workingLocationProperties", properties.validate());
+        }
+        errors.into_result()
+    }
+}
+
+/// JSON表現で比較し、異なる場合のみパッチに含めます
+fn diff_field<T: Serialize + Clone>(before: &Option<T>, after: &Option<T>) -> Patch<T> {
+    let same = match (before, after) {
+        (None, None) => true,
+        (Some(a), Some(b)) => serde_json::to_value(a).ok() == serde_json::to_value(b).ok(),
+        _ => false,
+    };
+    if same {
+        Patch::Absent
+    } else {
+        after.clone().into()
+    }
+}
+
+/// 配列のフィールドは空配列で消去を表します
+fn diff_list<T: PartialEq + Clone>(before: &[T], after: &[T]) -> Patch<Vec<T>> {
+    if before == after {
+        Patch::Absent
+    } else {
+        Patch::Value(after.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::test_utils::create_test_event;
+    use crate::reminders::Reminder;
+
+    #[test]
+    fn test_patch_serialization() {
+        let patch = EventPatch::new()
+            .with_summary("新しいタイトル")
+            .clear_location();
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({"summary": "新しいタイトル", "location": null})
+        );
+
+        let parsed: EventPatch = serde_json::from_str(
+            r#"{"location": null, "description": "説明", "colorId": "3", "visibility": "private"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.location, Patch::Null);
+        assert_eq!(parsed.description, Patch::Value("説明".to_string()));
+        assert_eq!(parsed.summary, Patch::Absent);
+        assert_eq!(parsed.color_id, Patch::Value("3".to_string()));
+        assert_eq!(parsed.extra["visibility"], "private");
+        assert_eq!(
+            serde_json::to_value(EventPatch::new().with_color(EventColor::Tomato)).unwrap(),
+            serde_json::json!({"colorId": "11"})
+        );
+        assert!(EventPatch::new().is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut before = create_test_event();
+        before.location = Some("会議室A".to_string());
+        before.color_id = Some("5".to_string());
+        before
+            .extra
+            .insert("visibility".to_string(), serde_json::json!("private"));
+
+        assert!(EventPatch::diff(&before, &before).is_empty());
+
+        let mut after = before.clone();
+        after.location = None;
+        after.attendees.push(Attendee::new("alice@example.com"));
+        after.reminders = Some(Reminders::with_overrides(vec![Reminder::popup(10)]));
+        after.extra.remove("visibility");
+        after.set_color(EventColor::Peacock);
+
+        let patch = EventPatch::diff(&before, &after);
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({
+                "location": null,
+                "attendees": [{"email": "alice@example.com"}],
+                "reminders": {"useDefault": false, "overrides": [{"method": "popup", "minutes": 10}]},
+                "colorId": "7",
+                "visibility": null
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let patch = EventPatch {
+            summary: Patch::Null,
+            ..Default::default()
+        };
+        assert!(patch.validate().is_err());
+        let patch = EventPatch::new()
+            .with_reminders(Reminders::with_overrides(vec![Reminder::popup(1); 6]));
+        assert!(patch
+            .validate()
+            .unwrap_err()
+            .contains("reminders.overrides"));
+        let patch = EventPatch {
+            attachments: Patch::Value(vec![Attachment::new(""); 26]),
+            ..Default::default()
+        };
+        assert!(patch.validate().is_err());
+        let patch = EventPatch {
+            event_type: Patch::Value(EventType::Other("someFutureType".to_string())),
+            ..Default::default()
+        };
+        assert!(patch.validate().is_err());
+        assert!(EventPatch::new().clear_location().validate().is_ok());
+    }
+}
@@ -0,0 +1,816 @@
+use crate::event_builder::EventBuilder;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Weekday};
+use chrono_tz::Tz;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QuickAddError {
+    #[error("日付または時刻が見つかりません")]
+    MissingDateTime,
+    #[error("タイトルが見つかりません")]
+    MissingSummary,
+    #[error("{0}が複数指定されています")]
+    Duplicate(&'static str),
+    #[error("存在しない日付です: {0}")]
+    InvalidDate(String),
+    #[error("存在しない時刻です: {0}")]
+    InvalidTime(String),
+    #[error("終了時刻と長さは同時に指定できません")]
+    EndAndDuration,
+    #[error("時刻のない予定に長さは指定できません")]
+    DurationWithoutTime,
+}
+
+/// タイムゾーンの略称と対応する地域
+///
+/// 略称には複数の地域で使われるものがあるため、代表的な地域に対応付けます。夏時間は地域の規則に従います。
+const ZONE_ABBREVIATIONS: &[(&str, Tz)] = &[
+    ("JST", Tz::Asia__Tokyo),
+    ("KST", Tz::Asia__Seoul),
+    ("UTC", Tz::UTC),
+    ("GMT", Tz::GMT),
+    ("PST", Tz::America__Los_Angeles),
+    ("PDT", Tz::America__Los_Angeles),
+    ("PT", Tz::America__Los_Angeles),
+    ("MST", Tz::America__Denver),
+    ("MDT", Tz::America__Denver),
+    ("CST", Tz::America__Chicago),
+    ("CDT", Tz::America__Chicago),
+    ("EST", Tz::America__New_York),
+    ("EDT", Tz::America__New_York),
+    ("ET", Tz::America__New_York),
+    ("BST", Tz::Europe__London),
+    ("CET", Tz::Europe__Berlin),
+    ("CEST", Tz::Europe__Berlin),
+    ("IST", Tz::Asia__Kolkata),
+    ("SGT", Tz::Asia__Singapore),
+    ("HKT", Tz::Asia__Hong_Kong),
+    ("AEST", Tz::Australia__Sydney),
+    ("AEDT", Tz::Australia__Sydney),
+];
+
+const WEEKDAY_NAMES: &[(&str, Weekday)] = &[
+    ("monday", Weekday::Mon),
+    ("mon", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("tues", Weekday::Tue),
+    ("tue", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("wed", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("thurs", Weekday::Thu),
+    ("thur", Weekday::Thu),
+    ("thu", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("fri", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sat", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+    ("sun", Weekday::Sun),
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// 日時などの直後にあれば読み飛ばす助詞
+const PARTICLES: &[&str] = &["の", "に", "は", "、"];
+
+/// 文中から読み取った日時などの要素
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    Date(NaiveDate),
+    Time(NaiveTime, Option<NaiveTime>),
+    Duration(Duration),
+    Zone(Tz),
+}
+
+/// "Standup tomorrow 10:00-10:15 JST" や "来週火曜 15時 定例" のような文からイベントを組み立てるパーサー
+///
+/// APIに送信せずに手元で解釈するため、作成前の確認表示に使えます。日時・長さ・タイムゾーンとして
+/// 読み取れなかった部分がタイトルになります。
+///
+/// - 日付: "today" / "tomorrow" / "next tue" / "May 1" / "2024-05-01" / "5/1" / "今日" / "明日" /
+///   "明後日" / "来週火曜" / "金曜日" / "5月1日" / "2024年5月1日"
+/// - 時刻: "10:00" / "3pm" / "10:00-10:15" / "15時" / "15時半" / "午後3時" / "15時から16時"
+/// - 長さ: "for 30 min" / "2h" / "1時間" / "30分間"
+/// - タイムゾーン: "JST" などの略称、IANA名、"日本時間"
+///
+/// "next tue" と "来週火曜" は翌週（月曜始まり）の曜日、曜日のみの場合は今日以降で最も近い日を表します。
+/// 日付のない時刻は今日、ただし既に過ぎている場合は翌日になります。時刻のない日付は終日イベントになります。
+#[derive(Debug, Clone)]
+pub struct QuickAddParser {
+    now: DateTime<Tz>,
+    default_duration: Duration,
+}
+
+impl QuickAddParser {
+    /// `now` を基準に相対的な日付を解釈します。タイムゾーンの指定がない場合は `now` のタイムゾーンを使用します
+    pub fn new(now: DateTime<Tz>) -> Self {
+        QuickAddParser {
+            now,
+            default_duration: Duration::hours(1),
+        }
+    }
+
+    /// 終了時刻も長さも指定されていない場合の長さ（既定は1時間）
+    pub fn with_default_duration(mut self, duration: Duration) -> Self {
+        self.default_duration = duration;
+        self
+    }
+
+    /// 文を解釈してイベントのビルダーを返します
+    pub fn parse(&self, text: &str) -> Result<EventBuilder, QuickAddError> {
+        let text = normalize(text);
+        let today = self.now.date_naive();
+        let mut summary = String::new();
+        let mut date = None;
+        let mut time = None;
+        let mut duration = None;
+        let mut zone = None;
+
+        let mut i = 0;
+        while i < text.len() {
+            let rest = &text[i..];
+            let word_start = !text[..i]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric());
+            let token = if word_start {
+                self.token(rest, today)?
+            } else {
+                None
+            };
+            let Some((token, len)) = token else {
+                let c = rest.chars().next().unwrap_or_default();
+                summary.push(c);
+                i += c.len_utf8();
+                continue;
+            };
+            match token {
+                Token::Date(d) => set_once(&mut date, d, "日付")?,
+                Token::Time(start, end) => set_once(&mut time, (start, end), "時刻")?,
+                Token::Duration(d) => set_once(&mut duration, d, "長さ")?,
+                Token::Zone(tz) => set_once(&mut zone, tz, "タイムゾーン")?,
+            }
+            i += len;
+            if let Some(particle) = PARTICLES.iter().find(|p| text[i..].starts_with(**p)) {
+                i += particle.len();
+            }
+            summary.push(' ');
+        }
+
+        let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+        let summary = summary.trim_matches(|c: char| matches!(c, ',' | '、' | '-' | '・'));
+        if summary.trim().is_empty() {
+            return Err(QuickAddError::MissingSummary);
+        }
+        let builder = EventBuilder::new(summary.trim());
+        let zone = zone.unwrap_or(self.now.timezone());
+
+        let (start, end) = match (date, time) {
+            (None, None) => return Err(QuickAddError::MissingDateTime),
+            (Some(date), None) => {
+                if duration.is_some() {
+                    return Err(QuickAddError::DurationWithoutTime);
+                }
+                return Ok(builder.all_day(date));
+            }
+            (_, Some(time)) => time,
+        };
+        let date = match date {
+            Some(date) => date,
+            None if local(zone, today, start)? < self.now => today + Duration::days(1),
+            None => today,
+        };
+        let builder = builder.start(local(zone, date, start)?);
+        match (end, duration) {
+            (Some(_), Some(_)) => Err(QuickAddError::EndAndDuration),
+            (Some(end), None) => {
+                // 終了時刻が開始時刻以前の場合は翌日に終わる予定とみなします
+                let end_date = if end <= start {
+                    date + Duration::days(1)
+                } else {
+                    date
+                };
+                Ok(builder.end(local(zone, end_date, end)?))
+            }
+            (None, duration) => Ok(builder.duration(duration.unwrap_or(self.default_duration))),
+        }
+    }
+
+    /// "on" / "at" / "from" に続く日時も含めて、`s` の先頭の要素を読み取ります
+    fn token(&self, s: &str, today: NaiveDate) -> Result<Option<(Token, usize)>, QuickAddError> {
+        if let Some(token) = self.bare_token(s, today)? {
+            return Ok(Some(token));
+        }
+        for prefix in ["on", "at", "from"] {
+            let Some(rest) = strip_prefix_ci(s, prefix) else {
+                continue;
+            };
+            let spaces = skip_spaces(rest);
+            if spaces == 0 {
+                continue;
+            }
+            if let Some((token @ (Token::Date(_) | Token::Time(..)), len)) =
+                self.bare_token(&rest[spaces..], today)?
+            {
+                return Ok(Some((token, prefix.len() + spaces + len)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn bare_token(
+        &self,
+        s: &str,
+        today: NaiveDate,
+    ) -> Result<Option<(Token, usize)>, QuickAddError> {
+        let token = match zone(s) {
+            Some((tz, len)) => Some((Token::Zone(tz), len)),
+            None => match duration(s) {
+                Some((d, len)) => Some((Token::Duration(d), len)),
+                None => match time_range(s) {
+                    Some((start, end, len)) => Some((Token::Time(start, end), len)),
+                    None => date(s, today)?.map(|(d, len)| (Token::Date(d), len)),
+                },
+            },
+        };
+        // 英数字の途中で終わる場合は単語の一部とみなします
+        Ok(token.filter(|(_, len)| {
+            let last_alnum = s[..*len]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric());
+            !last_alnum || word_end(&s[*len..])
+        }))
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, name: &'static str) -> Result<(), QuickAddError> {
+    if slot.replace(value).is_some() {
+        return Err(QuickAddError::Duplicate(name));
+    }
+    Ok(())
+}
+
+fn local(zone: Tz, date: NaiveDate, time: NaiveTime) -> Result<DateTime<Tz>, QuickAddError> {
+    let local = date.and_time(time);
+    zone.from_local_datetime(&local)
+        .earliest()
+        .ok_or_else(|| QuickAddError::InvalidTime(format!("{} {}", local, zone.name())))
+}
+
+/// 全角の数字・記号・空白を半角にします
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            '：' => ':',
+            '／' => '/',
+            '－' => '-',
+            '　' => ' ',
+            _ => c,
+        })
+        .collect()
+}
+
+fn number(s: &str) -> Option<(u32, usize)> {
+    let len = s.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 || len > 4 {
+        return None;
+    }
+    Some((s[..len].parse().ok()?, len))
+}
+
+fn word_end(s: &str) -> bool {
+    !s.chars().next().is_some_and(|c| c.is_ascii_alphanumeric())
+}
+
+fn skip_spaces(s: &str) -> usize {
+    s.len() - s.trim_start().len()
+}
+
+fn strip_prefix_ci<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    s.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &s[prefix.len()..])
+}
+
+fn zone(s: &str) -> Option<(Tz, usize)> {
+    if s.starts_with("日本時間") {
+        return Some((Tz::Asia__Tokyo, "日本時間".len()));
+    }
+    for (abbreviation, tz) in ZONE_ABBREVIATIONS {
+        if s.strip_prefix(abbreviation).is_some_and(word_end) {
+            return Some((*tz, abbreviation.len()));
+        }
+    }
+    let word = s.split_whitespace().next()?;
+    if word.contains('/') {
+        if let Ok(tz) = word.parse::<Tz>() {
+            return Some((tz, word.len()));
+        }
+    }
+    None
+}
+
+fn duration(s: &str) -> Option<(Duration, usize)> {
+    let mut pos = 0;
+    if let Some(rest) = strip_prefix_ci(s, "for ") {
+        pos = 4 + skip_spaces(rest);
+    }
+    let (n, len) = number(&s[pos..])?;
+    let n = i64::from(n);
+    pos += len;
+    let rest = &s[pos..];
+
+    if let Some(rest) = rest.strip_prefix("時間") {
+        pos += "時間".len();
+        let mut d = Duration::hours(n);
+        if rest.starts_with('半') {
+            d += Duration::minutes(30);
+            pos += '半'.len_utf8();
+        } else if let Some((m, len)) = number(rest) {
+            if rest[len..].starts_with('分') {
+                d += Duration::minutes(i64::from(m));
+                pos += len + '分'.len_utf8();
+            }
+        }
+        return Some((d, pos));
+    }
+    if let Some(rest) = rest.strip_prefix('分') {
+        pos += '分'.len_utf8();
+        if rest.starts_with('間') {
+            pos += '間'.len_utf8();
+        }
+        return Some((Duration::minutes(n), pos));
+    }
+
+    let spaces = skip_spaces(rest);
+    let rest = &rest[spaces..];
+    for (unit, minutes) in [
+        ("minutes", 1),
+        ("minute", 1),
+        ("mins", 1),
+        ("min", 1),
+        ("m", 1),
+        ("hours", 60),
+        ("hour", 60),
+        ("hrs", 60),
+        ("hr", 60),
+        ("h", 60),
+    ] {
+        if strip_prefix_ci(rest, unit).is_some_and(word_end) {
+            return Some((Duration::minutes(n * minutes), pos + spaces + unit.len()));
+        }
+    }
+    None
+}
+
+/// "10:00" / "3pm" / "15時30分" / "午後3時" などの時刻。午前・午後の指定があれば午後かどうかも返します
+fn clock(s: &str) -> Option<(NaiveTime, Option<bool>, usize)> {
+    let mut pos = 0;
+    let mut pm = None;
+    if let Some(rest) = s.strip_prefix("午前") {
+        pm = Some(false);
+        pos = s.len() - rest.len();
+    } else if let Some(rest) = s.strip_prefix("午後") {
+        pm = Some(true);
+        pos = s.len() - rest.len();
+    }
+    let (mut hour, len) = number(&s[pos..])?;
+    if len > 2 {
+        return None;
+    }
+    pos += len;
+    let mut minute = 0;
+    let mut explicit = pm.is_some();
+
+    let rest = &s[pos..];
+    if let Some(rest) = rest.strip_prefix(':') {
+        let (m, len) = number(rest)?;
+        if len != 2 {
+            return None;
+        }
+        minute = m;
+        pos += 1 + len;
+        explicit = true;
+    } else if let Some(rest) = rest.strip_prefix('時') {
+        if rest.starts_with('間') {
+            return None;
+        }
+        pos += '時'.len_utf8();
+        explicit = true;
+        if rest.starts_with('半') {
+            minute = 30;
+            pos += '半'.len_utf8();
+        } else if let Some((m, len)) = number(rest) {
+            if rest[len..].starts_with('分') && !rest[len..].starts_with("分間") {
+                minute = m;
+                pos += len + '分'.len_utf8();
+            }
+        }
+    }
+
+    if pm.is_none() {
+        let spaces = skip_spaces(&s[pos..]);
+        let rest = &s[pos + spaces..];
+        for (suffix, is_pm) in [("a.m.", false), ("p.m.", true), ("am", false), ("pm", true)] {
+            if strip_prefix_ci(rest, suffix).is_some_and(word_end) {
+                pm = Some(is_pm);
+                pos += spaces + suffix.len();
+                explicit = true;
+                break;
+            }
+        }
+    }
+    if !explicit {
+        return None;
+    }
+    if let Some(is_pm) = pm {
+        if hour == 0 || hour > 12 {
+            return None;
+        }
+        hour = hour % 12 + if is_pm { 12 } else { 0 };
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0).map(|t| (t, pm, pos))
+}
+
+/// 時刻、または "10:00-10:15" / "15時から16時まで" のような時間帯
+fn time_range(s: &str) -> Option<(NaiveTime, Option<NaiveTime>, usize)> {
+    let (start, start_pm, mut pos) = clock(s)?;
+    let spaces = skip_spaces(&s[pos..]);
+    let rest = &s[pos + spaces..];
+    let separator = ["-", "~", "〜", "～", "–", "から"]
+        .iter()
+        .find(|sep| rest.starts_with(**sep))
+        .map(|sep| sep.len())
+        .or_else(|| strip_prefix_ci(rest, "to ").map(|_| 3));
+    if let Some(separator) = separator {
+        let after = &rest[separator..];
+        let spaces_after = skip_spaces(after);
+        if let Some((mut end, end_pm, len)) = clock(&after[spaces_after..]) {
+            pos += spaces + separator + spaces_after + len;
+            // "午後3時から4時" / "3pm-4" の終了時刻は開始時刻の午後を引き継ぎます
+            if start_pm == Some(true) && end_pm.is_none() && end.hour() < 12 {
+                let afternoon = end + Duration::hours(12);
+                if afternoon > start {
+                    end = afternoon;
+                }
+            }
+            if s[pos..].starts_with("まで") {
+                pos += "まで".len();
+            }
+            return Some((start, Some(end), pos));
+        }
+        if rest.starts_with("から") {
+            pos += spaces + "から".len();
+        }
+    }
+    Some((start, None, pos))
+}
+
+fn date(s: &str, today: NaiveDate) -> Result<Option<(NaiveDate, usize)>, QuickAddError> {
+    if let Some((n, len)) = number(s) {
+        return numeric_date(s, n, len, today);
+    }
+    for (words, days) in [
+        (&["今日", "本日", "today"][..], 0),
+        (&["明後日", "あさって"][..], 2),
+        (&["明日", "あした", "あす", "tomorrow"][..], 1),
+    ] {
+        for word in words {
+            if strip_prefix_ci(s, word).is_some_and(word_end) {
+                return Ok(Some((today + Duration::days(days), word.len())));
+            }
+        }
+    }
+    if let Some(found) = japanese_weekday(s, today) {
+        return Ok(Some(found));
+    }
+    if let Some(found) = english_weekday(s, today) {
+        return Ok(Some(found));
+    }
+    english_month_day(s, today)
+}
+
+/// "2024-05-01" / "2024/5/1" / "2024年5月1日" / "5/1" / "5月1日"
+fn numeric_date(
+    s: &str,
+    first: u32,
+    len: usize,
+    today: NaiveDate,
+) -> Result<Option<(NaiveDate, usize)>, QuickAddError> {
+    let (year, month_start) = if len == 4 {
+        let rest = &s[len..];
+        let Some(sep) = ["-", "/", "年"].iter().find(|sep| rest.starts_with(**sep)) else {
+            return Ok(None);
+        };
+        (Some(first as i32), len + sep.len())
+    } else {
+        (None, 0)
+    };
+    let Some((month, month_len)) = number(&s[month_start..]).filter(|(_, len)| *len <= 2) else {
+        return Ok(None);
+    };
+    let mut pos = month_start + month_len;
+    let rest = &s[pos..];
+    let Some(sep) = ["-", "/", "月"].iter().find(|sep| rest.starts_with(**sep)) else {
+        return Ok(None);
+    };
+    if year.is_none() && *sep == "-" {
+        return Ok(None);
+    }
+    pos += sep.len();
+    let Some((day, day_len)) = number(&s[pos..]).filter(|(_, len)| *len <= 2) else {
+        return Ok(None);
+    };
+    pos += day_len;
+    if *sep == "月" {
+        if !s[pos..].starts_with('日') {
+            return Ok(None);
+        }
+        pos += '日'.len_utf8();
+    } else if s[pos..].starts_with('/') {
+        return Ok(None);
+    }
+    resolve_month_day(year, month, day, &s[..pos], today).map(|d| Some((d, pos)))
+}
+
+/// 年の指定がない場合は今日以降で最も近い日付にします
+fn resolve_month_day(
+    year: Option<i32>,
+    month: u32,
+    day: u32,
+    text: &str,
+    today: NaiveDate,
+) -> Result<NaiveDate, QuickAddError> {
+    let invalid = || QuickAddError::InvalidDate(text.to_string());
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid),
+        None => {
+            let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+            match this_year {
+                Some(date) if date >= today => Ok(date),
+                _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day).ok_or_else(invalid),
+            }
+        }
+    }
+}
+
+/// "来週火曜" / "今週の金曜日" / "水曜"
+fn japanese_weekday(s: &str, today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let mut pos = 0;
+    let mut weeks = None;
+    for (prefix, n) in [("再来週", 2), ("来週", 1), ("今週", 0)] {
+        if s.starts_with(prefix) {
+            weeks = Some(n);
+            pos = prefix.len();
+            if s[pos..].starts_with('の') {
+                pos += 'の'.len_utf8();
+            }
+            break;
+        }
+    }
+    let mut chars = s[pos..].chars();
+    let weekday = match chars.next()? {
+        '月' => Weekday::Mon,
+        '火' => Weekday::Tue,
+        '水' => Weekday::Wed,
+        '木' => Weekday::Thu,
+        '金' => Weekday::Fri,
+        '土' => Weekday::Sat,
+        '日' => Weekday::Sun,
+        _ => return None,
+    };
+    if chars.next()? != '曜' {
+        return None;
+    }
+    pos += "月曜".len();
+    if s[pos..].starts_with('日') {
+        pos += '日'.len_utf8();
+    }
+    Some((weekday_date(today, weekday, weeks), pos))
+}
+
+/// "tue" / "next tuesday" / "this friday"
+fn english_weekday(s: &str, today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let mut pos = 0;
+    let mut weeks = None;
+    for (prefix, n) in [("next", Some(1)), ("this", None)] {
+        if let Some(rest) = strip_prefix_ci(s, prefix) {
+            let spaces = skip_spaces(rest);
+            if spaces > 0 {
+                weeks = n;
+                pos = prefix.len() + spaces;
+                break;
+            }
+        }
+    }
+    let (name, weekday) = WEEKDAY_NAMES
+        .iter()
+        .find(|(name, _)| strip_prefix_ci(&s[pos..], name).is_some_and(word_end))?;
+    Some((weekday_date(today, *weekday, weeks), pos + name.len()))
+}
+
+/// `weeks` 週後（月曜始まり）の曜日。None の場合は今日以降で最も近い曜日
+fn weekday_date(today: NaiveDate, weekday: Weekday, weeks: Option<i64>) -> NaiveDate {
+    match weeks {
+        Some(weeks) => {
+            let monday = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+            monday + Duration::days(weeks * 7 + i64::from(weekday.num_days_from_monday()))
+        }
+        None => {
+            let ahead =
+                (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+            today + Duration::days(i64::from(ahead))
+        }
+    }
+}
+
+/// "May 1" / "Jan 15th"
+fn english_month_day(
+    s: &str,
+    today: NaiveDate,
+) -> Result<Option<(NaiveDate, usize)>, QuickAddError> {
+    let found = MONTH_NAMES.iter().enumerate().find_map(|(i, name)| {
+        [*name, &name[..3], "sept"]
+            .into_iter()
+            .filter(|candidate| *candidate != "sept" || i == 8)
+            .find(|candidate| strip_prefix_ci(s, candidate).is_some_and(word_end))
+            .map(|candidate| (i as u32 + 1, candidate.len()))
+    });
+    let Some((month, mut pos)) = found else {
+        return Ok(None);
+    };
+    if s[pos..].starts_with('.') {
+        pos += 1;
+    }
+    let spaces = skip_spaces(&s[pos..]);
+    if spaces == 0 {
+        return Ok(None);
+    }
+    pos += spaces;
+    let Some((day, len)) = number(&s[pos..]).filter(|(_, len)| *len <= 2) else {
+        return Ok(None);
+    };
+    pos += len;
+    if let Some(suffix) = ["st", "nd", "rd", "th"]
+        .iter()
+        .find(|suffix| strip_prefix_ci(&s[pos..], suffix).is_some_and(word_end))
+    {
+        pos += suffix.len();
+    }
+    resolve_month_day(None, month, day, &s[..pos], today).map(|d| Some((d, pos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::{America::New_York, Asia::Tokyo};
+
+    fn parser() -> QuickAddParser {
+        // 2024-05-13 は月曜日
+        QuickAddParser::new(Tokyo.with_ymd_and_hms(2024, 5, 13, 9, 0, 0).unwrap())
+    }
+
+    fn start_end(text: &str) -> (String, String, String) {
+        let event = parser().parse(text).unwrap().build().unwrap();
+        let start = event.start.unwrap();
+        let end = event.end.unwrap();
+        (event.summary.unwrap(), start.date_time, end.date_time)
+    }
+
+    #[test]
+    fn test_parse_english() {
+        assert_eq!(
+            start_end("Standup tomorrow 10:00-10:15 JST"),
+            (
+                "Standup".to_string(),
+                "2024-05-14T10:00:00+09:00".to_string(),
+                "2024-05-14T10:15:00+09:00".to_string()
+            )
+        );
+        assert_eq!(
+            start_end("Lunch with Bob on next Tuesday at 12pm for 90 min"),
+            (
+                "Lunch with Bob".to_string(),
+                "2024-05-21T12:00:00+09:00".to_string(),
+                "2024-05-21T13:30:00+09:00".to_string()
+            )
+        );
+        let event = parser()
+            .parse("Sync fri 9am America/New_York")
+            .unwrap()
+            .build()
+            .unwrap();
+        let start = event.start.unwrap();
+        assert_eq!(start.date_time, "2024-05-17T09:00:00-04:00");
+        assert_eq!(start.time_zone, New_York.name());
+    }
+
+    #[test]
+    fn test_parsed_zones_build() {
+        // 解析できたタイムゾーンはすべてイベントの検証も通る
+        for (abbreviation, tz) in ZONE_ABBREVIATIONS {
+            let text = format!("Standup tomorrow 10:00-10:15 {}", abbreviation);
+            let event = parser().parse(&text).unwrap().build().unwrap();
+            assert_eq!(event.start.unwrap().time_zone, tz.name(), "{}", text);
+        }
+        for name in ["America/Argentina/Buenos_Aires", "Etc/UTC"] {
+            let text = format!("Standup tomorrow 10:00 {}", name);
+            let event = parser().parse(&text).unwrap().build().unwrap();
+            assert_eq!(event.start.unwrap().time_zone, name);
+        }
+    }
+
+    #[test]
+    fn test_parse_japanese() {
+        assert_eq!(
+            start_end("来週火曜 15時 定例"),
+            (
+                "定例".to_string(),
+                "2024-05-21T15:00:00+09:00".to_string(),
+                "2024-05-21T16:00:00+09:00".to_string()
+            )
+        );
+        assert_eq!(
+            start_end("明日の午後３時半から４時まで顧客訪問"),
+            (
+                "顧客訪問".to_string(),
+                "2024-05-14T15:30:00+09:00".to_string(),
+                "2024-05-14T16:00:00+09:00".to_string()
+            )
+        );
+        assert_eq!(
+            start_end("振り返り 金曜日 17時 30分間"),
+            (
+                "振り返り".to_string(),
+                "2024-05-17T17:00:00+09:00".to_string(),
+                "2024-05-17T17:30:00+09:00".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_dates_without_time() {
+        let event = parser().parse("有給 5月20日").unwrap().build().unwrap();
+        assert_eq!(event.start.unwrap().date.as_deref(), Some("2024-05-20"));
+        // 過ぎた日付は翌年
+        let event = parser().parse("Offsite 4/1").unwrap().build().unwrap();
+        assert_eq!(event.start.unwrap().date.as_deref(), Some("2025-04-01"));
+        // 過ぎた時刻は翌日、終了時刻が開始以前の場合は翌日に終わる
+        assert_eq!(
+            start_end("Release 8:00-1:00"),
+            (
+                "Release".to_string(),
+                "2024-05-14T08:00:00+09:00".to_string(),
+                "2024-05-15T01:00:00+09:00".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parser = parser();
+        assert_eq!(
+            parser.parse("定例会議").unwrap_err(),
+            QuickAddError::MissingDateTime
+        );
+        assert_eq!(
+            parser.parse("明日 10時").unwrap_err(),
+            QuickAddError::MissingSummary
+        );
+        assert_eq!(
+            parser.parse("MTG today tomorrow 10am").unwrap_err(),
+            QuickAddError::Duplicate("日付")
+        );
+        assert_eq!(
+            parser.parse("MTG 2月30日").unwrap_err(),
+            QuickAddError::InvalidDate("2月30日".to_string())
+        );
+        assert_eq!(
+            parser.parse("MTG 10:00-11:00 for 2h").unwrap_err(),
+            QuickAddError::EndAndDuration
+        );
+        // 単語の一部は日時として扱わない
+        let event = parser
+            .parse("Monthly review 10am")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(event.summary.as_deref(), Some("Monthly review"));
+    }
+}
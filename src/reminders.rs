@@ -0,0 +1,234 @@
+use crate::validation::ValidationErrors;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 上書きリマインダーの最大件数
+pub const MAX_REMINDER_OVERRIDES: usize = 5;
+/// リマインダーの最大分数（4週間）
+pub const MAX_REMINDER_MINUTES: u32 = 40320;
+
+/// リマインダーの通知方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderMethod {
+    Email,
+    Popup,
+    /// 廃止された方法（sms など）。取得結果の読み込みのためにのみ使用されます
+    #[serde(other)]
+    Unsupported,
+}
+
+/// イベント開始の何分前に通知するか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Reminder {
+    pub method: ReminderMethod,
+    pub minutes: u32,
+}
+
+impl Reminder {
+    pub fn email(minutes: u32) -> Self {
+        Reminder {
+            method: ReminderMethod::Email,
+            minutes,
+        }
+    }
+
+    pub fn popup(minutes: u32) -> Self {
+        Reminder {
+            method: ReminderMethod::Popup,
+            minutes,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.method == ReminderMethod::Unsupported {
+            return Err("リマインダーの方法は email または popup のみ指定できます".to_string());
+        }
+        if self.minutes > MAX_REMINDER_MINUTES {
+            return Err(format!(
+                "リマインダーは0〜{}分前で指定する必要があります: {}",
+                MAX_REMINDER_MINUTES, self.minutes
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// イベントのリマインダー設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reminders {
+    pub use_default: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<Reminder>,
+}
+
+impl Reminders {
+    /// カレンダーのデフォルトリマインダーを使用します
+    pub fn use_default() -> Self {
+        Reminders {
+            use_default: true,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// イベント固有のリマインダーを使用します。空の場合は通知しません
+    pub fn with_overrides(overrides: Vec<Reminder>) -> Self {
+        Reminders {
+            use_default: false,
+            overrides,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        validate_reminders(self, &mut errors);
+        errors.into_result()
+    }
+
+    /// 実際に適用されるリマインダーを返します
+    pub fn effective<'a>(&'a self, calendar_defaults: &'a [Reminder]) -> &'a [Reminder] {
+        if self.use_default {
+            calendar_defaults
+        } else {
+            &self.overrides
+        }
+    }
+}
+
+/// リマインダー設定を検証し、エラーを "reminders" 以下のパスで追加します
+pub(crate) fn validate_reminders(reminders: &Reminders, errors: &mut ValidationErrors) {
+    if reminders.use_default && !reminders.overrides.is_empty() {
+        errors.add(
+            "reminders",
+            "デフォルトリマインダーと上書きリマインダーは同時に指定できません",
+        );
+    }
+    if reminders.overrides.len() > MAX_REMINDER_OVERRIDES {
+        errors.add(
+            "reminders.overrides",
+            format!(
+                "上書きリマインダーは{}件までです: {}",
+                MAX_REMINDER_OVERRIDES,
+                reminders.overrides.len()
+            ),
+        );
+    }
+    for (i, reminder) in reminders.overrides.iter().enumerate() {
+        errors.check(format!("reminders.overrides[{}]", i), reminder.validate());
+    }
+}
+
+/// 通知される日時が確定したリマインダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledReminder {
+    pub method: ReminderMethod,
+    pub at: DateTime<Utc>,
+}
+
+/// 指定した開始日時に対して、リマインダーが通知される日時を早い順に返します
+pub fn schedule(reminders: &[Reminder], occurrence_start: DateTime<Utc>) -> Vec<ScheduledReminder> {
+    let mut scheduled: Vec<ScheduledReminder> = reminders
+        .iter()
+        .filter(|r| r.method != ReminderMethod::Unsupported)
+        .map(|r| ScheduledReminder {
+            method: r.method,
+            at: occurrence_start - Duration::minutes(r.minutes as i64),
+        })
+        .collect();
+    scheduled.sort_by_key(|r| r.at);
+    scheduled.dedup();
+    scheduled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_serialization() {
+        let reminders = Reminders::with_overrides(vec![Reminder::popup(10), Reminder::email(60)]);
+        let json = serde_json::to_value(&reminders).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "useDefault": false,
+                "overrides": [
+                    {"method": "popup", "minutes": 10},
+                    {"method": "email", "minutes": 60}
+                ]
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(Reminders::use_default()).unwrap(),
+            serde_json::json!({"useDefault": true})
+        );
+
+        let parsed: Reminders = serde_json::from_str(
+            r#"{"useDefault": false, "overrides": [{"method": "sms", "minutes": 5}]}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.overrides[0].method, ReminderMethod::Unsupported);
+        assert!(parsed.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            Reminders::with_overrides(vec![Reminder::popup(0), Reminder::email(40320)])
+                .validate()
+                .is_ok()
+        );
+        assert!(Reminders::with_overrides(vec![Reminder::popup(40321)])
+            .validate()
+            .is_err());
+        assert!(Reminders::with_overrides(vec![Reminder::popup(1); 6])
+            .validate()
+            .is_err());
+        let both = Reminders {
+            use_default: true,
+            overrides: vec![Reminder::popup(10)],
+        };
+        assert!(both.validate().is_err());
+
+        let mut errors = ValidationErrors::new();
+        validate_reminders(
+            &Reminders {
+                use_default: true,
+                overrides: vec![Reminder::popup(40321); 6],
+            },
+            &mut errors,
+        );
+        assert_eq!(errors.len(), 8);
+        assert!(errors.contains("reminders"));
+        assert!(errors.contains("reminders.overrides"));
+        assert!(errors.contains("reminders.overrides[5]"));
+    }
+
+    #[test]
+    fn test_schedule() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+        let defaults = [Reminder::popup(30)];
+        let overrides = Reminders::with_overrides(vec![Reminder::popup(10), Reminder::email(1440)]);
+
+        let scheduled = schedule(overrides.effective(&defaults), start);
+        assert_eq!(scheduled.len(), 2);
+        assert_eq!(scheduled[0].method, ReminderMethod::Email);
+        assert_eq!(
+            scheduled[0].at,
+            Utc.with_ymd_and_hms(2023, 12, 31, 9, 0, 0).unwrap()
+        );
+        assert_eq!(
+            scheduled[1].at,
+            Utc.with_ymd_and_hms(2024, 1, 1, 8, 50, 0).unwrap()
+        );
+
+        let scheduled = schedule(Reminders::use_default().effective(&defaults), start);
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(
+            scheduled[0].at,
+            Utc.with_ymd_and_hms(2024, 1, 1, 8, 30, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,479 @@
+use crate::freebusy::FreeBusyResponse;
+use crate::interval::{Interval, IntervalSet};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use std::sync::Arc;
+
+/// 参加者の勤務時間（参加者のタイムゾーンでの現地時刻）
+///
+/// `end` が `start` 以前の場合は日をまたぐ勤務時間（夜勤など）として扱い、`days` は開始日の曜日を表します。
+#[derive(Debug, Clone)]
+pub struct WorkingHours {
+    pub time_zone: Tz,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub days: Vec<Weekday>,
+}
+
+impl WorkingHours {
+    /// 月曜〜金曜の勤務時間を作成します
+    pub fn new(time_zone: Tz, start: NaiveTime, end: NaiveTime) -> Self {
+        WorkingHours {
+            time_zone,
+            start,
+            end,
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+        }
+    }
+
+    pub fn with_days(mut self, days: Vec<Weekday>) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// 勤務時間が日をまたぐ場合に true を返します
+    pub fn is_overnight(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// 指定期間と重なる勤務時間帯をUTCで返します
+    pub fn availability(&self, window: Interval) -> IntervalSet {
+        let (from, to) = (window.start, window.end);
+        let mut spans = IntervalSet::new();
+        // タイムゾーンの差で日付がずれるため、前後1日ずつ広げて走査する
+        let mut date = from.with_timezone(&self.time_zone).date_naive() - Duration::days(1);
+        let last = to.with_timezone(&self.time_zone).date_naive() + Duration::days(1);
+        while date <= last {
+            if self.days.contains(&date.weekday()) {
+                // 夏時間の切り替えで存在しない現地時刻は、その日の勤務時間として扱わない
+                let start = self
+                    .time_zone
+                    .from_local_datetime(&date.and_time(self.start))
+                    .earliest();
+                let end_date = if self.is_overnight() {
+                    date + Duration::days(1)
+                } else {
+                    date
+                };
+                let end = self
+                    .time_zone
+                    .from_local_datetime(&end_date.and_time(self.end))
+                    .latest();
+                if let (Some(start), Some(end)) = (start, end) {
+                    let span = Interval::new(start.with_timezone(&Utc), end.with_timezone(&Utc))
+                        .and_then(|span| span.intersection(&window));
+                    if let Some(span) = span {
+                        spans.insert(span);
+                    }
+                }
+            }
+            date += Duration::days(1);
+        }
+        spans
+    }
+}
+
+/// 空き時間検索の対象となる参加者
+#[derive(Debug, Clone)]
+pub struct SlotAttendee {
+    pub calendar_id: String,
+    pub working_hours: WorkingHours,
+}
+
+impl SlotAttendee {
+    pub fn new(calendar_id: impl Into<String>, working_hours: WorkingHours) -> Self {
+        SlotAttendee {
+            calendar_id: calendar_id.into(),
+            working_hours,
+        }
+    }
+}
+
+/// 候補となる時間枠
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateSlot {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub score: f64,
+}
+
+/// 候補枠の評価関数。値が大きいほど上位に並びます
+pub type SlotScorer = Arc<dyn Fn(&CandidateSlot) -> f64 + Send + Sync>;
+
+/// 全参加者が参加可能な時間枠を探すための条件
+#[derive(Clone)]
+pub struct SlotSearch {
+    pub attendees: Vec<SlotAttendee>,
+    pub duration: Duration,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    /// 前後の予定との間に最低限空ける時間
+    pub buffer: Duration,
+    /// 候補枠の開始時刻の刻み幅
+    pub step: Duration,
+    pub max_results: usize,
+    scorer: Option<SlotScorer>,
+}
+
+impl std::fmt::Debug for SlotSearch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlotSearch")
+            .field("attendees", &self.attendees)
+            .field("duration", &self.duration)
+            .field("window_start", &self.window_start)
+            .field("window_end", &self.window_end)
+            .field("buffer", &self.buffer)
+            .field("step", &self.step)
+            .field("max_results", &self.max_results)
+            .field("scorer", &self.scorer.is_some())
+            .finish()
+    }
+}
+
+impl SlotSearch {
+    pub fn new(window_start: DateTime<Utc>, window_end: DateTime<Utc>, duration: Duration) -> Self {
+        SlotSearch {
+            attendees: Vec::new(),
+            duration,
+            window_start,
+            window_end,
+            buffer: Duration::zero(),
+            step: Duration::minutes(15),
+            max_results: 10,
+            scorer: None,
+        }
+    }
+
+    pub fn with_attendee(mut self, attendee: SlotAttendee) -> Self {
+        self.attendees.push(attendee);
+        self
+    }
+
+    pub fn with_buffer(mut self, buffer: Duration) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    pub fn with_step(mut self, step: Duration) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    pub fn with_scorer(
+        mut self,
+        scorer: impl Fn(&CandidateSlot) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        self.scorer = Some(Arc::new(scorer));
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.attendees.is_empty() {
+            return Err("参加者が指定されていません".to_string());
+        }
+        if self.duration <= Duration::zero() {
+            return Err("会議時間は正の値である必要があります".to_string());
+        }
+        if self.step <= Duration::zero() {
+            return Err("刻み幅は正の値である必要があります".to_string());
+        }
+        if self.buffer < Duration::zero() {
+            return Err("バッファは0以上である必要があります".to_string());
+        }
+        if self.window_end <= self.window_start {
+            return Err("検索期間の終了は開始より後である必要があります".to_string());
+        }
+        if let Some(attendee) = self
+            .attendees
+            .iter()
+            .find(|a| a.working_hours.start == a.working_hours.end)
+        {
+            return Err(format!(
+                "勤務時間の開始と終了が同じです: {}",
+                attendee.calendar_id
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn calendar_ids(&self) -> Vec<String> {
+        self.attendees
+            .iter()
+            .map(|a| a.calendar_id.clone())
+            .collect()
+    }
+}
+
+/// 指定した現地時刻に近い枠ほど高く評価する評価関数を返します
+pub fn prefer_time_of_day(time_zone: Tz, preferred: NaiveTime) -> impl Fn(&CandidateSlot) -> f64 {
+    move |slot| {
+        let local = slot.start.with_timezone(&time_zone).time();
+        let diff = (local.num_seconds_from_midnight() as i64
+            - preferred.num_seconds_from_midnight() as i64)
+            .abs();
+        -(diff as f64) / 60.0
+    }
+}
+
+/// freeBusy の結果から、全参加者が参加可能な候補枠を評価順に返します
+pub fn find_free_slots(search: &SlotSearch, free_busy: &FreeBusyResponse) -> Vec<CandidateSlot> {
+    let Some(window) = Interval::new(search.window_start, search.window_end) else {
+        return Vec::new();
+    };
+    let mut working = IntervalSet::from_intervals([window]);
+    let mut busy = IntervalSet::new();
+    for attendee in &search.attendees {
+        working = working.intersection(&attendee.working_hours.availability(window));
+        busy = busy.union(
+            &free_busy
+                .busy_for(&attendee.calendar_id)
+                .iter()
+                .filter_map(|p| p.to_interval())
+                .map(|i| i.expand(search.buffer, search.buffer))
+                .collect(),
+        );
+    }
+    let common = working.subtract(&busy);
+
+    let mut slots = Vec::new();
+    for free in &common {
+        // 枠の開始時刻は、全員の勤務時間が重なる時間帯の開始から刻み幅ごとに並べる
+        let origin = working
+            .iter()
+            .find(|span| span.contains(free.start))
+            .map_or(free.start, |span| span.start);
+        let mut slot_start = align_up(free.start, origin, search.step);
+        while slot_start + search.duration <= free.end {
+            let mut slot = CandidateSlot {
+                start: slot_start,
+                end: slot_start + search.duration,
+                score: 0.0,
+            };
+            if let Some(scorer) = &search.scorer {
+                slot.score = scorer(&slot);
+            }
+            slots.push(slot);
+            slot_start += search.step;
+        }
+    }
+
+    // 同点の場合は時系列順を保つ（安定ソート）
+    slots.sort_by(|a, b| b.score.total_cmp(&a.score));
+    slots.truncate(search.max_results);
+    slots
+}
+
+/// `origin` から刻み幅の倍数だけ進んだ時刻のうち、`dt` 以降で最も早いものを返します
+fn align_up(dt: DateTime<Utc>, origin: DateTime<Utc>, step: Duration) -> DateTime<Utc> {
+    let step_secs = step.num_seconds().max(1);
+    let offset = dt - origin;
+    let rem = offset.num_seconds().rem_euclid(step_secs);
+    let aligned = origin + Duration::seconds(offset.num_seconds() - rem);
+    if aligned == dt {
+        dt
+    } else {
+        aligned + Duration::seconds(step_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::freebusy::{FreeBusyCalendar, TimePeriod};
+    use std::collections::HashMap;
+
+    fn utc(d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        // 2024-01-01 は月曜日
+        Utc.with_ymd_and_hms(2024, 1, d, h, m, 0).unwrap()
+    }
+
+    fn hours(start: u32, end: u32) -> (NaiveTime, NaiveTime) {
+        (
+            NaiveTime::from_hms_opt(start, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(end, 0, 0).unwrap(),
+        )
+    }
+
+    fn response(busy: Vec<(&str, Vec<TimePeriod>)>) -> FreeBusyResponse {
+        let mut calendars = HashMap::new();
+        for (id, periods) in busy {
+            calendars.insert(
+                id.to_string(),
+                FreeBusyCalendar {
+                    busy: periods,
+                    errors: vec![],
+                },
+            );
+        }
+        FreeBusyResponse {
+            time_min: utc(1, 0, 0),
+            time_max: utc(8, 0, 0),
+            calendars,
+        }
+    }
+
+    #[test]
+    fn test_working_hours_across_time_zones() {
+        let (s, e) = hours(9, 18);
+        // 東京 9:00-18:00 = UTC 0:00-9:00、ロンドン 9:00-18:00 = UTC 9:00-18:00 → 重ならない
+        let search = SlotSearch::new(utc(1, 0, 0), utc(2, 0, 0), Duration::minutes(30))
+            .with_attendee(SlotAttendee::new(
+                "tokyo",
+                WorkingHours::new(chrono_tz::Asia::Tokyo, s, e),
+            ))
+            .with_attendee(SlotAttendee::new(
+                "london",
+                WorkingHours::new(chrono_tz::Europe::London, s, e),
+            ));
+        assert!(find_free_slots(&search, &response(vec![])).is_empty());
+
+        // 東京 9:00-19:00 なら UTC 9:00-10:00 が重なる
+        let (s2, e2) = hours(9, 19);
+        let search = SlotSearch::new(utc(1, 0, 0), utc(2, 0, 0), Duration::minutes(30))
+            .with_step(Duration::minutes(30))
+            .with_attendee(SlotAttendee::new(
+                "tokyo",
+                WorkingHours::new(chrono_tz::Asia::Tokyo, s2, e2),
+            ))
+            .with_attendee(SlotAttendee::new(
+                "london",
+                WorkingHours::new(chrono_tz::Europe::London, s, e),
+            ));
+        let slots = find_free_slots(&search, &response(vec![]));
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].start, utc(1, 9, 0));
+        assert_eq!(slots[1].start, utc(1, 9, 30));
+    }
+
+    #[test]
+    fn test_busy_and_buffer_are_excluded() {
+        let (s, e) = hours(9, 12);
+        let search = SlotSearch::new(utc(1, 0, 0), utc(2, 0, 0), Duration::hours(1))
+            .with_step(Duration::minutes(30))
+            .with_buffer(Duration::minutes(15))
+            .with_attendee(SlotAttendee::new(
+                "a",
+                WorkingHours::new(chrono_tz::UTC, s, e),
+            ));
+        let resp = response(vec![(
+            "a",
+            vec![TimePeriod {
+                start: utc(1, 10, 0),
+                end: utc(1, 10, 30),
+            }],
+        )]);
+        let slots = find_free_slots(&search, &resp);
+        // 空き: 9:00-9:45, 10:45-12:00 → 1時間枠は 11:00 開始のみ
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].start, utc(1, 11, 0));
+    }
+
+    #[test]
+    fn test_weekends_are_skipped() {
+        let (s, e) = hours(9, 10);
+        // 2024-01-06, 07 は土日
+        let search = SlotSearch::new(utc(6, 0, 0), utc(8, 0, 0), Duration::hours(1)).with_attendee(
+            SlotAttendee::new("a", WorkingHours::new(chrono_tz::UTC, s, e)),
+        );
+        assert!(find_free_slots(&search, &response(vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_scorer_ranks_slots() {
+        let (s, e) = hours(9, 17);
+        let search = SlotSearch::new(utc(1, 0, 0), utc(2, 0, 0), Duration::hours(1))
+            .with_step(Duration::hours(1))
+            .with_max_results(3)
+            .with_attendee(SlotAttendee::new(
+                "a",
+                WorkingHours::new(chrono_tz::UTC, s, e),
+            ))
+            .with_scorer(prefer_time_of_day(
+                chrono_tz::UTC,
+                NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+            ));
+        let slots = find_free_slots(&search, &response(vec![]));
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0].start, utc(1, 14, 0));
+        assert_eq!(slots[1].start, utc(1, 13, 0));
+        assert_eq!(slots[2].start, utc(1, 15, 0));
+    }
+
+    #[test]
+    fn test_slots_align_to_working_hours_start() {
+        // コルカタ 9:00-11:00 = UTC 3:30-5:30。エポック基準ではなく勤務開始から1時間刻みで並ぶ
+        let (s, e) = hours(9, 11);
+        let search = SlotSearch::new(utc(1, 0, 0), utc(2, 0, 0), Duration::hours(1))
+            .with_step(Duration::hours(1))
+            .with_attendee(SlotAttendee::new(
+                "a",
+                WorkingHours::new(chrono_tz::Asia::Kolkata, s, e),
+            ));
+        let slots = find_free_slots(&search, &response(vec![]));
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].start, utc(1, 3, 30));
+        assert_eq!(slots[1].start, utc(1, 4, 30));
+
+        // 予定の後は次の刻みまで切り上げる
+        let resp = response(vec![(
+            "a",
+            vec![TimePeriod {
+                start: utc(1, 3, 30),
+                end: utc(1, 3, 45),
+            }],
+        )]);
+        let slots = find_free_slots(&search.with_step(Duration::minutes(30)), &resp);
+        assert_eq!(slots[0].start, utc(1, 4, 0));
+    }
+
+    #[test]
+    fn test_overnight_working_hours() {
+        // 月曜 22:00 〜 火曜 2:00 の夜勤
+        let (s, e) = hours(22, 2);
+        let working = WorkingHours::new(chrono_tz::UTC, s, e).with_days(vec![Weekday::Mon]);
+        assert!(working.is_overnight());
+        let search = SlotSearch::new(utc(1, 0, 0), utc(3, 0, 0), Duration::hours(1))
+            .with_step(Duration::hours(1))
+            .with_attendee(SlotAttendee::new("a", working));
+        assert!(search.validate().is_ok());
+        let slots = find_free_slots(&search, &response(vec![]));
+        let starts: Vec<_> = slots.iter().map(|s| s.start).collect();
+        assert_eq!(
+            starts,
+            vec![utc(1, 22, 0), utc(1, 23, 0), utc(2, 0, 0), utc(2, 1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let search = SlotSearch::new(utc(1, 0, 0), utc(2, 0, 0), Duration::hours(1));
+        assert!(search.validate().is_err());
+        let (s, e) = hours(9, 17);
+        let search = search.with_attendee(SlotAttendee::new(
+            "a",
+            WorkingHours::new(chrono_tz::UTC, s, e),
+        ));
+        assert!(search.validate().is_ok());
+        let empty_hours = search.clone().with_attendee(SlotAttendee::new(
+            "b",
+            WorkingHours::new(chrono_tz::UTC, s, s),
+        ));
+        assert!(empty_hours.validate().is_err());
+        let search = SlotSearch {
+            window_end: utc(1, 0, 0),
+            ..search
+        };
+        assert!(search.validate().is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{Duration, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 設定ID: カレンダーの所有者のタイムゾーン（IANA名）
+pub const TIME_ZONE: &str = "timezone";
+/// 設定ID: 週の始まりの曜日（"0" は日曜、"1" は月曜、"6" は土曜）
+pub const WEEK_START: &str = "weekStart";
+/// 設定ID: 24時間表記を使うかどうか
+pub const FORMAT_24_HOUR_TIME: &str = "format24HourTime";
+/// 設定ID: ユーザーのロケール
+pub const LOCALE: &str = "locale";
+/// 設定ID: 新しいイベントの既定の長さ（分）
+pub const DEFAULT_EVENT_LENGTH: &str = "defaultEventLength";
+/// 設定ID: 週末を非表示にするかどうか
+pub const HIDE_WEEKENDS: &str = "hideWeekends";
+
+/// ユーザー設定の1項目（settings.get のレスポンス）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setting {
+    pub id: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+}
+
+/// settings.list のレスポンス
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingList {
+    #[serde(default)]
+    pub items: Vec<Setting>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_sync_token: Option<String>,
+}
+
+/// 型付きのユーザー設定
+///
+/// 設定されていない項目と解釈できない値は None になります。すべての設定の元の値は `raw` に保持されます。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserSettings {
+    pub time_zone: Option<String>,
+    pub week_start: Option<Weekday>,
+    pub format_24_hour_time: Option<bool>,
+    pub locale: Option<String>,
+    pub default_event_length: Option<Duration>,
+    pub hide_weekends: Option<bool>,
+    pub raw: BTreeMap<String, String>,
+}
+
+impl UserSettings {
+    pub fn from_settings(settings: impl IntoIterator<Item = Setting>) -> Self {
+        let raw: BTreeMap<String, String> = settings.into_iter().map(|s| (s.id, s.value)).collect();
+        let get = |id: &str| raw.get(id).map(String::as_str);
+        UserSettings {
+            time_zone: get(TIME_ZONE).map(str::to_string),
+            week_start: get(WEEK_START).and_then(|v| match v {
+                "0" => Some(Weekday::Sun),
+                "1" => Some(Weekday::Mon),
+                "6" => Some(Weekday::Sat),
+                _ => None,
+            }),
+            format_24_hour_time: get(FORMAT_24_HOUR_TIME).and_then(parse_bool),
+            locale: get(LOCALE).map(str::to_string),
+            default_event_length: get(DEFAULT_EVENT_LENGTH)
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(Duration::minutes),
+            hide_weekends: get(HIDE_WEEKENDS).and_then(parse_bool),
+            raw,
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_settings() {
+        let json = r#"{
+            "kind": "calendar#settings",
+            "items": [
+                {"kind": "calendar#setting", "etag": "\"1\"", "id": "timezone", "value": "Asia/Tokyo"},
+                {"id": "weekStart", "value": "1"},
+                {"id": "format24HourTime", "value": "true"},
+                {"id": "locale", "value": "ja"},
+                {"id": "defaultEventLength", "value": "30"},
+                {"id": "hideWeekends", "value": "maybe"},
+                {"id": "autoAddHangouts", "value": "false"}
+            ]
+        }"#;
+        let list: SettingList = serde_json::from_str(json).unwrap();
+        let settings = UserSettings::from_settings(list.items);
+        assert_eq!(settings.time_zone.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(settings.week_start, Some(Weekday::Mon));
+        assert_eq!(settings.format_24_hour_time, Some(true));
+        assert_eq!(settings.locale.as_deref(), Some("ja"));
+        assert_eq!(settings.default_event_length, Some(Duration::minutes(30)));
+        assert_eq!(settings.hide_weekends, None);
+        assert_eq!(settings.raw["hideWeekends"], "maybe");
+        assert_eq!(settings.raw["autoAddHangouts"], "false");
+    }
+}
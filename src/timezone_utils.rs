@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 
 /// タイムゾーン変換に関するエラー
 #[derive(Debug)]
@@ -21,53 +22,30 @@ impl std::fmt::Display for TimezoneError {
 impl std::error::Error for TimezoneError {}
 
 /// タイムゾーン文字列が有効かどうかを検証します
+///
+/// IANAタイムゾーンデータベースの名前（"Asia/Tokyo"、"America/Argentina/Buenos_Aires"、"Etc/UTC" など）と
+/// GMT+/-XX:XX 形式を受け付けます。
 pub fn validate_timezone(tz: &str) -> bool {
-    if tz == "UTC" {
-        return true;
-    }
+    is_gmt_offset(tz) || tz.parse::<Tz>().is_ok()
+}
 
-    // Region/City 形式 (例: "Asia/Tokyo")
-    if tz.contains('/') {
-        let parts: Vec<&str> = tz.split('/').collect();
-        if parts.len() == 2 {
-            let region = parts[0];
-            let city = parts[1];
-            // 有効な地域名のチェック
-            let valid_regions = [
-                "Asia",
-                "America",
-                "Europe",
-                "Africa",
-                "Australia",
-                "Pacific",
-                "Atlantic",
-                "Indian",
-            ];
-            if valid_regions.contains(&region) && !city.is_empty() {
-                return true;
-            }
-        }
+/// GMT+/-XX:XX 形式かどうかを返します
+fn is_gmt_offset(tz: &str) -> bool {
+    let Some(offset) = tz.strip_prefix("GMT") else {
         return false;
-    }
-
-    // GMT+/-XX:XX 形式
-    if let Some(offset) = tz.strip_prefix("GMT") {
-        // +09:00 形式のチェック
-        if offset.len() == 6
-            && (offset.starts_with('+') || offset.starts_with('-'))
-            && &offset[3..4] == ":"
-            && offset[1..3].chars().all(|c| c.is_ascii_digit())
-            && offset[4..6].chars().all(|c| c.is_ascii_digit())
+    };
+    // +09:00 形式のチェック
+    if offset.len() == 6
+        && (offset.starts_with('+') || offset.starts_with('-'))
+        && &offset[3..4] == ":"
+        && offset[1..3].chars().all(|c| c.is_ascii_digit())
+        && offset[4..6].chars().all(|c| c.is_ascii_digit())
+    {
+        if let (Ok(hours), Ok(minutes)) = (offset[1..3].parse::<i32>(), offset[4..6].parse::<i32>())
         {
-            if let (Ok(hours), Ok(minutes)) =
-                (offset[1..3].parse::<i32>(), offset[4..6].parse::<i32>())
-            {
-                return (0..=23).contains(&hours) && (0..=59).contains(&minutes);
-            }
+            return (0..=23).contains(&hours) && (0..=59).contains(&minutes);
         }
-        return false;
     }
-
     false
 }
 
@@ -82,35 +60,60 @@ pub fn convert_to_timezone(dt: DateTime<Utc>, timezone: &str) -> Result<String,
         return Ok(dt.format("%Y-%m-%dT%H:%M:%SZ").to_string());
     }
 
-    // GMT+/-XX:XX形式の場合は、オフセットを解析して適用
-    if let Some(offset) = timezone.strip_prefix("GMT") {
-        // +09:00 形式のチェック
-        if offset.len() == 6
-            && (offset.starts_with('+') || offset.starts_with('-'))
-            && &offset[3..4] == ":"
-            && offset[1..3].chars().all(|c| c.is_ascii_digit())
-            && offset[4..6].chars().all(|c| c.is_ascii_digit())
-        {
-            if let (Ok(hours), Ok(minutes)) =
-                (offset[1..3].parse::<i32>(), offset[4..6].parse::<i32>())
-            {
-                if (0..=23).contains(&hours) && (0..=59).contains(&minutes) {
-                    let local_dt = dt.format("%Y-%m-%dT%H:%M:%S").to_string();
-                    return Ok(format!("{}{}", local_dt, offset));
-                }
-            }
-        }
-        return Err(TimezoneError::InvalidTimezone(timezone.to_string()));
+    // GMT+/-XX:XX形式の場合は、オフセットをそのまま付与
+    if is_gmt_offset(timezone) {
+        let local_dt = dt.format("%Y-%m-%dT%H:%M:%S").to_string();
+        return Ok(format!("{}{}", local_dt, &timezone[3..]));
     }
 
-    // Region/City形式の場合は、そのままGoogle Calendar APIが解釈できる形式で返す
-    if timezone.contains('/') {
-        let parts: Vec<&str> = timezone.split('/').collect();
-        if parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-            return Ok(dt.format("%Y-%m-%dT%H:%M:%S").to_string());
-        }
+    // IANA名の場合は、そのままGoogle Calendar APIが解釈できる形式で返す
+    Ok(dt.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// GMT+/-XX:XX 形式の文字列を固定オフセットに変換します
+fn parse_gmt_offset(timezone: &str) -> Option<FixedOffset> {
+    if !is_gmt_offset(timezone) {
+        return None;
+    }
+    let offset = &timezone[3..];
+    let hours: i32 = offset[1..3].parse().ok()?;
+    let minutes: i32 = offset[4..6].parse().ok()?;
+    let secs = hours * 3600 + minutes * 60;
+    if offset.starts_with('-') {
+        FixedOffset::west_opt(secs)
+    } else {
+        FixedOffset::east_opt(secs)
     }
-    Err(TimezoneError::InvalidTimezone(timezone.to_string()))
+}
+
+/// 指定タイムゾーンでの現地日時をUTCに変換します
+///
+/// 夏時間の切り替えで現地時刻が重複する場合は早い方を採用します。
+pub fn local_to_utc(local: NaiveDateTime, timezone: &str) -> Result<DateTime<Utc>, TimezoneError> {
+    if timezone == "UTC" {
+        return Ok(Utc.from_utc_datetime(&local));
+    }
+
+    let resolved = if let Some(offset) = parse_gmt_offset(timezone) {
+        offset
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    } else {
+        let tz: chrono_tz::Tz = timezone
+            .parse()
+            .map_err(|_| TimezoneError::InvalidTimezone(timezone.to_string()))?;
+        tz.from_local_datetime(&local)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+
+    resolved.ok_or_else(|| {
+        TimezoneError::ConversionError(format!(
+            "{} は {} に存在しない現地時刻です",
+            local, timezone
+        ))
+    })
 }
 
 #[cfg(test)]
@@ -125,6 +128,12 @@ mod tests {
         assert!(validate_timezone("GMT+09:00"));
         assert!(!validate_timezone("Invalid/Zone"));
         assert!(!validate_timezone("GMT+9"));
+        assert!(validate_timezone("America/Argentina/Buenos_Aires"));
+        assert!(validate_timezone("America/Indiana/Indianapolis"));
+        assert!(validate_timezone("Etc/UTC"));
+        assert!(validate_timezone("Antarctica/Troll"));
+        assert!(validate_timezone("GMT"));
+        assert!(!validate_timezone("Asia/Nowhere"));
     }
 
     #[test]
@@ -144,6 +153,32 @@ mod tests {
         assert!(!result.contains("Z")); // タイムゾーン情報なし（Google Calendar APIが解釈）
     }
 
+    #[test]
+    fn test_local_to_utc() {
+        let local = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(local_to_utc(local, "Asia/Tokyo").unwrap(), expected);
+        assert_eq!(local_to_utc(local, "GMT+09:00").unwrap(), expected);
+        assert_eq!(
+            local_to_utc(local, "UTC").unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap()
+        );
+        assert!(local_to_utc(local, "Asia/Nowhere").is_err());
+
+        // 夏時間開始で存在しない時刻
+        let gap = chrono::NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert!(matches!(
+            local_to_utc(gap, "America/New_York"),
+            Err(TimezoneError::ConversionError(_))
+        ));
+    }
+
     #[test]
     fn test_invalid_timezone() {
         let dt = Utc::now();
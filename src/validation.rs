@@ -0,0 +1,163 @@
+use std::fmt;
+
+/// 1つのフィールドに対する検証エラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// フィールドのパス（例: "attendees[1].email"）。特定のフィールドに属さない場合は空文字列
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// 検証で見つかったエラーの一覧
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        ValidationErrors::default()
+    }
+
+    /// 指定したフィールドのエラー1件からなる一覧を作成します
+    pub fn single(path: impl Into<String>, message: impl Into<String>) -> Self {
+        let mut errors = ValidationErrors::new();
+        errors.add(path, message);
+        errors
+    }
+
+    pub fn add(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    /// `result` がエラーの場合、`path` のエラーとして追加します
+    pub fn check(&mut self, path: impl Into<String>, result: Result<(), String>) {
+        if let Err(message) = result {
+            self.add(path, message);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// 指定したパスのエラーがある場合に true を返します
+    pub fn contains(&self, path: &str) -> bool {
+        self.errors.iter().any(|e| e.path == path)
+    }
+
+    /// エラーがなければ `Ok(())` を返します
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl From<String> for ValidationErrors {
+    /// フィールドを特定しないエラー1件として扱います
+    fn from(message: String) -> Self {
+        ValidationErrors::single("", message)
+    }
+}
+
+impl From<&str> for ValidationErrors {
+    fn from(message: &str) -> Self {
+        ValidationErrors::single("", message)
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = FieldError;
+    type IntoIter = std::vec::IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+/// メールアドレスとして最低限の形式を満たしているかを判定します
+///
+/// ローカル部とドメインが `@` で区切られ、ドメインに `.` を含み、空白を含まないことのみを確認します。
+pub fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain.contains('.')
+        && !domain.contains('@')
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_and_result() {
+        let mut errors = ValidationErrors::new();
+        assert_eq!(errors.clone().into_result(), Ok(()));
+
+        errors.add("end", "終了日時は開始日時より後である必要があります");
+        errors.check("summary", Ok(()));
+        errors.check("", Err("不正です".to_string()));
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains("end"));
+        assert_eq!(
+            errors.to_string(),
+            "end: 終了日時は開始日時より後である必要があります; 不正です"
+        );
+        assert!(errors.into_result().is_err());
+    }
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email("alice@example.com"));
+        assert!(is_valid_email("room+1@resource.calendar.google.com"));
+        assert!(!is_valid_email("alice"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("alice@localhost"));
+        assert!(!is_valid_email("alice@example.com."));
+        assert!(!is_valid_email("alice smith@example.com"));
+    }
+}